@@ -44,6 +44,10 @@ pub mod util {
 	pub use crate::build_util::*;
 }
 
+/// Unit tests
+#[cfg(test)]
+mod tests;
+
 
 
 //////
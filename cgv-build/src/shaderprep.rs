@@ -74,7 +74,9 @@ where
 // Functions
 //
 
-/// Prepare the shaders in the given directory, skipping indicated directory sub-trees.
+/// Prepare the shaders in the given directory, skipping indicated directory sub-trees. Shaders are only re-packaged if
+/// they or any of the modules they (transitively) import changed since the last build, and *Cargo* is instructed to
+/// re-run the build script whenever one of them does.
 pub fn prepareShaders (
 	buildSetup: &Setup, sourceTypes: Option<&[shader::WgpuSourceType]>,
 	shaderDirectory: impl AsRef<Path>, skipSubDirs: Option<&[impl AsRef<Path>]>
@@ -117,6 +119,18 @@ pub fn prepareShaders (
 		.addSearchPaths(buildSetup.shaderPath())
 		.build()?;
 
+	// Packages are only recompiled if any of their inputs changed since the last build. To find out, we keep a cache in
+	// the output directory which remembers the files each package was compiled from (i.e. including all transitively
+	// imported modules), with entries keyed by everything else that influences compilation.
+	let buildCache = shader::cache::Cache::inDirectory(getCargoOutDir().join("shader-cache"));
+	let cacheKey = |srcPath: &Path| {
+		let key = sourceTypes.iter().fold(
+			shader::cache::KeyBuilder::new().context(&slangContext), |key, &sourceType| key.sourceType(sourceType)
+		);
+		buildSetup.shaderPath().iter().fold(key, |key, dir| key.data(dir.to_string_lossy().as_bytes()))
+			.data(srcPath.to_string_lossy().as_bytes()).build()
+	};
+
 	// Recurse through provided shader directory and package each .slang shader encountered that is not in a skipped
 	// subdirectory
 	util::fs::doRecursively(absoluteShaderDir, |srcPath, destStack, fileType|
@@ -146,13 +160,35 @@ pub fn prepareShaders (
 			}
 			let tgtPath = targetDir.join(destStack).with_extension("spk");
 			let tgtParent = targetDir.join(destStackParent);
-			dependOnFile(srcPath);
 			fs::create_dir_all(tgtParent)?;
-			let package = shader::Package::fromSourceFileMultipleTypes(
-				sourceTypes, &slangContext, srcPath, None
-			)?;
-			package.writeToFile(&tgtPath)?;
-			dependOnGeneratedFile(tgtPath)?;
+
+			// Package the shader unless it is up-to-date
+			let (written, fileDependencies) = updatePackage(&buildCache, &cacheKey(srcPath), &tgtPath, || {
+				let module = slangContext.compile(srcPath).map_err(
+					|err| withRenderedDiagnostics(err, srcPath, buildSetup.shaderPath())
+				)?;
+				let package = shader::Package::fromModuleMultipleTypes(
+					sourceTypes, &slangContext, &module, None
+				).map_err(|err| withRenderedDiagnostics(err, srcPath, buildSetup.shaderPath()))?;
+
+				// The cache key only covers the path of the shader, so its contents must be tracked as well
+				let mut fileDependencies = module.fileDependencies();
+				fileDependencies.push(srcPath.to_owned());
+				fileDependencies.sort();
+				fileDependencies.dedup();
+				Ok((package, fileDependencies))
+			})?;
+			if written {
+				dependOnGeneratedFile(&tgtPath)?;
+			}
+			else {
+				dependOnFile(&tgtPath);
+			}
+
+			// Make Cargo re-run the build script if the shader or any module it imports changes
+			for filepath in fileDependencies {
+				dependOnFile(filepath);
+			}
 			Ok(())
 		}
 		else {
@@ -164,6 +200,60 @@ pub fn prepareShaders (
 	Ok(())
 }
 
+/// Make sure the package file at the given target path is up-to-date, consulting the given build cache to find out
+/// whether the shader needs to be re-packaged.
+///
+/// # Arguments
+///
+/// * `buildCache` – The cache remembering the packages of previous builds and the files they were compiled from.
+/// * `key` – The key of the shader in the cache.
+/// * `tgtPath` – The path of the package file.
+/// * `build` – Callback packaging the shader, returning the package along with the files it was compiled from.
+///
+/// # Returns
+///
+/// Whether the package file was (re-)written, along with the files the package was compiled from.
+pub(crate) fn updatePackage (
+	buildCache: &shader::cache::Cache, key: &shader::cache::Key, tgtPath: &Path,
+	build: impl FnOnce()->Result<(shader::Package, Vec<PathBuf>)>
+) -> Result<(bool, Vec<PathBuf>)>
+{
+	match buildCache.getWithFileDependencies(key)
+	{
+		Some((package, fileDependencies)) => {
+			// Leave existing packages untouched if they are identical to the cached one, so that crates embedding them
+			// don't rebuild needlessly
+			let bytes = package.serialize();
+			if fs::read(tgtPath).is_ok_and(|existing| existing == bytes) {
+				return Ok((false, fileDependencies));
+			}
+			fs::write(tgtPath, bytes)?;
+			Ok((true, fileDependencies))
+		},
+		None => {
+			let (package, fileDependencies) = build()?;
+			package.writeToFile(tgtPath)?;
+			if let Err(err) = buildCache.insert(key, &package, &fileDependencies) {
+				println!("cargo::warning=Failed to cache shader package for '{}': {err}", tgtPath.display());
+			}
+			Ok((true, fileDependencies))
+		}
+	}
+}
+
+/// Turn a shader compilation error into an [`anyhow::Error`] that – in case the error carries structured
+/// [diagnostics](shader::compile::Diagnostics) – contains a human-readable report with excerpts from the offending
+/// source files, suitable for displaying in the panic message of a failed build script.
+fn withRenderedDiagnostics (
+	err: impl std::error::Error+Send+Sync+'static, srcPath: &Path, shaderPath: &[PathBuf]
+) -> anyhow::Error
+{
+	let Some(diagnostics) = shader::compile::diagnosticsOf(&err) else {
+		return err.into();
+	};
+	anyhow!("failed to compile shader '{}':\n\n{}\n", srcPath.display(), diagnostics.renderFromFiles(shaderPath))
+}
+
 ///
 pub fn generateShaderEnvironment<CompileContextBuilder> (
 	contextBuilder: CompileContextBuilder, environmentFilename: impl AsRef<Path>, addModulesRoot: impl AsRef<Path>,
@@ -0,0 +1,8 @@
+
+//////
+//
+// Module definitions
+//
+
+/// Tests for the `shaderprep` module.
+mod shaderprep;
@@ -0,0 +1,83 @@
+
+//////
+//
+// Imports
+//
+
+// Standard library
+use std::{fs, path::PathBuf};
+
+// CGV-rs core libraries
+use cgv_shader::{self as shader, cache::{Cache, KeyBuilder}, ProgramInstance, WgpuSourceType};
+
+// Local imports
+use crate::shaderprep::updatePackage;
+
+
+
+//////
+//
+// Helpers
+//
+
+/// Create a unique, initially empty directory for a test to work in.
+fn tempDir () -> PathBuf {
+	let dir = std::env::temp_dir().join(format!("cgv-build-test-shaderprep{}", shader::uniqueAnonymousName()));
+	fs::create_dir_all(&dir).unwrap();
+	dir
+}
+
+/// Create a dummy package.
+fn dummyPackage (code: &[u8]) -> shader::Package {
+	shader::Package::fromSingleInstance(
+		WgpuSourceType::WGSL, ProgramInstance::generic(code.to_vec()), Some("dummy".into())
+	)
+}
+
+
+
+//////
+//
+// Tests
+//
+
+#[test]
+fn test_updatePackage ()
+{
+	let dir = tempDir();
+	let cache = Cache::inDirectory(dir.join("cache"));
+	let source = dir.join("test.slang");
+	fs::write(&source, "// version 1").unwrap();
+	let target = dir.join("test.spk");
+	let key = KeyBuilder::new().data(b"test").build();
+	let build = |code: &'static [u8]| {
+		let source = source.clone();
+		move || Ok((dummyPackage(code), vec![source]))
+	};
+
+	// The first build packages the shader
+	assert_eq!(updatePackage(&cache, &key, &target, build(&[1])).unwrap(), (true, vec![source.clone()]));
+	assert_eq!(fs::read(&target).unwrap(), dummyPackage(&[1]).serialize());
+
+	// Unchanged inputs leave the target untouched without packaging again
+	let unchanged = updatePackage(&cache, &key, &target, || panic!("shader should not have been re-packaged"));
+	assert_eq!(unchanged.unwrap(), (false, vec![source.clone()]));
+
+	// A missing or stale target (e.g. left over from a build with a different cache key) is restored from the cache
+	fs::remove_file(&target).unwrap();
+	assert!(updatePackage(&cache, &key, &target, || panic!("shader should not have been re-packaged")).unwrap().0);
+	assert_eq!(fs::read(&target).unwrap(), dummyPackage(&[1]).serialize());
+	dummyPackage(&[0]).writeToFile(&target).unwrap();
+	assert!(updatePackage(&cache, &key, &target, || panic!("shader should not have been re-packaged")).unwrap().0);
+	assert_eq!(fs::read(&target).unwrap(), dummyPackage(&[1]).serialize());
+
+	// Changing a dependency re-packages the shader
+	fs::write(&source, "// version 2, which is longer").unwrap();
+	assert!(updatePackage(&cache, &key, &target, build(&[2])).unwrap().0);
+	assert_eq!(fs::read(&target).unwrap(), dummyPackage(&[2]).serialize());
+
+	// Packaging errors are propagated
+	fs::write(&source, "// version 3, which is even longer").unwrap();
+	assert!(updatePackage(&cache, &key, &target, || Err(anyhow::anyhow!("compilation failed"))).is_err());
+	fs::remove_dir_all(dir).ok();
+}
@@ -296,11 +296,13 @@ export class SlangContext
 		this.modules = new Map();
 		this.entryPoints = new Map();
 		this.composites = new Map();
+		this.lastErrorMessage = "";
 
 		// Private API
 		this.handleContextError = () => {
 			const error = slangModule.getLastError();
 			console.error(error.type + " error: " + error.message);
+			this.lastErrorMessage = error.message;
 			return BigInt(-1);
 		}
 		this.handleCodeTranslationError = () => {
@@ -479,6 +481,9 @@ export default async function slang_setupAndAddInterface (targetObj)
 	targetObj.slangjs_createGlobalSession = function () {
 		return targetObj.slangCtx.createGlobalSession();
 	};
+	targetObj.slangjs_lastErrorMessage = function () {
+		return targetObj.slangCtx.lastErrorMessage;
+	};
 	targetObj.slangjs_dropGlobalSession = function (handle) {
 		targetObj.slangCtx.dropGlobalSession(handle);
 	};
@@ -18,7 +18,7 @@
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
-use syn::{parse_macro_input, Data, DeriveInput, Field, Fields};
+use syn::{parse_macro_input, spanned::Spanned, Data, DeriveInput, Field, Fields};
 
 
 
@@ -78,6 +78,139 @@ fn getNamedFields (input: &DeriveInput) -> Result<&syn::FieldsNamed, TokenStream
 	}
 }
 
+/// The parsed contents of a `#[cgv_vertexAttr(...)]` field attribute.
+#[derive(Default)]
+struct VertexAttrOptions {
+	location: Option<u32>,
+	normalized: bool,
+	format: Option<syn::Ident>,
+	skip: bool
+}
+
+/// Parse all `#[cgv_vertexAttr(...)]` attributes of the given field into a [`VertexAttrOptions`].
+fn parseVertexAttrOptions (field: &Field) -> syn::Result<VertexAttrOptions>
+{
+	let mut options = VertexAttrOptions::default();
+	for attr in &field.attrs
+	{
+		if !attr.path().is_ident("cgv_vertexAttr") {
+			continue;
+		}
+		attr.parse_nested_meta(|meta| {
+			if meta.path.is_ident("location") {
+				let lit: syn::LitInt = meta.value()?.parse()?;
+				options.location = Some(lit.base10_parse()?);
+			}
+			else if meta.path.is_ident("normalized") {
+				options.normalized = true;
+			}
+			else if meta.path.is_ident("format") {
+				options.format = Some(meta.value()?.parse()?);
+			}
+			else if meta.path.is_ident("skip") {
+				options.skip = true;
+			}
+			else {
+				return Err(meta.error(
+					"unknown vertex attribute option, expected one of `location`, `normalized`, `format` or `skip`"
+				));
+			}
+			Ok(())
+		})?;
+	}
+	Ok(options)
+}
+
+/// Determine the `wgpu::VertexStepMode` variant requested via `#[cgv_vertexLayout(...)]` on the struct.
+fn parseVertexStepMode (input: &DeriveInput) -> syn::Result<syn::Ident>
+{
+	let mut stepMode = syn::Ident::new("Vertex", proc_macro2::Span::call_site());
+	for attr in &input.attrs
+	{
+		if !attr.path().is_ident("cgv_vertexLayout") {
+			continue;
+		}
+		attr.parse_nested_meta(|meta| {
+			if meta.path.is_ident("instance") {
+				stepMode = syn::Ident::new("Instance", meta.path.span());
+				Ok(())
+			}
+			else if meta.path.is_ident("vertex") {
+				stepMode = syn::Ident::new("Vertex", meta.path.span());
+				Ok(())
+			}
+			else {
+				Err(meta.error("unknown vertex layout option, expected `instance` or `vertex`"))
+			}
+		})?;
+	}
+	Ok(stepMode)
+}
+
+/// Infer the name of the `wgpu::VertexFormat` variant matching a single scalar component type, given the number of
+/// components. Returns `None` if there is no matching format.
+fn vertexFormatFromComponents (scalar: &str, count: usize, normalized: bool) -> Option<&'static str>
+{
+	Some(match (scalar, count, normalized)
+	{
+		("f32", 1, false) => "Float32",    ("f32", 2, false) => "Float32x2",
+		("f32", 3, false) => "Float32x3",  ("f32", 4, false) => "Float32x4",
+		("u32", 1, false) => "Uint32",     ("u32", 2, false) => "Uint32x2",
+		("u32", 3, false) => "Uint32x3",   ("u32", 4, false) => "Uint32x4",
+		("i32", 1, false) => "Sint32",     ("i32", 2, false) => "Sint32x2",
+		("i32", 3, false) => "Sint32x3",   ("i32", 4, false) => "Sint32x4",
+		("f64", 1, false) => "Float64",    ("f64", 2, false) => "Float64x2",
+		("f64", 3, false) => "Float64x3",  ("f64", 4, false) => "Float64x4",
+		("u16", 1, false) => "Uint16",     ("u16", 2, false) => "Uint16x2",    ("u16", 4, false) => "Uint16x4",
+		("i16", 1, false) => "Sint16",     ("i16", 2, false) => "Sint16x2",    ("i16", 4, false) => "Sint16x4",
+		("u16", 1, true)  => "Unorm16",    ("u16", 2, true)  => "Unorm16x2",   ("u16", 4, true)  => "Unorm16x4",
+		("i16", 1, true)  => "Snorm16",    ("i16", 2, true)  => "Snorm16x2",   ("i16", 4, true)  => "Snorm16x4",
+		("u8", 1, false)  => "Uint8",      ("u8", 2, false)  => "Uint8x2",     ("u8", 4, false)  => "Uint8x4",
+		("i8", 1, false)  => "Sint8",      ("i8", 2, false)  => "Sint8x2",     ("i8", 4, false)  => "Sint8x4",
+		("u8", 1, true)   => "Unorm8",     ("u8", 2, true)   => "Unorm8x2",    ("u8", 4, true)   => "Unorm8x4",
+		("i8", 1, true)   => "Snorm8",     ("i8", 2, true)   => "Snorm8x2",    ("i8", 4, true)   => "Snorm8x4",
+		_ => return None
+	})
+}
+
+/// Infer the `wgpu::VertexFormat` variant name(s) for a field type. Matrices yield one format per column, everything
+/// else exactly one format.
+fn inferVertexFormats (ty: &syn::Type, normalized: bool) -> Option<Vec<&'static str>>
+{
+	match ty
+	{
+		// Primitives and *glm* types
+		syn::Type::Path(path) => {
+			let ident = path.path.segments.last()?.ident.to_string();
+			let (scalar, count, columns) = match ident.as_str() {
+				"Vec2" => ("f32", 2, 1), "Vec3" => ("f32", 3, 1), "Vec4" => ("f32", 4, 1),
+				"IVec2" => ("i32", 2, 1), "IVec3" => ("i32", 3, 1), "IVec4" => ("i32", 4, 1),
+				"UVec2" => ("u32", 2, 1), "UVec3" => ("u32", 3, 1), "UVec4" => ("u32", 4, 1),
+				"DVec2" => ("f64", 2, 1), "DVec3" => ("f64", 3, 1), "DVec4" => ("f64", 4, 1),
+				"Mat2" => ("f32", 2, 2), "Mat3" => ("f32", 3, 3), "Mat4" => ("f32", 4, 4),
+				"Quat" | "RGBA" | "Rgba" => ("f32", 4, 1),
+				scalar => (scalar, 1, 1)
+			};
+			let format = vertexFormatFromComponents(
+				// Only small integers can be normalized
+				if normalized && !matches!(scalar, "u8" | "i8" | "u16" | "i16") { return None } else { scalar },
+				count, normalized
+			)?;
+			Some(vec![format; columns])
+		},
+
+		// Arrays of primitives
+		syn::Type::Array(array) => {
+			let syn::Type::Path(elem) = array.elem.as_ref() else { return None };
+			let scalar = elem.path.get_ident()?.to_string();
+			let syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Int(len), .. }) = &array.len else { return None };
+			Some(vec![vertexFormatFromComponents(&scalar, len.base10_parse().ok()?, normalized)?])
+		},
+
+		// Nothing else is supported
+		_ => None
+	}
+}
 
 
 //////
@@ -361,6 +494,100 @@ pub fn deriveInterleavedElem (input: TokenStream) -> TokenStream
 	.into()
 }
 
+/// Derive [`cgv::hal::VertexLayout`] for a vertex struct, generating the [`wgpu::VertexBufferLayout`] that describes
+/// a buffer of its instances.
+///
+/// Field formats are inferred from the field types (primitives, arrays of up to four primitives, and the *glm* vector,
+/// matrix and quaternion types), offsets are determined via [`core::mem::offset_of`]. Use `#[cgv_vertexAttr(...)]` on
+/// fields with the options `location=N`, `normalized`, `format=<VertexFormat variant>` or `skip` to adjust individual
+/// attributes, and `#[cgv_vertexLayout(instance)]` on the struct to advance the attributes per instance. Can be
+/// combined freely with `#[derive(InterleavedElem)]`.
+#[proc_macro_derive(VertexLayout, attributes(cgv_vertexAttr, cgv_vertexLayout))]
+pub fn deriveVertexLayout (input: TokenStream) -> TokenStream
+{
+	let input = parse_macro_input!(input as DeriveInput);
+	let name = &input.ident;
+	let (implGenerics, tyGenerics, whereClause) = input.generics.split_for_impl();
+
+	let fields = match getNamedFields(&input) {
+		Ok(f) => f,
+		Err(e) => return e.into(),
+	};
+	let stepMode = match parseVertexStepMode(&input) {
+		Ok(mode) => mode,
+		Err(e) => return e.to_compile_error().into(),
+	};
+
+	// Build the attribute list
+	let mut attributes = Vec::with_capacity(fields.named.len());
+	let mut sizeChecks = Vec::with_capacity(fields.named.len());
+	let mut nextLocation = 0u32;
+	for field in &fields.named
+	{
+		let options = match parseVertexAttrOptions(field) {
+			Ok(options) => options,
+			Err(e) => return e.to_compile_error().into(),
+		};
+		if options.skip {
+			continue;
+		}
+		let ident = field.ident.as_ref().unwrap();
+		let ty = &field.ty;
+
+		// Determine format(s)
+		let formats: Vec<syn::Ident> = if let Some(format) = options.format {
+			vec![format]
+		} else if let Some(formats) = inferVertexFormats(ty, options.normalized) {
+			formats.into_iter().map(|f| syn::Ident::new(f, ty.span())).collect()
+		} else {
+			return syn::Error::new(
+				ty.span(), "cannot infer vertex format for this type, use `#[cgv_vertexAttr(format=...)]` or \
+				            `#[cgv_vertexAttr(skip)]`"
+			).to_compile_error().into()
+		};
+
+		// Emit one attribute per column, consecutive in both memory and shader locations
+		let mut location = options.location.unwrap_or(nextLocation);
+		let mut columnOffset = quote! { 0u64 };
+		for format in &formats
+		{
+			attributes.push(quote! {
+				::cgv::wgpu::VertexAttribute {
+					format: ::cgv::wgpu::VertexFormat::#format,
+					offset: ::core::mem::offset_of!(Self, #ident) as u64 + #columnOffset,
+					shader_location: #location
+				}
+			});
+			columnOffset = quote! { #columnOffset + ::cgv::wgpu::VertexFormat::#format.size() };
+			location += 1;
+		}
+		nextLocation = location;
+		sizeChecks.push(quote! {
+			::core::assert!(
+				size_of::<#ty>() as u64 == #columnOffset,
+				concat!("size of field `", stringify!(#ident), "` does not match its vertex format")
+			);
+		});
+	}
+
+	// Size checks can only be emitted for non-generic structs as they live outside of the impl
+	let sizeChecks = if input.generics.params.is_empty() {
+		quote! { const _: () = { #(#sizeChecks)* }; }
+	} else {
+		quote! {}
+	};
+
+	quote! {
+		impl #implGenerics ::cgv::hal::VertexLayout for #name #tyGenerics #whereClause
+		{
+			const ATTRIBUTES: &'static [::cgv::wgpu::VertexAttribute] = &[#(#attributes),*];
+			const STEP_MODE: ::cgv::wgpu::VertexStepMode = ::cgv::wgpu::VertexStepMode::#stepMode;
+		}
+		#sizeChecks
+	}
+	.into()
+}
+
 /// Derive a "no normals" impl of [`cgv::renderer::data::host::CanHaveNormals`].
 ///
 /// `hasNormals()` will return `false`; the other methods will panic if invoked.
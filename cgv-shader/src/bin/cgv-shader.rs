@@ -0,0 +1,412 @@
+
+//////
+//
+// Language config
+//
+
+// Eff this convention.
+#![allow(non_snake_case)]
+
+
+
+//////
+//
+// Module definitions
+//
+
+/// Unit tests
+#[cfg(test)]
+#[path="cgv-shader/tests.rs"]
+mod tests;
+
+
+
+//////
+//
+// Imports
+//
+
+// Standard library
+use std::{collections::BTreeSet, fs, path::{Path, PathBuf}, process::ExitCode};
+
+// Anyhow library
+use anyhow::{anyhow, bail, Result};
+
+// SPIR-V library
+use rspirv::binary::Disassemble;
+
+// CGV-rs core libraries
+use cgv_shader::{self as shader, Target, compile::prelude::*};
+use cgv_util as util;
+
+
+
+//////
+//
+// Constants
+//
+
+/// The usage information printed for `--help` or in case of invalid invocations.
+const USAGE: &str = "\
+Usage: cgv-shader <COMMAND> [OPTIONS]
+
+Commands:
+  compile [OPTIONS] <INPUT>       Compile a .slang file, or all .slang files in a directory tree, to .spk packages
+      -t, --types <TARGETS>       Comma-separated targets to include (spirv, wgsl, glsl, hlsl, dxil, metal, cuda)
+                                  [default: all feasible source types]
+      -I, --include <DIR>         Add a directory to the module search path (repeatable)
+      -e, --env <FILE>            Make the modules of a serialized compilation environment available for importing
+      -D, --define <NAME=VALUE>   Define a preprocessor macro for all modules (repeatable)
+      -V, --variant <DEFINES>     Include a variant with the given comma-separated NAME=VALUE defines (repeatable)
+                                  [default: only the default variant]
+      -p, --entry-point <NAME>    Include a specialization for the named entry point instead of the generic code
+                                  (repeatable)
+      -o, --output <PATH>         Output file (for file inputs) or directory (for directory inputs)
+  list <PACKAGE>                  List the variants, instances and entry points contained in a package
+  dump [OPTIONS] <PACKAGE>        Print the code of a package instance (SPIR-V is disassembled)
+      -V, --variant <KEY>         The key of the variant to dump [default: the default variant]
+      -t, --type <TARGET>         The target of the instance to dump [default: first in package]
+      -p, --entry-point <NAME>    The entry point specialization to dump [default: generic code]
+      -o, --output <FILE>         Write to a file instead of standard output
+  env list <ENVIRONMENT>          List the modules of a serialized compilation environment
+  env extract [OPTIONS] <ENVIRONMENT> [MODULE...]
+                                  Extract all (or the given) modules of a serialized compilation environment
+      -o, --output <DIR>          Directory to extract into [default: current directory]
+";
+
+/// The file extension used for extracted *Slang*-IR modules.
+const IR_MODULE_EXTENSION: &str = "slang-module";
+
+
+
+//////
+//
+// Structs
+//
+
+/// The options and positional arguments of a command.
+#[derive(Default)]
+struct Arguments {
+	options: Vec<(&'static str, String)>,
+	positional: Vec<String>
+}
+impl Arguments
+{
+	/// Parse the given command line arguments, recognizing the given options, each of which takes a value and is listed
+	/// as a pair of short and long form.
+	fn parse (args: &[String], known: &[(&'static str, &'static str)]) -> Result<Self>
+	{
+		let mut parsed = Self::default();
+		let mut args = args.iter();
+		while let Some(arg) = args.next()
+		{
+			if !arg.starts_with('-') || arg == "-" {
+				parsed.positional.push(arg.clone());
+				continue;
+			}
+			let &(_, long) = known.iter().find(|(short, long)| arg == short || arg == long).ok_or_else(
+				|| anyhow!("unknown option '{arg}'")
+			)?;
+			let value = args.next().ok_or_else(|| anyhow!("option '{arg}' requires a value"))?;
+			parsed.options.push((long, value.clone()));
+		}
+		Ok(parsed)
+	}
+
+	/// Iterate over all values given for the option with the given long form.
+	fn all<'this> (&'this self, option: &'this str) -> impl Iterator<Item=&'this str> + 'this {
+		self.options.iter().filter(move |(long, _)| *long == option).map(|(_, value)| value.as_str())
+	}
+
+	/// Retrieve the value last given for the option with the given long form.
+	fn last (&self, option: &str) -> Option<&str> {
+		self.options.iter().rev().find(|(long, _)| *long == option).map(|(_, value)| value.as_str())
+	}
+
+	/// Retrieve the single positional argument the command expects.
+	fn single (&self, what: &str) -> Result<&str> {
+		match self.positional.as_slice() {
+			[arg] => Ok(arg),
+			[] => bail!("missing {what}"),
+			_ => bail!("expected exactly one {what}, got {}", self.positional.len())
+		}
+	}
+}
+
+
+
+//////
+//
+// Functions
+//
+
+/// Parse a preprocessor define given as `NAME=VALUE` (or just `NAME`, which defines it as `1`).
+fn parseDefine (define: &str) -> Result<(&str, &str)> {
+	match define.split_once('=') {
+		Some((name, value)) if !name.trim().is_empty() => Ok((name.trim(), value.trim())),
+		None if !define.trim().is_empty() => Ok((define.trim(), "1")),
+		_ => bail!("invalid define '{define}', expected NAME=VALUE")
+	}
+}
+
+/// Parse a compilation [`Target`] from its command line designation.
+fn parseTarget (designation: &str) -> Result<Target> {
+	match designation.trim().to_ascii_lowercase().as_str() {
+		"spirv" | "spir-v" | "spv" => Ok(Target::SPIRV),
+		"wgsl" => Ok(Target::WGSL),
+		"glsl" => Ok(Target::GLSL),
+		"hlsl" => Ok(Target::HLSL),
+		"dxil" => Ok(Target::DXIL),
+		"metal" | "msl" => Ok(Target::Metal),
+		"cuda" | "cpp" => Ok(Target::CudaCpp),
+		_ => bail!("unknown target '{designation}' (expected spirv, wgsl, glsl, hlsl, dxil, metal or cuda)")
+	}
+}
+
+/// Turn a shader compilation error into a human-readable report, including source excerpts if the error carries
+/// structured [diagnostics](shader::compile::Diagnostics).
+fn compilationFailure (err: impl std::error::Error+Send+Sync+'static, srcPath: &Path, searchPath: &[PathBuf])
+	-> anyhow::Error
+{
+	match shader::compile::diagnosticsOf(&err) {
+		Some(diagnostics) => anyhow!(
+			"failed to compile '{}':\n\n{}\n", srcPath.display(), diagnostics.renderFromFiles(searchPath)
+		),
+		None => anyhow!("failed to compile '{}': {err}", srcPath.display())
+	}
+}
+
+/// Implements the `compile` command.
+fn compile (args: &[String]) -> Result<()>
+{
+	// Parse arguments
+	let args = Arguments::parse(args, &[
+		("-t", "--types"), ("-I", "--include"), ("-e", "--env"), ("-D", "--define"), ("-V", "--variant"),
+		("-p", "--entry-point"), ("-o", "--output")
+	])?;
+	let input = PathBuf::from(args.single("input file or directory")?);
+	let targets = match args.last("--types") {
+		Some(types) => types.split(',').map(parseTarget).collect::<Result<Vec<_>>>()?,
+		None => Target::vecFromWgpuSourceTypes(shader::feasibleSourceTypes())
+	};
+	let searchPath: Vec<PathBuf> = args.all("--include").map(PathBuf::from).collect();
+	let entryPoints = args.all("--entry-point").next().is_some().then(
+		|| args.all("--entry-point").map(Some).collect::<BTreeSet<_>>()
+	);
+	let defines = args.all("--define").map(parseDefine).collect::<Result<Vec<_>>>()?;
+	let mut variants = args.all("--variant").map(|defines| defines.split(',').map(parseDefine).try_fold(
+		shader::compile::Variant::new(), |variant, define| define.map(|(name, value)| variant.define(name, value))
+	)).collect::<Result<Vec<_>>>()?;
+	if variants.is_empty() {
+		variants.push(shader::compile::Variant::new());
+	}
+
+	// Set up the compilation context
+	let mut context = shader::slang::ContextBuilder::withTargets(&targets)
+		.addSearchPaths(searchPath.as_slice()).addDefines(&defines).build()?;
+	if let Some(envFile) = args.last("--env") {
+		let env = shader::compile::Environment::<shader::slang::EnvModule>::deserializeFromFile(envFile)?;
+		context.replaceEnvironment(Some(env))?;
+	}
+
+	// Helper for packaging a single source file
+	let package = |srcPath: &Path, tgtPath: &Path| -> Result<()> {
+		let package = shader::Package::fromSourceFileVariantsMultipleTypes(
+			&targets, &context, srcPath, &variants, entryPoints.clone()
+		).map_err(|err| compilationFailure(err, srcPath, &searchPath))?;
+		package.writeToFile(tgtPath)?;
+		println!("{} -> {}", srcPath.display(), tgtPath.display());
+		Ok(())
+	};
+
+	// Dispatch
+	if input.is_dir()
+	{
+		// Mirror the directory structure in the output directory
+		let outputDir = args.last("--output").map(PathBuf::from).unwrap_or_else(|| input.clone());
+		util::fs::doRecursively(&input, |srcPath, destStack, fileType| {
+			if fileType.is_dir() || !srcPath.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("slang")) {
+				return Ok(());
+			}
+			let tgtPath = outputDir.join(destStack).with_extension("spk");
+			if let Some(parent) = tgtPath.parent() {
+				fs::create_dir_all(parent)?;
+			}
+			package(srcPath, &tgtPath)
+		})
+	}
+	else {
+		let output = args.last("--output").map(PathBuf::from).unwrap_or_else(|| input.with_extension("spk"));
+		package(&input, &output)
+	}
+}
+
+/// Implements the `list` command.
+fn list (args: &[String]) -> Result<()>
+{
+	let args = Arguments::parse(args, &[])?;
+	let bytes = std::fs::read(args.single("package file")?)?;
+	let package = shader::Package::deserialize(&bytes)?;
+	match shader::PACKAGE_FORMAT.header(&bytes)? {
+		Some(header) => println!(
+			"package '{}' (format version {}, {:?} compression)", package.name(), header.formatVersion,
+			header.compression
+		),
+		None => println!("package '{}' (legacy format)", package.name())
+	}
+	for variant in package.variants()
+	{
+		println!("  variant '{variant}':");
+		for (target, instance) in package.variantInstances(variant)
+		{
+			println!("    {target} instance:");
+			for entryPoint in instance.entryPoints() {
+				let numBytes = instance.code(entryPoint).map_or(0, |code| code.len());
+				println!("      {:<30} {numBytes:>10} bytes", entryPoint.unwrap_or("<generic>"));
+			}
+		}
+	}
+	Ok(())
+}
+
+/// Implements the `dump` command.
+fn dump (args: &[String]) -> Result<()>
+{
+	// Parse arguments
+	let args = Arguments::parse(
+		args, &[("-t", "--type"), ("-V", "--variant"), ("-p", "--entry-point"), ("-o", "--output")]
+	)?;
+	let package = shader::Package::fromFile(args.single("package file")?)?;
+	let variant = args.last("--variant").unwrap_or(shader::Package::DEFAULT_VARIANT);
+	if !package.hasVariant(variant) {
+		bail!("package contains no variant '{variant}'");
+	}
+	let (target, instance) = match args.last("--type") {
+		Some(designation) => {
+			let target = parseTarget(designation)?;
+			let instance = package.variantInstance(variant, target).ok_or_else(
+				|| anyhow!("variant '{variant}' contains no {target} instance")
+			)?;
+			(target, instance)
+		},
+		None => package.variantInstances(variant).next().ok_or_else(
+			|| anyhow!("variant '{variant}' contains no instances")
+		)?
+	};
+	let entryPoint = args.last("--entry-point");
+	let code = instance.code(entryPoint).ok_or_else(|| anyhow!(
+		"{target} instance contains no code for entry point '{}'", entryPoint.unwrap_or("<generic>")
+	))?;
+
+	// Render code
+	let text = if target.isSPIRV() {
+		rspirv::dr::load_bytes(code).map_err(|err| anyhow!("invalid SPIR-V code: {err}"))?.disassemble()
+	}
+	else if target.isText() {
+		String::from_utf8(code.to_vec())?
+	}
+	else {
+		// Other binary targets can only be written out verbatim
+		let Some(output) = args.last("--output") else {
+			bail!("{target} code is binary and can only be dumped to a file (use --output)");
+		};
+		fs::write(output, code)?;
+		return Ok(());
+	};
+
+	// Output
+	match args.last("--output") {
+		Some(output) => fs::write(output, text)?,
+		None => println!("{text}")
+	}
+	Ok(())
+}
+
+/// Implements the `env` command.
+fn env (args: &[String]) -> Result<()>
+{
+	type Environment = shader::compile::Environment<shader::slang::EnvModule>;
+	let Some((subcommand, args)) = args.split_first() else {
+		bail!("missing env subcommand (expected 'list' or 'extract')");
+	};
+	match subcommand.as_str()
+	{
+		"list" => {
+			let args = Arguments::parse(args, &[])?;
+			let env = Environment::deserializeFromFile(args.single("environment file")?)?;
+			println!(
+				"environment '{}' (uuid={}, compatHash={:#018x}, {} modules)",
+				env.label(), env.uuid(), env.compatHash(), env.numModules()
+			);
+			for entry in env.modules()
+			{
+				let (kind, numBytes) = match &entry.module {
+					shader::slang::EnvModule::SourceCode(code) => ("source", code.len()),
+					shader::slang::EnvModule::IR(bytes) => ("IR", bytes.len())
+				};
+				let origin = entry.sourceEnv.map_or_else(|| "own".to_owned(), |uuid| format!("from {uuid}"));
+				println!("  {:<48} {kind:<6} {numBytes:>10} bytes  ({origin})", entry.path.display());
+			}
+			Ok(())
+		},
+
+		"extract" => {
+			let args = Arguments::parse(args, &[("-o", "--output")])?;
+			let Some((envFile, modules)) = args.positional.split_first() else {
+				bail!("missing environment file");
+			};
+			let env = Environment::deserializeFromFile(envFile)?;
+			let outputDir = PathBuf::from(args.last("--output").unwrap_or("."));
+			for module in modules {
+				if !env.modules().any(|entry| entry.path == Path::new(module)) {
+					bail!("environment contains no module '{module}'");
+				}
+			}
+			for entry in env.modules().filter(
+				|entry| modules.is_empty() || modules.iter().any(|module| entry.path == Path::new(module))
+			){
+				// Module paths are virtual, so make sure they cannot escape the output directory
+				let relPath: PathBuf = entry.path.components().filter(
+					|component| matches!(component, std::path::Component::Normal(_))
+				).collect();
+				let (tgtPath, contents) = match &entry.module {
+					shader::slang::EnvModule::SourceCode(code) => (outputDir.join(&relPath), code.as_bytes()),
+					shader::slang::EnvModule::IR(bytes) =>
+						(outputDir.join(&relPath).with_extension(IR_MODULE_EXTENSION), bytes.as_slice())
+				};
+				if let Some(parent) = tgtPath.parent() {
+					fs::create_dir_all(parent)?;
+				}
+				fs::write(&tgtPath, contents)?;
+				println!("{} -> {}", entry.path.display(), tgtPath.display());
+			}
+			Ok(())
+		},
+
+		_ => bail!("unknown env subcommand '{subcommand}' (expected 'list' or 'extract')")
+	}
+}
+
+/// The tool entry point.
+fn main () -> ExitCode
+{
+	let args: Vec<String> = std::env::args().skip(1).collect();
+	let result = match args.split_first() {
+		Some((command, args)) => match command.as_str() {
+			"compile" => compile(args),
+			"list" => list(args),
+			"dump" => dump(args),
+			"env" => env(args),
+			"-h" | "--help" | "help" => { print!("{USAGE}"); Ok(()) },
+			_ => Err(anyhow!("unknown command '{command}'\n\n{USAGE}"))
+		},
+		None => Err(anyhow!("missing command\n\n{USAGE}"))
+	};
+	match result {
+		Ok(()) => ExitCode::SUCCESS,
+		Err(err) => {
+			eprintln!("error: {err}");
+			ExitCode::FAILURE
+		}
+	}
+}
@@ -0,0 +1,107 @@
+
+//////
+//
+// Imports
+//
+
+// Local imports
+use super::*;
+
+
+
+//////
+//
+// Helpers
+//
+
+/// Turn the given string literals into owned command line arguments.
+fn args (args: &[&str]) -> Vec<String> {
+	args.iter().map(|&arg| arg.to_owned()).collect()
+}
+
+/// Create a unique, initially empty directory for a test to write its outputs to.
+fn tempDir () -> PathBuf {
+	let dir = std::env::temp_dir().join(format!("cgv-shader-test-cli{}", shader::uniqueAnonymousName()));
+	fs::create_dir_all(&dir).unwrap();
+	dir
+}
+
+
+
+//////
+//
+// Tests
+//
+
+#[test]
+fn test_Arguments_parse ()
+{
+	let known = [("-I", "--include"), ("-o", "--output")];
+	let parsed = Arguments::parse(
+		&args(&["-I", "a", "input", "--include", "b", "-o", "x", "-o", "y"]), &known
+	).unwrap();
+	assert_eq!(parsed.all("--include").collect::<Vec<_>>(), ["a", "b"]);
+	assert_eq!(parsed.last("--output"), Some("y"));
+	assert_eq!(parsed.last("--define"), None);
+	assert_eq!(parsed.single("input").unwrap(), "input");
+
+	// Malformed invocations
+	assert!(Arguments::parse(&args(&["-x", "a"]), &known).is_err());
+	assert!(Arguments::parse(&args(&["input", "-o"]), &known).is_err());
+	assert!(Arguments::parse(&args(&[]), &known).unwrap().single("input").is_err());
+	assert!(Arguments::parse(&args(&["a", "b"]), &known).unwrap().single("input").is_err());
+}
+
+#[test]
+fn test_parseDefine ()
+{
+	assert_eq!(parseDefine("NAME=VALUE").unwrap(), ("NAME", "VALUE"));
+	assert_eq!(parseDefine(" NAME = 2 ").unwrap(), ("NAME", "2"));
+	assert_eq!(parseDefine("FLAG").unwrap(), ("FLAG", "1"));
+	assert!(parseDefine("=VALUE").is_err());
+	assert!(parseDefine(" ").is_err());
+}
+
+#[test]
+fn test_parseTarget ()
+{
+	assert_eq!(parseTarget("SPIR-V").unwrap(), Target::SPIRV);
+	assert_eq!(parseTarget(" wgsl").unwrap(), Target::WGSL);
+	assert_eq!(parseTarget("msl").unwrap(), Target::Metal);
+	assert!(parseTarget("vulkan").is_err());
+}
+
+#[test]
+fn test_compile_listAndDump ()
+{
+	// Compile the test shader into a package
+	let dir = tempDir();
+	let package = dir.join("test.spk");
+	compile(&args(&[
+		"-t", "spirv,wgsl", "-I", util::pathInsideCrate!("/../cgv/shader/lib"), "-o", package.to_str().unwrap(),
+		util::pathInsideCrate!("/shader/tests/multiple_entrypoints.slang")
+	])).unwrap();
+	assert!(list(&args(&[package.to_str().unwrap()])).is_ok());
+
+	// Dump text code to a file, and refuse to dump instances the package does not contain
+	let dump = dir.join("test.wgsl");
+	super::dump(&args(&["-t", "wgsl", "-o", dump.to_str().unwrap(), package.to_str().unwrap()])).unwrap();
+	assert!(fs::read_to_string(&dump).unwrap().contains("fn "));
+	assert!(super::dump(&args(&["-t", "hlsl", package.to_str().unwrap()])).is_err());
+	assert!(super::dump(&args(&["-V", "nonexistent", package.to_str().unwrap()])).is_err());
+	fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn test_compile_failure ()
+{
+	// Compilation errors are reported along with an excerpt of the offending source
+	let dir = tempDir();
+	let source = dir.join("broken.slang");
+	fs::write(&source, "void broken () {\n\tundefinedThing = 1;\n}\n").unwrap();
+	let err = compile(&args(&["-t", "spirv", source.to_str().unwrap()])).unwrap_err().to_string();
+	assert!(err.starts_with(&format!("failed to compile '{}'", source.display())));
+	assert!(err.contains("undefinedThing = 1;"));
+	assert!(!dir.join("broken.spk").exists());
+	fs::remove_dir_all(dir).ok();
+}
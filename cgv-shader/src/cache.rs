@@ -0,0 +1,515 @@
+
+//////
+//
+// Imports
+//
+
+// Standard library
+use std::{fmt::{Display, Formatter}, path::{Path, PathBuf}};
+
+// Anyhow library
+#[cfg(target_arch="wasm32")]
+use anyhow::anyhow;
+
+// CGV-rs core libraries
+use cgv_util::uuid::Uuid;
+
+// Local imports
+use crate::*;
+
+
+
+//////
+//
+// Constants
+//
+
+/// The version of the format cache entries are stored in. Must be bumped whenever the layout of [`Entry`] or the
+/// encoding of [`Package`]s changes, so that entries written by older versions are discarded instead of misread.
+const ENTRY_FORMAT_VERSION: u32 = 2;
+
+
+
+//////
+//
+// Traits
+//
+
+/// The trait of backends a shader [`Cache`] can persist its entries in.
+pub trait Storage: Send+Sync
+{
+	/// Load the raw bytes stored under the given key, if there are any.
+	fn load (&self, key: &Key) -> Option<Vec<u8>>;
+
+	/// Store the given raw bytes under the given key, replacing any bytes stored there previously.
+	fn store (&self, key: &Key, bytes: &[u8]) -> anyhow::Result<()>;
+
+	/// Remove whatever is stored under the given key. Removing a key that is not present is not an error.
+	fn remove (&self, key: &Key);
+}
+
+
+
+//////
+//
+// Structs
+//
+
+/// The content-addressed key of a [`Cache`] entry, computed by a [`KeyBuilder`] from everything that influences the
+/// outcome of a shader compilation.
+#[derive(Clone,Copy,Debug,PartialEq,Eq,PartialOrd,Ord,Hash)]
+pub struct Key(Uuid);
+impl Key {
+	/// Report the digest the key consists of.
+	#[inline(always)]
+	pub fn digest (&self) -> Uuid {
+		self.0
+	}
+}
+impl Display for Key {
+	fn fmt (&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+		write!(formatter, "{}", self.0.simple())
+	}
+}
+
+
+/// Accumulates all inputs of a shader compilation into a [`Key`]. Every input is length-prefixed and tagged with its
+/// kind before being fed into the digest, so different combinations of inputs can never produce the same key by
+/// accident. The order in which inputs are added is significant.
+#[derive(Default)]
+pub struct KeyBuilder {
+	material: Vec<u8>
+}
+impl KeyBuilder
+{
+	/// Helper for appending a tagged, length-prefixed chunk of key material.
+	fn chunk (mut self, tag: u8, bytes: &[u8]) -> Self {
+		self.material.push(tag);
+		self.material.extend_from_slice(&(bytes.len() as u64).to_le_bytes());
+		self.material.extend_from_slice(bytes);
+		self
+	}
+
+	/// Start an empty key.
+	#[inline(always)]
+	pub fn new () -> Self {
+		Self::default()
+	}
+
+	/// Add the digest of all compatibility-relevant options a [`compile::Context`] was set up with.
+	#[inline(always)]
+	pub fn optionsDigest (self, digest: u64) -> Self {
+		self.chunk(b'o', &digest.to_le_bytes())
+	}
+
+	/// Add the [options digest](Self::optionsDigest) and the [preprocessor defines](compile::Context::defines) of the
+	/// given context.
+	pub fn context (self, context: &(impl compile::EnvironmentEnabled+compile::Context)) -> Self {
+		context.defines().fold(
+			self.optionsDigest(context.environmentCompatHash()), |key, (name, value)| key.define(name, value)
+		)
+	}
+
+	/// Add a preprocessor define.
+	#[inline(always)]
+	pub fn define (self, name: &str, value: &str) -> Self {
+		self.chunk(b'D', name.as_bytes()).chunk(b'V', value.as_bytes())
+	}
+
+	/// Add a program [variant](compile::Variant), i.e. its key, its defines and its specialization arguments.
+	pub fn variant (self, variant: &compile::Variant) -> Self {
+		let key = variant.defines().fold(self.chunk(b'v', variant.key().as_bytes()), |key, (name, value)|
+			key.define(name, value)
+		);
+		match variant.specializationModuleSource() {
+			Some(sourceCode) => key.chunk(b'x', sourceCode.as_bytes()),
+			None => key
+		}
+	}
+
+	/// Add the compatibility hash and the [content digest](compile::Environment::contentDigest) of the given
+	/// environment. This makes sure entries get invalidated whenever any module available for importing changes.
+	pub fn environment<ModuleType> (self, environment: &compile::Environment<ModuleType>) -> Self
+	where
+		ModuleType: compile::env::Module + serde::Serialize+(for<'de> serde::Deserialize<'de>)
+	{
+		self.chunk(b'e', &environment.compatHash().to_le_bytes())
+			.chunk(b'E', environment.contentDigest().as_bytes())
+	}
+
+	/// Add a compilation target.
+	#[inline(always)]
+	pub fn target (self, target: compile::Target) -> Self {
+		self.chunk(b't', target.to_string().as_bytes())
+	}
+
+	/// Add a [`WgpuSourceType`] to compile for.
+	#[inline(always)]
+	pub fn sourceType (self, sourceType: WgpuSourceType) -> Self {
+		self.target(sourceType.into())
+	}
+
+	/// Add a module compiled from source code under the given virtual filepath.
+	#[inline(always)]
+	pub fn source (self, virtualFilepath: impl AsRef<Path>, sourceCode: &str) -> Self {
+		self.chunk(b'p', virtualFilepath.as_ref().to_string_lossy().as_bytes()).chunk(b's', sourceCode.as_bytes())
+	}
+
+	/// Add a module compiled from a source file. Only the contents of the file itself are added – files it imports are
+	/// tracked separately when [inserting](Cache::insert) the entry.
+	pub fn sourceFile (self, filename: impl AsRef<Path>) -> std::io::Result<Self> {
+		let sourceCode = std::fs::read(filename.as_ref())?;
+		Ok(self.chunk(b'p', filename.as_ref().to_string_lossy().as_bytes()).chunk(b's', &sourceCode))
+	}
+
+	/// Add an entry point specialization to include, with `None` referring to the generic code of all entry points.
+	#[inline(always)]
+	pub fn entryPoint (self, name: Option<&str>) -> Self {
+		match name {
+			Some(name) => self.chunk(b'n', name.as_bytes()),
+			None => self.chunk(b'N', &[])
+		}
+	}
+
+	/// Add arbitrary additional data that influences the compilation result.
+	#[inline(always)]
+	pub fn data (self, bytes: &[u8]) -> Self {
+		self.chunk(b'd', bytes)
+	}
+
+	/// Compute the key from all inputs added so far.
+	#[inline(always)]
+	pub fn build (self) -> Key {
+		Key(digestOf(&self.material))
+	}
+}
+
+
+/// A file on disk a cached [`Package`] was compiled from, along with what we know about its state at that time.
+#[derive(bitcode::Encode,bitcode::Decode)]
+struct FileDependency {
+	path: String,
+	size: u64,
+	modified: u64,
+	digest: [u8; 16]
+}
+impl FileDependency
+{
+	/// Record the current state of the file at the given path.
+	fn capture (path: &Path) -> std::io::Result<Self>
+	{
+		// Query the metadata before reading, so that a concurrent modification will at worst cause a superfluous
+		// re-hash on the next check instead of going unnoticed
+		let (size, modified) = fileStamp(path)?;
+		let contents = std::fs::read(path)?;
+		Ok(Self { path: path.to_string_lossy().into_owned(), size, modified, digest: *digestOf(&contents).as_bytes() })
+	}
+
+	/// Check whether the file is still unchanged. Only files whose size or modification time differ from the recorded
+	/// state get read and hashed, and if their contents turn out unchanged, the recorded state is updated.
+	///
+	/// # Returns
+	///
+	/// `None` if the file changed or cannot be accessed anymore, `Some(true)` if it is unchanged but the recorded state
+	/// had to be updated, and `Some(false)` if it is unchanged as recorded.
+	fn check (&mut self) -> Option<bool>
+	{
+		let (size, modified) = fileStamp(Path::new(&self.path)).ok()?;
+		if size == self.size && modified == self.modified {
+			return Some(false);
+		}
+		if size != self.size {
+			return None;
+		}
+		let contents = std::fs::read(&self.path).ok()?;
+		(digestOf(&contents).as_bytes() == &self.digest).then(|| {
+			self.modified = modified;
+			true
+		})
+	}
+}
+
+
+/// The representation of a [`Package`] inside a [`Storage`].
+#[derive(bitcode::Encode,bitcode::Decode)]
+struct Entry {
+	version: u32,
+	fileDependencies: Vec<FileDependency>,
+	package: Vec<u8>
+}
+impl Entry
+{
+	/// Check whether all files the package was built from are still unchanged.
+	///
+	/// # Returns
+	///
+	/// `None` if any of the files changed, otherwise whether the recorded state of any file had to be updated (meaning
+	/// the entry should be stored again).
+	fn checkUpToDate (&mut self) -> Option<bool> {
+		self.fileDependencies.iter_mut().try_fold(false, |updated, file| Some(file.check()? || updated))
+	}
+}
+
+
+/// A persistent cache of compiled [`Package`]s, addressed by [`Key`]s that capture all inputs of the compilation. In
+/// addition, each entry remembers the digests of all files on disk the package was compiled from (typically obtained
+/// via [`compile::Module::fileDependencies`]), and is discarded as soon as any of them changes. To keep lookups cheap,
+/// files are only re-hashed when their size or modification time differs from when the entry was stored.
+///
+/// On native platforms, entries are stored as individual files in a directory. On the web, they are kept in the
+/// browser's local storage.
+pub struct Cache {
+	storage: Box<dyn Storage>
+}
+impl Cache
+{
+	/// Create a cache that keeps its entries in the given storage backend.
+	pub fn new (storage: impl Storage+'static) -> Self {
+		Self { storage: Box::new(storage) }
+	}
+
+	/// Create a cache that keeps its entries in the given directory, creating it when the first entry is inserted.
+	#[cfg(not(target_arch="wasm32"))]
+	#[inline(always)]
+	pub fn inDirectory (directory: impl AsRef<Path>) -> Self {
+		Self::new(DirectoryStorage::new(directory))
+	}
+
+	/// Create a cache that keeps its entries in the browser's local storage, prefixing all its keys with the given
+	/// string.
+	#[cfg(target_arch="wasm32")]
+	#[inline(always)]
+	pub fn inBrowserStorage (prefix: &str) -> Self {
+		Self::new(BrowserStorage::new(prefix))
+	}
+
+	/// Create a cache at the default location for the current platform. On native platforms, this is the directory
+	/// given by the `CGV_SHADER_CACHE_DIR` environment variable, or a `cgv-rs/shader-cache` subdirectory of the
+	/// system's temporary directory if unset. On the web, it is the browser's local storage.
+	pub fn inDefaultLocation () -> Self
+	{
+		#[cfg(not(target_arch="wasm32"))] {
+			Self::inDirectory(std::env::var_os("CGV_SHADER_CACHE_DIR").map(PathBuf::from).unwrap_or_else(
+				|| std::env::temp_dir().join("cgv-rs").join("shader-cache")
+			))
+		}
+		#[cfg(target_arch="wasm32")] {
+			Self::inBrowserStorage("cgv-rs/shader-cache/")
+		}
+	}
+
+	/// Look up the package stored under the given key. Entries that are stored in an outdated format, cannot be
+	/// decoded or were compiled from files that have since changed are removed from the cache.
+	///
+	/// # Arguments
+	///
+	/// * `key` – The key to look up.
+	///
+	/// # Returns
+	///
+	/// The cached package if a valid entry was found, `None` otherwise.
+	#[inline(always)]
+	pub fn get (&self, key: &Key) -> Option<Package> {
+		self.getWithFileDependencies(key).map(|(package, _)| package)
+	}
+
+	/// Look up the package stored under the given key along with the files on disk it was compiled from, e.g. for
+	/// letting a build system track them. Invalid entries are removed just like in [`get`](Self::get).
+	pub fn getWithFileDependencies (&self, key: &Key) -> Option<(Package, Vec<PathBuf>)>
+	{
+		let bytes = self.storage.load(key)?;
+		let result = bitcode::decode::<Entry>(&bytes).ok().filter(|entry| entry.version == ENTRY_FORMAT_VERSION)
+			.and_then(|mut entry| {
+				// Files that were merely touched get their new state recorded so we don't re-hash them every time
+				if entry.checkUpToDate()? {
+					self.storage.store(key, &bitcode::encode(&entry)).ok();
+				}
+				Some((
+					Package::deserialize(&entry.package).ok()?,
+					entry.fileDependencies.into_iter().map(|file| PathBuf::from(file.path)).collect()
+				))
+			});
+		if result.is_none() {
+			self.storage.remove(key);
+		}
+		result
+	}
+
+	/// Store the given package under the given key.
+	///
+	/// # Arguments
+	///
+	/// * `key` – The key to store the package under.
+	/// * `package` – The package to store.
+	/// * `fileDependencies` – The files on disk the package was compiled from. The entry will be invalidated if any of
+	///                        them changes.
+	///
+	/// # Returns
+	///
+	/// `Ok` if the package was stored, or an error describing why reading a dependency or writing to the storage
+	/// failed.
+	pub fn insert (&self, key: &Key, package: &Package, fileDependencies: &[impl AsRef<Path>]) -> anyhow::Result<()>
+	{
+		let fileDependencies = fileDependencies.iter().map(
+			|path| FileDependency::capture(path.as_ref())
+		).collect::<std::io::Result<_>>()?;
+		let entry = Entry { version: ENTRY_FORMAT_VERSION, fileDependencies, package: package.serialize() };
+		self.storage.store(key, &bitcode::encode(&entry))
+	}
+
+	/// Look up the package stored under the given key, or build and store it if there is no valid entry.
+	///
+	/// # Arguments
+	///
+	/// * `key` – The key to look up.
+	/// * `build` – Callback producing the package along with the files on disk it was compiled from.
+	///
+	/// # Returns
+	///
+	/// The cached or newly built package, or the error returned by `build`. Failing to store the newly built package is
+	/// not considered an error, as the cache is merely an optimization.
+	pub fn getOrTryInsertWith<Error> (
+		&self, key: &Key, build: impl FnOnce()->Result<(Package, Vec<PathBuf>), Error>
+	) -> Result<Package, Error>
+	{
+		if let Some(package) = self.get(key) {
+			return Ok(package);
+		}
+		let (package, fileDependencies) = build()?;
+		self.insert(key, &package, &fileDependencies).ok();
+		Ok(package)
+	}
+
+	/// Infallible version of [`getOrTryInsertWith`](Self::getOrTryInsertWith).
+	#[inline(always)]
+	pub fn getOrInsertWith (&self, key: &Key, build: impl FnOnce()->(Package, Vec<PathBuf>)) -> Package {
+		let Ok(package) = self.getOrTryInsertWith(key, || Ok::<_, std::convert::Infallible>(build()));
+		package
+	}
+
+	/// Remove the entry stored under the given key, if any.
+	#[inline(always)]
+	pub fn remove (&self, key: &Key) {
+		self.storage.remove(key)
+	}
+}
+
+
+/// A cache [`Storage`] keeping each entry in its own file inside a directory.
+#[cfg(not(target_arch="wasm32"))]
+pub struct DirectoryStorage {
+	directory: PathBuf
+}
+#[cfg(not(target_arch="wasm32"))]
+impl DirectoryStorage
+{
+	/// Create a storage in the given directory. The directory is only created once the first entry gets stored.
+	pub fn new (directory: impl AsRef<Path>) -> Self {
+		Self { directory: directory.as_ref().to_owned() }
+	}
+
+	/// Determine the file the entry with the given key is stored in.
+	#[inline(always)]
+	fn entryFilename (&self, key: &Key) -> PathBuf {
+		self.directory.join(format!("{key}.spkc"))
+	}
+}
+#[cfg(not(target_arch="wasm32"))]
+impl Storage for DirectoryStorage
+{
+	fn load (&self, key: &Key) -> Option<Vec<u8>> {
+		std::fs::read(self.entryFilename(key)).ok()
+	}
+
+	fn store (&self, key: &Key, bytes: &[u8]) -> anyhow::Result<()>
+	{
+		// Write to a uniquely named temporary file first and then move it into place, so concurrent readers (possibly
+		// from other processes) never see partially written entries
+		std::fs::create_dir_all(&self.directory)?;
+		let tmpFilename = self.directory.join(format!("{key}.{}.tmp", uniqueAnonymousName()));
+		std::fs::write(&tmpFilename, bytes)?;
+		std::fs::rename(&tmpFilename, self.entryFilename(key)).inspect_err(
+			|_| { std::fs::remove_file(&tmpFilename).ok(); }
+		)?;
+		Ok(())
+	}
+
+	fn remove (&self, key: &Key) {
+		std::fs::remove_file(self.entryFilename(key)).ok();
+	}
+}
+
+
+/// A cache [`Storage`] keeping entries in the browser's
+/// [local storage](https://developer.mozilla.org/en-US/docs/Web/API/Window/localStorage).
+#[cfg(target_arch="wasm32")]
+pub struct BrowserStorage {
+	prefix: String
+}
+#[cfg(target_arch="wasm32")]
+impl BrowserStorage
+{
+	/// Create a storage that prefixes all its local storage keys with the given string.
+	pub fn new (prefix: &str) -> Self {
+		Self { prefix: prefix.to_owned() }
+	}
+
+	/// Obtain the local storage of the current browser window, if available.
+	fn localStorage () -> Option<web_sys::Storage> {
+		web_sys::window()?.local_storage().ok()?
+	}
+
+	/// Determine the local storage key of the entry with the given key.
+	#[inline(always)]
+	fn itemName (&self, key: &Key) -> String {
+		format!("{}{key}", self.prefix)
+	}
+}
+#[cfg(target_arch="wasm32")]
+impl Storage for BrowserStorage
+{
+	fn load (&self, key: &Key) -> Option<Vec<u8>> {
+		// Local storage only holds strings, so bytes are stored as one character each (see `store`)
+		let item = Self::localStorage()?.get_item(&self.itemName(key)).ok()??;
+		item.chars().map(|c| u8::try_from(c).ok()).collect()
+	}
+
+	fn store (&self, key: &Key, bytes: &[u8]) -> anyhow::Result<()>
+	{
+		// Map each byte to the character of the same code point – browsers store strings as UTF-16, so this takes up
+		// exactly one code unit per byte, which is the best we can do with local storage
+		let storage = Self::localStorage().ok_or_else(|| anyhow!("browser local storage unavailable"))?;
+		let item: String = bytes.iter().map(|&b| b as char).collect();
+		storage.set_item(&self.itemName(key), &item).map_err(|err| anyhow!("local storage error: {err:?}"))
+	}
+
+	fn remove (&self, key: &Key) {
+		if let Some(storage) = Self::localStorage() {
+			storage.remove_item(&self.itemName(key)).ok();
+		}
+	}
+}
+
+
+
+//////
+//
+// Functions
+//
+
+/// Compute a stable (i.e. platform- and toolchain-independent) digest of the given bytes.
+#[inline(always)]
+fn digestOf (bytes: &[u8]) -> Uuid {
+	Uuid::new_v5(cgv_util::meta::uuidNamespace(), bytes)
+}
+
+/// Query the size and the modification time (in nanoseconds since the Unix epoch, or *0* if the platform does not
+/// report it) of the file at the given path.
+fn fileStamp (path: &Path) -> std::io::Result<(u64, u64)>
+{
+	let metadata = std::fs::metadata(path)?;
+	let modified = metadata.modified().ok().and_then(
+		|time| time.duration_since(std::time::UNIX_EPOCH).ok()
+	).map_or(0, |duration| duration.as_nanos() as u64);
+	Ok((metadata.len(), modified))
+}
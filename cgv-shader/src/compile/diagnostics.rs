@@ -0,0 +1,355 @@
+
+//////
+//
+// Imports
+//
+
+// Standard library
+use std::{error::Error, fmt::{Display, Formatter}, ops::Deref, path::{Path, PathBuf}};
+
+
+
+//////
+//
+// Enums
+//
+
+/// The severity of a [`Diagnostic`] reported by a shader compiler.
+#[derive(Clone,Copy,Debug,PartialEq,Eq,PartialOrd,Ord,Hash)]
+pub enum Severity {
+	/// Additional information, usually attached to a preceding warning or error.
+	Note,
+
+	/// A potential problem that did not prevent compilation.
+	Warning,
+
+	/// A problem that caused compilation to fail.
+	Error,
+
+	/// A problem that caused the compiler to stop processing altogether (includes internal compiler errors).
+	Fatal
+}
+impl Severity
+{
+	/// Parse the severity from the keyword used in *Slang* diagnostic output.
+	fn fromKeyword (keyword: &str) -> Option<Self> {
+		match keyword {
+			"note" => Some(Self::Note),
+			"warning" => Some(Self::Warning),
+			"error" => Some(Self::Error),
+			"fatal error" | "internal error" | "fatal" => Some(Self::Fatal),
+			_ => None
+		}
+	}
+}
+impl Display for Severity
+{
+	fn fmt (&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+		formatter.write_str(match self {
+			Self::Note => "note",
+			Self::Warning => "warning",
+			Self::Error => "error",
+			Self::Fatal => "fatal error"
+		})
+	}
+}
+
+
+
+//////
+//
+// Structs
+//
+
+/// A single structured diagnostic record emitted by a shader compiler.
+#[derive(Clone,Debug,PartialEq,Eq)]
+pub struct Diagnostic
+{
+	/// The file the diagnostic refers to, if any. For modules compiled from source strings, this will be the virtual
+	/// filepath the module was given.
+	pub file: Option<PathBuf>,
+
+	/// The 1-based line the diagnostic refers to, if known. Some compilers report line *0* for diagnostics that refer
+	/// to the file as a whole.
+	pub line: Option<u32>,
+
+	/// The 1-based column the diagnostic refers to, if known. May be *0* if the diagnostic refers to a line as a whole.
+	pub column: Option<u32>,
+
+	/// How severe the diagnosed problem is.
+	pub severity: Severity,
+
+	/// The compiler-specific diagnostic code, if any.
+	pub code: Option<u32>,
+
+	/// The human-readable diagnostic message.
+	pub message: String
+}
+impl Diagnostic
+{
+	/// Check whether the diagnostic reports an actual error, i.e. something that causes compilation to fail.
+	#[inline(always)]
+	pub fn isError (&self) -> bool {
+		self.severity >= Severity::Error
+	}
+
+	/// Check whether the diagnostic refers to the given file. Paths are compared component-wise from the end, so
+	/// `shader/lib/foo.slang` will be considered to refer to `lib/foo.slang` and vice versa.
+	pub fn isInFile (&self, path: impl AsRef<Path>) -> bool {
+		self.file.as_ref().is_some_and(|file| file.ends_with(path.as_ref()) || path.as_ref().ends_with(file))
+	}
+
+	/// Render the diagnostic in a human-readable multi-line form, including an excerpt of the offending source line
+	/// with the column marked if the source code of the referenced file is provided.
+	///
+	/// # Arguments
+	///
+	/// * `source` – The source code of the file the diagnostic refers to, if available.
+	///
+	/// # Returns
+	///
+	/// The rendered diagnostic, without trailing newline.
+	pub fn render (&self, source: Option<&str>) -> String
+	{
+		// Header
+		let mut rendered = match self.code {
+			Some(code) => format!("{}[{code}]: {}", self.severity, self.message),
+			None => format!("{}: {}", self.severity, self.message)
+		};
+
+		// Location
+		let Some(file) = &self.file else { return rendered };
+		rendered += &format!("\n  --> {}", file.display());
+		let Some(line) = self.line else { return rendered };
+		rendered += &format!(":{line}");
+		if let Some(column) = self.column {
+			rendered += &format!(":{column}");
+		}
+
+		// Source excerpt (compilers report line 0 for diagnostics that refer to the file as a whole)
+		let excerpt = (line as usize).checked_sub(1).and_then(|index| source.and_then(|src| src.lines().nth(index)));
+		if let Some(excerpt) = excerpt
+		{
+			let lineNo = line.to_string();
+			let gutter = " ".repeat(lineNo.len());
+			rendered += &format!("\n{gutter} |\n{lineNo} | {excerpt}");
+			if let Some(column) = self.column.and_then(|column| (column as usize).checked_sub(1)) {
+				// Keep tabs so the marker lines up with the excerpt regardless of tab width
+				let indent: String = excerpt.chars().take(column).map(
+					|c| if c == '\t' { '\t' } else { ' ' }
+				).collect();
+				rendered += &format!("\n{gutter} | {indent}^");
+			}
+		}
+		rendered
+	}
+}
+impl Display for Diagnostic
+{
+	fn fmt (&self, formatter: &mut Formatter<'_>) -> std::fmt::Result
+	{
+		if let Some(file) = &self.file
+		{
+			write!(formatter, "{}", file.display())?;
+			if let Some(line) = self.line {
+				write!(formatter, ":{line}")?;
+			}
+			if let Some(column) = self.column {
+				write!(formatter, ":{column}")?;
+			}
+			formatter.write_str(": ")?;
+		}
+		match self.code {
+			Some(code) => write!(formatter, "{} {code}: {}", self.severity, self.message),
+			None => write!(formatter, "{}: {}", self.severity, self.message)
+		}
+	}
+}
+
+
+/// A collection of [`Diagnostic`]s parsed from the output of a shader compiler, retaining the original output. Shader
+/// compilation contexts attach this to the [errors](super::LoadModuleError) they report, where it can be retrieved
+/// via the `diagnostics()` method of the error or via [`diagnosticsOf`].
+#[derive(Clone,Debug,Default)]
+pub struct Diagnostics {
+	records: Vec<Diagnostic>,
+	raw: String
+}
+impl Diagnostics
+{
+	/// Parse the textual diagnostic output of the *Slang* compiler. Lines of the form
+	/// `path(line[,column]): severity [code]: message` start a new record, and the source excerpt plus marker lines
+	/// *Slang* prints after them are used to infer the column if it was not stated explicitly. Any text that does not
+	/// belong to a recognized record is still retained in the [raw output](Self::raw).
+	pub fn parse (text: &str) -> Self
+	{
+		let mut records: Vec<Diagnostic> = Vec::new();
+		for line in text.lines()
+		{
+			if let Some(record) = parseRecordLine(line) {
+				records.push(record);
+			}
+			else if let Some(last) = records.last_mut() && last.column.is_none() && last.line.is_some()
+			        && let Some(caret) = markerColumn(line) {
+				last.column = Some(caret);
+			}
+		}
+		Self { records, raw: text.trim_end().to_owned() }
+	}
+
+	/// Create from an arbitrary compiler backend error by parsing its textual representation.
+	#[inline(always)]
+	pub fn fromBackendError (err: impl Display) -> Self {
+		Self::parse(&err.to_string())
+	}
+
+	/// Create from records a compiler reported in structured form, along with the textual output it rendered for
+	/// them.
+	pub fn fromRecords (records: Vec<Diagnostic>, raw: impl Into<String>) -> Self {
+		Self { records, raw: raw.into().trim_end().to_owned() }
+	}
+
+	/// Reference the parsed diagnostic records.
+	#[inline(always)]
+	pub fn records (&self) -> &[Diagnostic] {
+		&self.records
+	}
+
+	/// Reference the unaltered compiler output the records were parsed from.
+	#[inline(always)]
+	pub fn raw (&self) -> &str {
+		&self.raw
+	}
+
+	/// Iterate over the records that report actual errors.
+	pub fn errors (&self) -> impl Iterator<Item=&Diagnostic> {
+		self.records.iter().filter(|d| d.isError())
+	}
+
+	/// Iterate over the records that refer to the given file (see [`Diagnostic::isInFile`]).
+	pub fn inFile<'this> (&'this self, path: &'this Path) -> impl Iterator<Item=&'this Diagnostic> {
+		self.records.iter().filter(move |d| d.isInFile(path))
+	}
+
+	/// Render all records in a human-readable form with source excerpts.
+	///
+	/// # Arguments
+	///
+	/// * `loadSource` – Callback for obtaining the source code of a file referenced by a diagnostic. May return `None`
+	///                  if the source is unavailable, in which case the excerpt is omitted.
+	///
+	/// # Returns
+	///
+	/// The rendered diagnostics separated by blank lines, or the raw compiler output if no records could be parsed.
+	pub fn render (&self, mut loadSource: impl FnMut(&Path)->Option<String>) -> String
+	{
+		if self.records.is_empty() {
+			return self.raw.clone();
+		}
+		self.records.iter().map(|d| {
+			let source = d.file.as_deref().and_then(&mut loadSource);
+			d.render(source.as_deref())
+		}).collect::<Vec<_>>().join("\n\n")
+	}
+
+	/// [Render](Self::render) the diagnostics with source excerpts read from disk. Since compilers may refer to files
+	/// relative to their module search path, files that cannot be found as given are looked up relative to each of the
+	/// given directories in turn.
+	///
+	/// # Arguments
+	///
+	/// * `searchPath` – The directories to look up relatively referenced files in.
+	///
+	/// # Returns
+	///
+	/// The rendered diagnostics, as returned by [`render`](Self::render).
+	pub fn renderFromFiles (&self, searchPath: &[impl AsRef<Path>]) -> String {
+		self.render(|file|
+			std::iter::once(file.to_owned()).chain(searchPath.iter().map(|dir| dir.as_ref().join(file))).find_map(
+				|candidate| std::fs::read_to_string(candidate).ok()
+			)
+		)
+	}
+}
+impl Deref for Diagnostics {
+	type Target = [Diagnostic];
+
+	#[inline(always)]
+	fn deref (&self) -> &Self::Target {
+		&self.records
+	}
+}
+impl Display for Diagnostics {
+	fn fmt (&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+		formatter.write_str(&self.raw)
+	}
+}
+impl Error for Diagnostics {}
+
+
+
+//////
+//
+// Functions
+//
+
+/// Try to parse the header line of a *Slang* diagnostic record.
+fn parseRecordLine (line: &str) -> Option<Diagnostic>
+{
+	// Split off the location, if any
+	let (file, lineNo, column, rest) = match line.find("): ") {
+		Some(locEnd) if !line.starts_with(' ') && !line.starts_with('\t') => {
+			let locStart = line[..locEnd].rfind('(')?;
+			let mut coords = line[locStart+1..locEnd].split(',').map(|c| c.trim().parse::<u32>());
+			let lineNo = coords.next()?.ok()?;
+			let column = match coords.next() {
+				Some(column) => Some(column.ok()?),
+				None => None
+			};
+			(Some(PathBuf::from(&line[..locStart])), Some(lineNo), column, &line[locEnd+3..])
+		},
+		_ => (None, None, None, line)
+	};
+
+	// Parse severity and code
+	let (head, message) = rest.split_once(':')?;
+	let head = head.trim();
+	let (severity, code) = match head.rsplit_once(' ') {
+		Some((keyword, code)) if code.chars().all(|c| c.is_ascii_digit()) =>
+			(Severity::fromKeyword(keyword)?, Some(code.parse().ok()?)),
+		_ => (Severity::fromKeyword(head)?, None)
+	};
+
+	// Done!
+	Some(Diagnostic { file, line: lineNo, column, severity, code, message: message.trim().to_owned() })
+}
+
+/// Infer the 1-based column from a *Slang* marker line (e.g. `    ^~~~`), if the given line is one.
+fn markerColumn (line: &str) -> Option<u32>
+{
+	if !line.contains('^') || !line.chars().all(|c| c.is_whitespace() || c == '^' || c == '~') {
+		return None;
+	}
+	line.chars().position(|c| c == '^').map(|pos| pos as u32 + 1)
+}
+
+/// Walk the [source chain](Error::source) of the given error and return the first [`Diagnostics`] encountered.
+///
+/// # Arguments
+///
+/// * `err` – The error to inspect.
+///
+/// # Returns
+///
+/// The diagnostics attached to the error or one of its sources, if any.
+pub fn diagnosticsOf<'err> (err: &'err (dyn Error+'static)) -> Option<&'err Diagnostics>
+{
+	let mut current = Some(err);
+	while let Some(err) = current {
+		if let Some(diagnostics) = err.downcast_ref::<Diagnostics>() {
+			return Some(diagnostics);
+		}
+		current = err.source();
+	}
+	None
+}
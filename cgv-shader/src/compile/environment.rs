@@ -19,7 +19,21 @@ use uuid::Uuid;
 use cgv_util::{self as util, ds::UniqueVecElement};
 
 // Local imports
-use crate::compile;
+use crate::{compile, container};
+
+
+
+//////
+//
+// Constants
+//
+
+/// The container [format](container::Format) of serialized [`Environment`]s.
+pub const ENVIRONMENT_FORMAT: container::Format = container::Format {
+	name: "shader compilation environment", magic: *b"CGV-ENV\x1A", version: 1,
+	// Legacy header-less environments encode the environment exactly like version 1 does
+	migrations: &[container::Migration::identity(0)]
+};
 
 
 
@@ -203,9 +217,9 @@ impl<ModuleType> Environment<ModuleType>
 	////
 	// Constructors
 
-	///
+	/// Deserialize from the given bytes, which must be a container of the [environment format](ENVIRONMENT_FORMAT).
 	pub fn deserialize (bytes: &[u8]) -> anyhow::Result<Self> {
-		Ok(postcard::from_bytes(bytes).map_err(anyhow::Error::new)?)
+		Ok(ENVIRONMENT_FORMAT.decode(bytes, |payload| Ok(postcard::from_bytes(payload)?))?)
 	}
 
 	///
@@ -239,9 +253,15 @@ impl<ModuleType> Environment<ModuleType>
 	////
 	// Methods
 
-	///
+	/// Serialize the environment into a series of bytes, using the [default compression](container::Compression::default).
+	#[inline(always)]
 	pub fn serialize (&self) -> Vec<u8> {
-		postcard::to_allocvec(self).unwrap()
+		self.serializeWithCompression(container::Compression::default())
+	}
+
+	/// Serialize the environment into a series of bytes, compressing the payload with the given method.
+	pub fn serializeWithCompression (&self, compression: container::Compression) -> Vec<u8> {
+		ENVIRONMENT_FORMAT.pack(&postcard::to_allocvec(self).unwrap(), compression)
 	}
 
 	///
@@ -294,6 +314,18 @@ impl<ModuleType> Environment<ModuleType>
 		self.compatHash
 	}
 
+	/// Compute a digest of the actual contents of the environment, i.e. its compatibility hash plus the paths and
+	/// contents of all its modules. Unlike the [UUID](Environment::uuid), the digest stays the same for clones and
+	/// merges that do not alter any modules, which makes it suitable for keying caches of compilation results.
+	pub fn contentDigest (&self) -> Uuid
+	{
+		let mut material = self.compatHash.to_le_bytes().to_vec();
+		for entry in self.modules.iter() {
+			material.extend(postcard::to_allocvec(&(&entry.path, &entry.module)).unwrap());
+		}
+		Uuid::new_v5(util::meta::uuidNamespace(), &material)
+	}
+
 	/// Report the number of [modules](ModuleEntry) currently in the environment.
 	#[inline]
 	pub fn numModules (&self) -> usize {
@@ -7,7 +7,7 @@
 use std::error::Error;
 use std::fmt::{Display, Formatter};
 // Standard library
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 // Local imports
 use super::compile;
@@ -140,6 +140,13 @@ pub trait Module<EntryPointType: EntryPoint>: Component
 
 	///
 	fn entryPoints (&self) -> &[EntryPointType];
+
+	/// Report the files on disk the module was compiled from, including all files it (transitively) imports. Modules
+	/// compiled from in-memory source code or taken from a [`compile::Environment`] do not contribute any files, which
+	/// is what the default implementation reports.
+	fn fileDependencies (&self) -> Vec<PathBuf> {
+		Vec::new()
+	}
 }
 
 
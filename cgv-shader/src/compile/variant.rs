@@ -0,0 +1,200 @@
+//////
+//
+// Imports
+//
+
+// Standard library
+use std::{borrow::Cow, collections::{BTreeMap, BTreeSet}, fmt::Write, hash::{Hash, Hasher}, path::{Path, PathBuf}};
+
+
+
+//////
+//
+// Structs
+//
+
+/// Describes one variant of a shader program, i.e. the compile-time configuration that a [`Package`](crate::Package)
+/// instantiates the program under. A variant consists of
+///
+/// * preprocessor **defines** that get prepended to the source code of the main module, and
+/// * link-time **specialization arguments** that bind `extern` types and constants declared by the program. For each
+///   variant with specialization arguments, an additional module consisting of the corresponding `export` declarations
+///   is generated and linked together with the main module. Modules declaring the interfaces and implementing types
+///   that the generated module refers to must be made available to it via [`import`](Self::import).
+///
+/// Packages store the instances for each variant under its [key](Self::key).
+///
+/// Note that defines only affect the main module – like in C/C++, defines do not propagate into imported modules. Use
+/// the defines of the [compile context](super::ContextBuilder::addDefines) for configuring the whole session instead.
+#[derive(Debug,Default,Clone,PartialEq,Eq)]
+pub struct Variant {
+	name: Option<String>,
+	defines: BTreeMap<String, String>,
+	types: BTreeMap<String, (String, String)>,
+	constants: BTreeMap<String, (String, String)>,
+	imports: BTreeSet<String>
+}
+impl Variant
+{
+	/// Create the default variant, which neither defines any macros nor specializes anything.
+	#[inline(always)]
+	pub fn new () -> Self {
+		Self::default()
+	}
+
+	/// Create an empty variant that will be stored under the given key instead of a derived one.
+	#[inline(always)]
+	pub fn named (name: impl Into<String>) -> Self {
+		Self { name: Some(name.into()), ..Default::default() }
+	}
+
+	/// Add the preprocessor define `#define <name> <value>`.
+	pub fn define (mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+		self.defines.insert(name.into(), value.into());
+		self
+	}
+
+	/// Bind the type declared as `extern struct <name>: <interface>;` to `implementation`.
+	pub fn specializeType (
+		mut self, name: impl Into<String>, interface: impl Into<String>, implementation: impl Into<String>
+	) -> Self {
+		self.types.insert(name.into(), (interface.into(), implementation.into()));
+		self
+	}
+
+	/// Bind the constant declared as `extern static const <ty> <name>;` to `value`.
+	pub fn specializeConstant (mut self, name: impl Into<String>, ty: impl Into<String>, value: impl Into<String>)
+		-> Self
+	{
+		self.constants.insert(name.into(), (ty.into(), value.into()));
+		self
+	}
+
+	/// Make the module at the given path available to the generated specialization module, e.g. because it declares an
+	/// interface or implementing type used in the specialization arguments.
+	pub fn import (mut self, modulePath: impl Into<String>) -> Self {
+		self.imports.insert(modulePath.into());
+		self
+	}
+
+	/// The key under which a [`Package`](crate::Package) stores the instances for this variant. Unless the variant was
+	/// [named](Self::named), the key is derived from the defines and specialization arguments in a canonical way, e.g.
+	/// `WITH_COLORS=1,Material->Phong` – the default variant maps to the empty key.
+	pub fn key (&self) -> String
+	{
+		if let Some(name) = &self.name {
+			return name.clone();
+		}
+		let defines = self.defines.iter().map(|(name, value)| format!("{name}={value}"));
+		let types = self.types.iter().map(|(name, (_, implementation))| format!("{name}->{implementation}"));
+		let constants = self.constants.iter().map(|(name, (_, value))| format!("{name}->{value}"));
+		defines.chain(types).chain(constants).collect::<Vec<_>>().join(",")
+	}
+
+	/// Iterate over all defines of the variant as `(name, value)` pairs.
+	pub fn defines (&self) -> impl Iterator<Item=(&str, &str)> {
+		self.defines.iter().map(|(name, value)| (name.as_str(), value.as_str()))
+	}
+
+	/// Check whether the variant binds any link-time specialization points.
+	#[inline(always)]
+	pub fn hasSpecializations (&self) -> bool {
+		!self.types.is_empty() || !self.constants.is_empty()
+	}
+
+	/// Derive the virtual path to compile the main module of the program under for this variant from the path of the
+	/// program. The default variant uses the program path unchanged, every other variant gets a unique path in the same
+	/// directory so that relative imports keep working.
+	pub fn modulePath (&self, programPath: &Path) -> PathBuf {
+		let extension = programPath.extension().map_or("slang".into(), |ext| ext.to_string_lossy());
+		self.derivedPath(programPath, "", &extension)
+	}
+
+	/// The virtual path to compile the generated [specialization module](Self::specializationModuleSource) under.
+	/// Since the module is always written in *Slang*, it gets a `.slang` extension regardless of the program's.
+	pub fn specializationModulePath (&self, programPath: &Path) -> PathBuf {
+		self.derivedPath(programPath, "_specialization", "slang")
+	}
+
+	/// Prepend the defines of the variant to the given source code of the module at the given path.
+	pub fn preprocessedSource<'src> (&self, modulePath: &Path, sourceCode: &'src str) -> Cow<'src, str> {
+		sourceWithDefines(modulePath, self.defines(), sourceCode)
+	}
+
+	/// Generate the source code of the module binding the specialization points of the program, if the variant has any
+	/// specialization arguments.
+	pub fn specializationModuleSource (&self) -> Option<String>
+	{
+		if !self.hasSpecializations() {
+			return None;
+		}
+		let mut src = String::new();
+		for modulePath in &self.imports {
+			writeln!(src, "import \"{modulePath}\";").unwrap();
+		}
+		for (name, (interface, implementation)) in &self.types {
+			writeln!(src, "export struct {name}: {interface} = {implementation};").unwrap();
+		}
+		for (name, (ty, value)) in &self.constants {
+			writeln!(src, "export static const {ty} {name} = {value};").unwrap();
+		}
+		Some(src)
+	}
+
+	/// Helper for deriving variant-specific module paths.
+	fn derivedPath (&self, programPath: &Path, suffix: &str, extension: &str) -> PathBuf
+	{
+		if *self == Self::default() && suffix.is_empty() {
+			return programPath.to_owned();
+		}
+		let mut hasher = std::hash::DefaultHasher::new();
+		self.key().hash(&mut hasher);
+		let stem = programPath.file_stem().map(|stem| stem.to_string_lossy()).unwrap_or_default();
+		programPath.with_file_name(format!("{stem}__variant_{:016x}{suffix}.{extension}", hasher.finish()))
+	}
+}
+
+
+
+//////
+//
+// Functions
+//
+
+/// Prepend `#define` directives for the given defines to the given source code of the module at the given path,
+/// followed by a `#line` directive that keeps the line numbers in diagnostics referring to the original source.
+pub(crate) fn sourceWithDefines<'src, 'def> (
+	modulePath: &Path, defines: impl Iterator<Item=(&'def str, &'def str)>, sourceCode: &'src str
+) -> Cow<'src, str>
+{
+	let mut src = String::new();
+	for (name, value) in defines {
+		writeln!(src, "#define {name} {value}").unwrap();
+	}
+	if src.is_empty() {
+		return Cow::Borrowed(sourceCode);
+	}
+	writeln!(src, "#line 1 \"{}\"", modulePath.to_string_lossy().replace('\\', "/")).unwrap();
+	src.push_str(sourceCode);
+	Cow::Owned(src)
+}
+
+/// The inverse of [`sourceWithDefines`] for compile contexts whose front-ends take defines separately from the source
+/// code: split the `#define` directives and the `#line` directive following them off the front of the given source
+/// code. Source code that does not start with such a prelude is returned unchanged, without any defines.
+pub(crate) fn splitDefines (sourceCode: &str) -> (Vec<(&str, &str)>, &str)
+{
+	let mut defines = Vec::new();
+	let mut rest = sourceCode;
+	while let Some(directive) = rest.strip_prefix("#define ")
+	{
+		let (line, next) = directive.split_once('\n').unwrap_or((directive, ""));
+		let line = line.trim_end();
+		defines.push(line.split_once(' ').unwrap_or((line, "")));
+		rest = next;
+	}
+	match rest.strip_prefix("#line ").and_then(|directive| directive.split_once('\n')) {
+		Some((_, code)) if !defines.is_empty() => (defines, code),
+		_ => (Vec::new(), sourceCode)
+	}
+}
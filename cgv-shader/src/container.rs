@@ -0,0 +1,380 @@
+//////
+//
+// Imports
+//
+
+// Standard library
+use std::fmt::{Display, Formatter};
+
+// Anyhow library
+use anyhow;
+
+// CRC32 library
+use crc32fast as crc32;
+
+// LZ4 library
+use lz4_flex;
+
+
+
+//////
+//
+// Constants
+//
+
+/// The version of the container layout itself (i.e. of the [`Header`]), independent of the versions of the payload
+/// formats stored in containers.
+pub const CONTAINER_VERSION: u16 = 1;
+
+/// The number of bytes every container header occupies in front of the payload.
+pub const HEADER_SIZE: usize = 28;
+
+/// The leading bytes shared by the magic numbers of all *CGV-rs* container formats.
+const MAGIC_PREFIX: &[u8; 4] = b"CGV-";
+
+/// The largest ratio between uncompressed and compressed size that an *LZ4* block can achieve. Announced payload
+/// lengths beyond this are rejected before decompressing, so corrupted or hostile data can't cause huge allocations.
+const MAX_LZ4_RATIO: usize = 255;
+
+
+
+//////
+//
+// Errors
+//
+
+/// Error conditions when unpacking the payload of a container.
+#[derive(Debug)]
+pub enum UnpackError
+{
+	/// The bytes are too short to hold the container header or the payload it announces.
+	Truncated,
+
+	/// The bytes are a *CGV-rs* container, but of a different format than the one requested. Holds the name of the
+	/// requested format.
+	WrongFormat(&'static str),
+
+	/// The container header was written by an unknown version of the container layout.
+	UnsupportedContainerVersion(u16),
+
+	/// The payload was written in a format version that can't be loaded, either because it is newer than the one
+	/// supported by this build or because no migration path from it exists.
+	IncompatibleVersion { format: &'static str, found: u16, supported: u16 },
+
+	/// The checksum stored in the header does not match the header fields and payload.
+	ChecksumMismatch { stored: u32, computed: u32 },
+
+	/// The header announces an unknown compression method.
+	UnknownCompression(u8),
+
+	/// The payload length announced by the header can't result from the stored payload bytes.
+	InconsistentLength { stored: u64, announced: u64 },
+
+	/// The payload could not be decompressed.
+	Decompression(anyhow::Error),
+
+	/// Migrating the payload from an older format version failed. Holds the version the failing migration started
+	/// from along with the error it reported.
+	Migration { from: u16, error: anyhow::Error },
+
+	/// The payload was accepted by the container but could not be decoded. Holds the version the payload was
+	/// originally stored in along with the decoding error.
+	Decoding { format: &'static str, version: u16, error: anyhow::Error }
+}
+impl Display for UnpackError
+{
+	fn fmt (&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+		let desc = match self {
+			Self::Truncated => "data is truncated".into(),
+			Self::WrongFormat(expected) => format!("not a {expected}"),
+			Self::UnsupportedContainerVersion(version) => format!("unsupported container version {version}"),
+			Self::IncompatibleVersion { format, found, supported } => if found > supported { format!(
+				"incompatible version: {format} was written in format version {found}, but only versions up to \
+				 {supported} are supported – it was probably created by a newer version of CGV-rs"
+			)}
+			else { format!(
+				"incompatible version: {format} was written in format version {found} which can't be migrated \
+				 to the current version {supported} – please re-create it"
+			)},
+			Self::ChecksumMismatch { stored, computed } => format!(
+				"checksum mismatch (stored: {stored:#010x}, computed: {computed:#010x}) – data is corrupted"
+			),
+			Self::UnknownCompression(method) => format!("unknown compression method {method}"),
+			Self::InconsistentLength { stored, announced } => format!(
+				"announced payload length {announced} is inconsistent with {stored} stored bytes – data is corrupted"
+			),
+			Self::Decompression(err) => format!("decompression failed: {err}"),
+			Self::Migration { from, error } => format!("migration from format version {from} failed: {error}"),
+			Self::Decoding { format, version: 0, error } => format!(
+				"legacy (header-less) {format} could not be decoded, it was probably created by an older version of \
+				 CGV-rs – please re-create it ({error})"
+			),
+			Self::Decoding { format, version, error } => format!(
+				"{format} (format version {version}) could not be decoded: {error}"
+			)
+		};
+		write!(formatter, "UnpackError[{desc}]")
+	}
+}
+impl std::error::Error for UnpackError {
+	fn source (&self) -> Option<&(dyn std::error::Error+'static)> {
+		match self {
+			Self::Decompression(error) | Self::Migration { error, .. } | Self::Decoding { error, .. }
+				=> Some(error.as_ref()),
+			_ => None
+		}
+	}
+}
+
+
+
+//////
+//
+// Enums
+//
+
+/// The compression methods the payload of a container can be stored with.
+#[derive(Debug,Default,Clone,Copy,PartialEq,Eq)]
+#[repr(u8)]
+pub enum Compression {
+	/// The payload is stored as-is.
+	None = 0,
+
+	/// The payload is compressed as a single [*LZ4*](https://lz4.org) block.
+	#[default]
+	Lz4 = 1
+}
+impl Compression
+{
+	/// Get the compression method identified by the given header byte, if it is known.
+	pub fn fromByte (byte: u8) -> Option<Self> {
+		match byte {
+			0 => Some(Self::None),
+			1 => Some(Self::Lz4),
+			_ => None
+		}
+	}
+}
+
+
+
+//////
+//
+// Structs
+//
+
+/// A step in the migration path of a container [`Format`], converting a payload from one format version to the next.
+#[derive(Clone,Copy)]
+pub struct Migration {
+	/// The format version the migration converts from. It produces a payload of version `from+1`.
+	pub from: u16,
+
+	/// The conversion function.
+	pub migrate: fn(Vec<u8>) -> anyhow::Result<Vec<u8>>
+}
+impl Migration
+{
+	/// A migration from the given version to the next that leaves the payload untouched, for format versions that only
+	/// differ in the container around the payload but not in the payload encoding itself.
+	pub const fn identity (from: u16) -> Self {
+		Self { from, migrate: Ok }
+	}
+}
+
+/// Describes a payload format that can be stored in a container.
+///
+/// Whenever the encoding of the payload changes, the `version` must be bumped and a [`Migration`] from the previous
+/// version be added to `migrations` – or, if the old payloads can't be converted, loaders will report an
+/// [incompatible version](UnpackError::IncompatibleVersion) instead of misreading them. Data stored without any
+/// container header, as written before containers were introduced, is considered to be of version `0`.
+pub struct Format {
+	/// Human-readable name of what the format stores, for use in error messages.
+	pub name: &'static str,
+
+	/// The magic number identifying the format. Must start with `CGV-`.
+	pub magic: [u8; 8],
+
+	/// The current version of the payload format.
+	pub version: u16,
+
+	/// The migration path from older versions of the payload format.
+	pub migrations: &'static [Migration]
+}
+impl Format
+{
+	/// Store the given payload in a container of this format, compressing it with the given method.
+	pub fn pack (&self, payload: &[u8], compression: Compression) -> Vec<u8>
+	{
+		let compressed;
+		let stored = match compression {
+			Compression::None => payload,
+			Compression::Lz4 => {
+				compressed = lz4_flex::block::compress(payload);
+				compressed.as_slice()
+			}
+		};
+		let header = Header {
+			containerVersion: CONTAINER_VERSION, formatVersion: self.version, compression, checksum: 0,
+			payloadLength: payload.len() as u64
+		};
+		let mut bytes = Vec::with_capacity(HEADER_SIZE + stored.len());
+		header.writeTo(&self.magic, &mut bytes);
+		bytes.extend_from_slice(stored);
+		let checksum = Header::computeChecksum(&bytes);
+		bytes[Header::CHECKSUM_RANGE].copy_from_slice(&checksum.to_le_bytes());
+		bytes
+	}
+
+	/// Read the header of the given container bytes. Returns `None` if the bytes are not a container of this format,
+	/// which is the case for legacy header-less data.
+	pub fn header (&self, bytes: &[u8]) -> Result<Option<Header>, UnpackError> {
+		Header::read(&self.magic, bytes)
+	}
+
+	/// Extract the payload from the given container bytes, verifying its integrity and migrating it to the current
+	/// format version if needed. Returns the payload along with the format version it was originally stored in.
+	pub fn unpack (&self, bytes: &[u8]) -> Result<(Vec<u8>, u16), UnpackError>
+	{
+		// Determine the stored payload and its version
+		let (payload, storedVersion) = match self.header(bytes)?
+		{
+			Some(header) => {
+				let stored = &bytes[HEADER_SIZE..];
+				let computed = Header::computeChecksum(bytes);
+				if computed != header.checksum {
+					return Err(UnpackError::ChecksumMismatch { stored: header.checksum, computed });
+				}
+				let payloadLength = usize::try_from(header.payloadLength).map_err(|_| UnpackError::Truncated)?;
+				let consistent = match header.compression {
+					Compression::None => payloadLength == stored.len(),
+					Compression::Lz4 => payloadLength <= stored.len().saturating_mul(MAX_LZ4_RATIO)
+				};
+				if !consistent {
+					return Err(UnpackError::InconsistentLength {
+						stored: stored.len() as u64, announced: header.payloadLength
+					});
+				}
+				let payload = match header.compression {
+					Compression::None => stored.to_vec(),
+					Compression::Lz4 => lz4_flex::block::decompress(stored, payloadLength).map_err(
+						|err| UnpackError::Decompression(err.into())
+					)?
+				};
+				if payload.len() != payloadLength {
+					return Err(UnpackError::Truncated);
+				}
+				(payload, header.formatVersion)
+			},
+			None => {
+				// Reject containers of other formats, consider anything else legacy header-less data
+				if bytes.len() >= HEADER_SIZE && bytes.starts_with(MAGIC_PREFIX) && bytes[..8] != self.magic {
+					return Err(UnpackError::WrongFormat(self.name));
+				}
+				(bytes.to_vec(), 0)
+			}
+		};
+
+		// Bring the payload up to the current version
+		let incompatible = || UnpackError::IncompatibleVersion {
+			format: self.name, found: storedVersion, supported: self.version
+		};
+		if storedVersion > self.version {
+			return Err(incompatible());
+		}
+		let mut payload = payload;
+		for from in storedVersion..self.version
+		{
+			let migration = self.migrations.iter().find(|m| m.from == from).ok_or_else(incompatible)?;
+			payload = (migration.migrate)(payload).map_err(|error| match storedVersion {
+				// Legacy data can't be validated by the container, so failing to migrate it means it can't be decoded
+				0 => UnpackError::Decoding { format: self.name, version: 0, error },
+				_ => UnpackError::Migration { from, error }
+			})?;
+		}
+		Ok((payload, storedVersion))
+	}
+
+	/// [Unpack](Self::unpack) the given container bytes and decode the payload using the given decoder, reporting
+	/// decoding failures as [`UnpackError::Decoding`].
+	pub fn decode<T> (&self, bytes: &[u8], decoder: impl FnOnce(&[u8]) -> anyhow::Result<T>)
+		-> Result<T, UnpackError>
+	{
+		let (payload, version) = self.unpack(bytes)?;
+		decoder(&payload).map_err(|error| UnpackError::Decoding { format: self.name, version, error })
+	}
+}
+
+/// The header in front of the payload of every container.
+///
+/// It is laid out as follows, with all multibyte values in little-endian byte order:
+///
+/// | Offset | Size | Contents                                                        |
+/// |-------:|-----:|-----------------------------------------------------------------|
+/// |      0 |    8 | magic number of the [`Format`]                                  |
+/// |      8 |    2 | container layout version                                        |
+/// |     10 |    2 | payload format version                                          |
+/// |     12 |    1 | [compression](Compression) method                               |
+/// |     13 |    3 | *reserved, zero*                                                |
+/// |     16 |    4 | CRC32 of all other header fields and the stored payload bytes   |
+/// |     20 |    8 | length of the uncompressed payload                              |
+#[derive(Debug,Clone,Copy,PartialEq,Eq)]
+pub struct Header {
+	/// The version of the container layout.
+	pub containerVersion: u16,
+
+	/// The version of the payload format.
+	pub formatVersion: u16,
+
+	/// The compression method of the payload.
+	pub compression: Compression,
+
+	/// The CRC32 of all other header fields followed by the stored (i.e. potentially compressed) payload bytes.
+	pub checksum: u32,
+
+	/// The length of the payload after decompression.
+	pub payloadLength: u64
+}
+impl Header
+{
+	/// The byte range of the checksum within the header.
+	const CHECKSUM_RANGE: std::ops::Range<usize> = 16..20;
+
+	/// Compute the checksum of the given container bytes, covering everything except the checksum field itself.
+	fn computeChecksum (bytes: &[u8]) -> u32 {
+		let mut hasher = crc32::Hasher::new();
+		hasher.update(&bytes[..Self::CHECKSUM_RANGE.start]);
+		hasher.update(&bytes[Self::CHECKSUM_RANGE.end..]);
+		hasher.finalize()
+	}
+
+	/// Parse the header from the given bytes if they start with the given magic number and are long enough. Headers of
+	/// unknown container layout versions are rejected.
+	fn read (magic: &[u8; 8], bytes: &[u8]) -> Result<Option<Self>, UnpackError>
+	{
+		if bytes.len() < HEADER_SIZE || bytes[..8] != *magic {
+			return Ok(None);
+		}
+		let u16At = |offset: usize| u16::from_le_bytes([bytes[offset], bytes[offset+1]]);
+		let containerVersion = u16At(8);
+		if containerVersion != CONTAINER_VERSION {
+			return Err(UnpackError::UnsupportedContainerVersion(containerVersion));
+		}
+		Ok(Some(Self {
+			containerVersion,
+			formatVersion: u16At(10),
+			compression: Compression::fromByte(bytes[12]).ok_or(UnpackError::UnknownCompression(bytes[12]))?,
+			checksum: u32::from_le_bytes(bytes[Self::CHECKSUM_RANGE].try_into().unwrap()),
+			payloadLength: u64::from_le_bytes(bytes[20..28].try_into().unwrap())
+		}))
+	}
+
+	/// Append the binary representation of the header to the given bytes.
+	fn writeTo (&self, magic: &[u8; 8], bytes: &mut Vec<u8>) {
+		bytes.extend_from_slice(magic);
+		bytes.extend_from_slice(&self.containerVersion.to_le_bytes());
+		bytes.extend_from_slice(&self.formatVersion.to_le_bytes());
+		bytes.extend_from_slice(&[self.compression as u8, 0, 0, 0]);
+		bytes.extend_from_slice(&self.checksum.to_le_bytes());
+		bytes.extend_from_slice(&self.payloadLength.to_le_bytes());
+	}
+}
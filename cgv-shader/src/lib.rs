@@ -24,14 +24,29 @@ mod program;
 #[cfg(feature="compilation")]
 pub use program::Program; // re-export
 
+/// Submodule defining the shader compilation targets
+mod target;
+pub use target::{Target, TargetFormat}; // re-export
+
+/// Submodule implementing the versioned, integrity-checked container format of serialized packages and environments
+pub mod container;
+
 /// Submodule implementing the shader package facilities
 mod pak;
-pub use pak::Package; // re-export
+pub use pak::{Package, ProgramInstance, PACKAGE_FORMAT}; // re-export
+
+/// Submodule implementing the persistent cache for runtime-compiled shader packages
+#[cfg(feature="compilation")]
+pub mod cache;
 
 /// Submodule providing the abstractions for *Slang* [`Program`](slang::Program)s.
 #[cfg(feature="slang_runtime")]
 pub mod slang;
 
+/// Submodule providing a pure-Rust compilation context for *WGSL* and *GLSL* built on *Naga*.
+#[cfg(feature="naga_runtime")]
+pub mod naga;
+
 /// Unit tests
 #[cfg(test)]
 mod tests;
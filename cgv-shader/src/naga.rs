@@ -0,0 +1,640 @@
+//////
+//
+// Imports
+//
+
+// Standard library
+use std::{collections::BTreeMap, error::Error, path::{Path, PathBuf}, sync::Arc};
+
+// Anyhow library
+use anyhow::anyhow;
+
+// Naga library
+use ::naga;
+
+// CGV-rs core libraries
+use cgv_util as util;
+
+// Local imports
+use crate::{*, compile::{Context as _, ComponentRef, Diagnostic, Diagnostics, Severity}};
+
+
+
+//////
+//
+// Enums
+//
+
+/// The source languages a [`naga::Context`](Context) can compile, determined from the (virtual) file path of a
+/// module via [`SourceLanguage::fromPath`].
+#[derive(Debug,Clone,Copy,PartialEq,Eq)]
+pub enum SourceLanguage {
+	/// *WebGPU Shading Language*. As *WGSL* has no preprocessor, [defines](compile::ContextBuilder::addDefines) can't
+	/// be applied to modules in this language.
+	WGSL,
+
+	/// *GL Shading Language* for the given shader stage. Every *GLSL* module contains exactly one entry point called
+	/// `main`.
+	GLSL(naga::ShaderStage)
+}
+impl SourceLanguage
+{
+	/// Determine the source language from the extension of the given path. The extensions `vert`, `frag` and `comp`
+	/// indicate *GLSL* shaders of the respective stage, anything else (including no extension) is considered *WGSL*.
+	pub fn fromPath (path: &Path) -> Self {
+		match path.extension().and_then(|ext| ext.to_str()) {
+			Some("vert") => Self::GLSL(naga::ShaderStage::Vertex),
+			Some("frag") => Self::GLSL(naga::ShaderStage::Fragment),
+			Some("comp") => Self::GLSL(naga::ShaderStage::Compute),
+			_ => Self::WGSL
+		}
+	}
+}
+
+
+
+//////
+//
+// Structs
+//
+
+/// The parsed and validated source code of a module, shared between the [`Module`] and all [`Composite`]s that
+/// include it.
+struct Source {
+	moduleId: u32,
+	path: PathBuf,
+	code: String,
+	language: SourceLanguage,
+	module: naga::Module
+}
+
+
+///
+pub struct EntryPoint {
+	id: u32,
+	name: String,
+	stage: naga::ShaderStage
+}
+impl EntryPoint {
+	/// The shader stage of the entry point.
+	#[inline(always)]
+	pub fn stage (&self) -> naga::ShaderStage {
+		self.stage
+	}
+}
+impl compile::Component for EntryPoint {
+	type Id = u32;
+	fn id (&self) -> Self::Id {
+		self.id
+	}
+}
+impl compile::EntryPoint for EntryPoint {
+	fn name (&self) -> &str {
+		&self.name
+	}
+}
+
+///
+pub struct Module {
+	source: Arc<Source>,
+	filePath: Option<PathBuf>,
+	entryPoints: Vec<EntryPoint>
+}
+impl Module {
+	/// The source language the module was compiled from.
+	#[inline(always)]
+	pub fn language (&self) -> SourceLanguage {
+		self.source.language
+	}
+}
+impl compile::Component for Module {
+	type Id = u32;
+	fn id (&self) -> Self::Id {
+		self.source.moduleId
+	}
+}
+impl compile::Module<EntryPoint> for Module
+{
+	fn virtualFilepath (&self) -> &Path {
+		&self.source.path
+	}
+
+	fn entryPoint (&self, name: &str) -> Option<&EntryPoint> {
+		self.entryPoints.iter().find(|ep| ep.name == name)
+	}
+
+	fn entryPoints (&self) -> &[EntryPoint] {
+		&self.entryPoints
+	}
+
+	fn fileDependencies (&self) -> Vec<PathBuf> {
+		// Naga has no notion of imports, so the only dependency is the file itself
+		self.filePath.iter().cloned().collect()
+	}
+}
+
+///
+pub struct Composite {
+	id: u32,
+	sources: Vec<Arc<Source>>,
+	entryPoints: Vec<(String, naga::ShaderStage)>
+}
+impl compile::Component for Composite {
+	type Id = u32;
+	fn id (&self) -> Self::Id {
+		self.id
+	}
+}
+impl compile::Composite for Composite {}
+
+///
+pub struct LinkedComposite<'this> {
+	module: naga::Module,
+	info: naga::valid::ModuleInfo,
+	entryPoints: Vec<(String, naga::ShaderStage)>,
+	context: &'this Context
+}
+impl LinkedComposite<'_>
+{
+	/// Generate code for the given target from the given module.
+	fn translate (
+		&self, target: compile::Target, module: &naga::Module, info: &naga::valid::ModuleInfo,
+		entryPoint: Option<&(String, naga::ShaderStage)>
+	) -> Result<compile::ProgramCode, compile::TranslateError>
+	{
+		let backendError = |err: &dyn std::fmt::Display| compile::TranslateError::Backend(
+			anyhow!("translation to {target} failed: {err}")
+		);
+		match target
+		{
+			compile::Target::SPIRV => {
+				let pipelineOptions = entryPoint.map(|(name, stage)| naga::back::spv::PipelineOptions {
+					shader_stage: *stage, entry_point: name.clone()
+				});
+				let words = naga::back::spv::write_vec(
+					module, info, &naga::back::spv::Options::default(), pipelineOptions.as_ref()
+				).map_err(|err| backendError(&err))?;
+				Ok(compile::ProgramCode::Binary(bytemuck::cast_slice(&words).to_owned()))
+			},
+			compile::Target::WGSL => Ok(compile::ProgramCode::Text(
+				naga::back::wgsl::write_string(module, info, naga::back::wgsl::WriterFlags::empty()).map_err(
+					|err| backendError(&err)
+				)?
+			)),
+			_ => Err(compile::TranslateError::InvalidTarget(target))
+		}
+	}
+}
+impl compile::LinkedComposite for LinkedComposite<'_>
+{
+	#[inline(always)]
+	fn numEntryPoints (&self) -> usize {
+		self.entryPoints.len()
+	}
+
+	#[inline]
+	fn entryPointIdx (&self, name: &str) -> usize {
+		self.entryPoints.iter().position(|(epName, _)| epName == name).expect(
+			&format!("entry point '{name}' not found")
+		)
+	}
+
+	fn entryPointName (&self, entryPointIdx: usize) -> &str {
+		&self.entryPoints[entryPointIdx].0
+	}
+
+	fn allEntryPointsCode (&self, target: compile::Target) -> Result<compile::ProgramCode, compile::TranslateError>
+	{
+		if !self.context.supportsTarget(target) {
+			return Err(compile::TranslateError::InvalidTarget(target));
+		}
+		self.translate(target, &self.module, &self.info, None)
+	}
+
+	fn entryPointCode (&self, target: compile::Target, entryPointIdx: usize)
+		-> Option<Result<compile::ProgramCode, compile::TranslateError>>
+	{
+		// Check for entry points index out-of-bounds
+		let entryPoint = self.entryPoints.get(entryPointIdx)?;
+		if !self.context.supportsTarget(target) {
+			return Some(Err(compile::TranslateError::InvalidTarget(target)));
+		}
+
+		// The SPIR-V backend can select the entry point itself, for WGSL we have to strip all others from the module
+		if target.isSPIRV() {
+			return Some(self.translate(target, &self.module, &self.info, Some(entryPoint)));
+		}
+		let result = restrictEntryPoints(&self.module, &self.entryPoints[entryPointIdx..=entryPointIdx]).map_err(
+			|err| compile::TranslateError::Backend(anyhow!(
+				"specializing to entry point {entryPointIdx}['{}'] failed: {err}", entryPoint.0
+			))
+		);
+		Some(result.and_then(|(module, info)| self.translate(target, &module, &info, Some(entryPoint))))
+	}
+}
+
+
+///
+pub struct ContextBuilder {
+	targets: util::ds::HashUniqueVec<compile::Target>,
+	searchPath: util::ds::HashUniqueVec<PathBuf>,
+	defines: BTreeMap<String, String>
+}
+impl Default for ContextBuilder {
+	fn default () -> Self { Self {
+		targets: vec![compile::mostSuitableTarget()].into(),
+		searchPath: Default::default(),
+		defines: Default::default()
+	}}
+}
+impl compile::ContextBuilder for ContextBuilder
+{
+	type Context = Context;
+
+	#[inline(always)]
+	fn withPlatformDefaults (platform: &util::meta::SupportedPlatform) -> Self { Self {
+		targets: vec![compile::mostSuitableTargetForPlatform(platform)].into(),
+		..Default::default()
+	}}
+
+	#[inline(always)]
+	fn withTargets (targets: impl AsRef<[compile::Target]>) -> Self { Self {
+		targets: targets.as_ref().into(),
+		..Default::default()
+	}}
+
+	#[inline(always)]
+	fn addTargets (mut self, targets: &[compile::Target]) -> Self {
+		self.targets.extend(targets.iter().copied());
+		self
+	}
+
+	#[inline(always)]
+	fn addDefines (mut self, defines: &[(&str, &str)]) -> Self {
+		self.defines.extend(defines.iter().map(|&(name, value)| (name.to_owned(), value.to_owned())));
+		self
+	}
+
+	fn build (self) -> Result<Self::Context, compile::CreateContextError>
+	{
+		// Naga only has backends for a subset of the targets
+		for target in self.targets.iter() {
+			if !matches!(target, compile::Target::SPIRV | compile::Target::WGSL) {
+				return Err(compile::CreateContextError::UnsupportedTarget(*target));
+			}
+		}
+
+		// Done!
+		Ok(Context { targets: self.targets, searchPath: self.searchPath, defines: self.defines })
+	}
+}
+impl compile::BuildsContextWithFilesystemAccess for ContextBuilder {
+	#[inline(always)]
+	fn withSearchPaths (paths: &[impl AsRef<Path>]) -> Self { Self {
+		searchPath: paths.iter().map(|p| p.as_ref().to_owned()).collect(),
+		..Default::default()
+	}}
+
+	#[inline(always)]
+	fn addSearchPaths (mut self, paths: &[impl AsRef<Path>]) -> Self {
+		self.searchPath.extend(paths.iter().map(|p| p.as_ref().to_owned()));
+		self
+	}
+}
+
+
+/// A pure-Rust [compilation context](compile::Context) built on *Naga*, the shader translator used internally by
+/// *WGPU*. It accepts *WGSL* as well as *GLSL* source code (see [`SourceLanguage`]) and translates to *WGSL* and
+/// *SPIR-V*, which makes it a compile path for builds that can't or don't want to depend on *Slang*.
+///
+/// Since neither language has a module system, linking a [`Composite`] of several modules concatenates their source
+/// code, which is only supported for *WGSL*. Each module still has to be valid on its own. Composites that don't
+/// select any entry points explicitly keep all entry points of their modules when linked.
+pub struct Context {
+	targets: util::ds::HashUniqueVec<compile::Target>,
+	searchPath: util::ds::HashUniqueVec<PathBuf>,
+	defines: BTreeMap<String, String>
+}
+impl Context
+{
+	/// Parse and validate the given source code, reporting problems as diagnostics referring to the given path.
+	fn parse (&self, path: &Path, sourceCode: &str, language: SourceLanguage)
+		-> Result<naga::Module, compile::LoadModuleError>
+	{
+		// Collect the defines from both the context and any variant prelude
+		let (prelude, code) = compile::variant::splitDefines(sourceCode);
+		let defines = self.defines().chain(prelude).map(
+			|(name, value)| (name.to_owned(), value.to_owned())
+		).collect::<naga::FastHashMap<_, _>>();
+
+		// Parse
+		let pathStr = path.to_string_lossy();
+		let module = match language
+		{
+			SourceLanguage::WGSL => {
+				if !defines.is_empty() {
+					return Err(compile::LoadModuleError::CompilationError(anyhow!(
+						"cannot apply preprocessor defines to '{pathStr}': WGSL has no preprocessor"
+					)));
+				}
+				naga::front::wgsl::parse_str(code).map_err(|err| {
+					let mut records = vec![record(path, code, err.labels().next().map(|l| l.0), err.message())];
+					records.extend(err.labels().skip(1).filter(|l| !l.1.is_empty()).map(
+						|(span, label)| Diagnostic { severity: Severity::Note, ..record(path, code, Some(span), label) }
+					));
+					compilationError(records, err.emit_to_string_with_path(code, &*pathStr))
+				})?
+			},
+			SourceLanguage::GLSL(stage) => naga::front::glsl::Frontend::default().parse(
+				&naga::front::glsl::Options { stage, defines }, code
+			).map_err(|err| compilationError(
+				err.errors.iter().map(|e| record(path, code, Some(e.meta), &e.kind.to_string())).collect(),
+				err.emit_to_string_with_path(code, &pathStr)
+			))?
+		};
+
+		// Validate
+		validate(&module).map_err(|err| compilationError(
+			validationRecords(&err, |span| {
+				let location = span.location(code);
+				(path.to_owned(), location.line_number, location)
+			}),
+			err.emit_to_string_with_path(code, &pathStr)
+		))?;
+		Ok(module)
+	}
+
+	/// Locate the given source file, either as-is or relative to one of the search paths.
+	fn resolve (&self, sourceFile: &Path) -> Option<PathBuf> {
+		if sourceFile.is_file() {
+			return Some(sourceFile.to_owned());
+		}
+		self.searchPath.iter().map(|dir| dir.join(sourceFile)).find(|path| path.is_file())
+	}
+
+	/// Common module creation logic.
+	fn compileModule (&self, path: &Path, sourceCode: &str, filePath: Option<PathBuf>)
+		-> Result<Module, compile::LoadModuleError>
+	{
+		// Make sure we get a valid module path
+		if path.file_name().is_none() {
+			return Err(compile::LoadModuleError::InvalidModulePaths(path.to_owned()));
+		}
+
+		// Compile
+		let language = SourceLanguage::fromPath(path);
+		let module = self.parse(path, sourceCode, language)?;
+
+		// Enumerate and save entry points
+		let entryPoints = module.entry_points.iter().map(|ep| EntryPoint {
+			id: util::unique::uint32(), name: ep.name.clone(), stage: ep.stage
+		}).collect();
+
+		// Done!
+		let code = compile::variant::splitDefines(sourceCode).1.to_owned();
+		Ok(Module {
+			source: Arc::new(Source {
+				moduleId: util::unique::uint32(), path: path.to_owned(), code, language, module
+			}),
+			filePath, entryPoints
+		})
+	}
+}
+impl compile::Context for Context
+{
+	type ModuleType<'module> = Module;
+	type EntryPointType<'ep> = EntryPoint;
+	type CompositeType<'cp> = Composite;
+	type LinkedCompositeType<'lct> = LinkedComposite<'lct>;
+	type Builder = ContextBuilder;
+
+	fn supportsTarget (&self, target: compile::Target) -> bool {
+		self.targets.contains(&target)
+	}
+
+	fn defines (&self) -> impl Iterator<Item=(&str, &str)> {
+		self.defines.iter().map(|(name, value)| (name.as_str(), value.as_str()))
+	}
+
+	#[inline]
+	fn compileFromSource<'ctx> (&self, sourceCode: &str) -> Result<Module, compile::LoadModuleError>
+		where Self: 'ctx
+	{
+		let mut targetPath = uniqueAnonymousPath();
+		targetPath.set_extension("wgsl");
+		self.compileFromNamedSource(&targetPath, sourceCode)
+	}
+
+	#[inline(always)]
+	fn compileFromNamedSource<'ctx> (&self, virtualFilepath: impl AsRef<Path>, sourceCode: &str)
+		-> Result<Module, compile::LoadModuleError>
+		where Self: 'ctx
+	{
+		self.compileModule(virtualFilepath.as_ref(), sourceCode, None)
+	}
+
+	fn createComposite<'ctx> (&self, components: &[ComponentRef<'_, '_, Self>])
+		-> Result<Composite, compile::CreateCompositeError>
+		where Self: 'ctx
+	{
+		// Flatten the component list, including every module only once
+		let mut sources: Vec<Arc<Source>> = Vec::new();
+		let mut entryPoints = Vec::new();
+		let mut addSource = |source: &Arc<Source>| if !sources.iter().any(|s| s.moduleId == source.moduleId) {
+			sources.push(source.clone());
+		};
+		for component in components {
+			match component {
+				ComponentRef::Module(module) => addSource(&module.source),
+				ComponentRef::EntryPoint(ep) => entryPoints.push((ep.name.clone(), ep.stage)),
+				ComponentRef::Composite(composite) => {
+					composite.sources.iter().for_each(&mut addSource);
+					entryPoints.extend(composite.entryPoints.iter().cloned());
+				}
+			}
+		}
+		let mut unique = Vec::with_capacity(entryPoints.len());
+		for ep in entryPoints {
+			if !unique.contains(&ep) {
+				unique.push(ep);
+			}
+		}
+
+		// Done!
+		Ok(Composite { id: util::unique::uint32(), sources, entryPoints: unique })
+	}
+
+	fn linkComposite<'ctx> (&'ctx self, composite: &Composite) -> Result<LinkedComposite<'ctx>, compile::LinkError>
+		where Self: 'ctx
+	{
+		// Combine the modules
+		let module = match composite.sources.as_slice()
+		{
+			[] => return Err(compile::LinkError::Backend(anyhow!("composite does not contain any modules"))),
+			[source] => source.module.clone(),
+			sources => {
+				if let Some(glsl) = sources.iter().find(|s| s.language != SourceLanguage::WGSL) {
+					return Err(compile::LinkError::Backend(anyhow!(
+						"'{}': GLSL modules can only be linked on their own", glsl.path.display()
+					)));
+				}
+				linkSources(sources)?
+			}
+		};
+
+		// Select the entry points
+		let entryPoints = if composite.entryPoints.is_empty() {
+			module.entry_points.iter().map(|ep| (ep.name.clone(), ep.stage)).collect()
+		} else {
+			composite.entryPoints.clone()
+		};
+		let (module, info) = restrictEntryPoints(&module, &entryPoints).map_err(
+			|err| compile::LinkError::Backend(anyhow!("link failure: {err}"))
+		)?;
+		for (name, _) in &entryPoints {
+			if !module.entry_points.iter().any(|ep| &ep.name == name) {
+				return Err(compile::LinkError::Backend(anyhow!("entry point '{name}' not found in composite")));
+			}
+		}
+
+		// Done!
+		Ok(LinkedComposite { module, info, entryPoints, context: self })
+	}
+}
+impl compile::HasFileSystemAccess for Context
+{
+	fn compile<'ctx> (&self, sourceFile: impl AsRef<Path>) -> Result<Module, compile::LoadModuleError>
+		where Self: 'ctx
+	{
+		let sourceFile = sourceFile.as_ref();
+		let filePath = self.resolve(sourceFile).ok_or_else(|| compile::LoadModuleError::CompilationError(
+			anyhow!("could not find source file '{}'", sourceFile.display())
+		))?;
+		let sourceCode = std::fs::read_to_string(&filePath).map_err(
+			|err| compile::LoadModuleError::CompilationError(err.into())
+		)?;
+		self.compileModule(sourceFile, &sourceCode, Some(filePath))
+	}
+}
+
+
+
+//////
+//
+// Functions
+//
+
+/// Validate the given module with all validation rules and capabilities enabled – the *WGPU* device the code will end
+/// up on validates it again against what it actually supports.
+fn validate (module: &naga::Module)
+	-> Result<naga::valid::ModuleInfo, naga::WithSpan<naga::valid::ValidationError>>
+{
+	naga::valid::Validator::new(naga::valid::ValidationFlags::all(), naga::valid::Capabilities::all()).validate(module)
+}
+
+/// Strip all entry points except the given ones from a copy of the given module and re-validate it.
+fn restrictEntryPoints (module: &naga::Module, entryPoints: &[(String, naga::ShaderStage)])
+	-> Result<(naga::Module, naga::valid::ModuleInfo), naga::WithSpan<naga::valid::ValidationError>>
+{
+	let mut module = module.clone();
+	let numEntryPoints = module.entry_points.len();
+	module.entry_points.retain(|ep| entryPoints.iter().any(|(name, stage)| &ep.name == name && ep.stage == *stage));
+	if module.entry_points.len() < numEntryPoints {
+		naga::compact::compact(&mut module, naga::compact::KeepUnused::No);
+	}
+	let info = validate(&module)?;
+	Ok((module, info))
+}
+
+/// Concatenate the source code of the given *WGSL* modules and compile the result, reporting problems as diagnostics
+/// referring to the original modules.
+fn linkSources (sources: &[Arc<Source>]) -> Result<naga::Module, compile::LinkError>
+{
+	// Concatenate, remembering the line each module starts at
+	let mut code = String::new();
+	let mut firstLines = Vec::with_capacity(sources.len());
+	for source in sources {
+		firstLines.push(code.lines().count() as u32 + 1);
+		code.push_str(&source.code);
+		if !code.ends_with('\n') {
+			code.push('\n');
+		}
+	}
+	let locate = |span: naga::Span| {
+		let location = span.location(&code);
+		let idx = firstLines.partition_point(|&first| first <= location.line_number) - 1;
+		(sources[idx].path.clone(), location.line_number - firstLines[idx] + 1, location)
+	};
+
+	// Compile the combined code
+	let module = naga::front::wgsl::parse_str(&code).map_err(|err| {
+		let records = err.labels().take(1).map(|(span, _)| {
+			let (file, line, location) = locate(span);
+			Diagnostic {
+				file: Some(file), line: Some(line), column: Some(location.line_position), severity: Severity::Error,
+				code: None, message: err.message().to_owned()
+			}
+		}).collect::<Vec<_>>();
+		compile::LinkError::Backend(withDiagnostics("link failure", records))
+	})?;
+	validate(&module).map_err(
+		|err| compile::LinkError::Backend(withDiagnostics("link failure", validationRecords(&err, locate)))
+	)?;
+	Ok(module)
+}
+
+/// Create an error diagnostic for the given span of the given source code.
+fn record (path: &Path, sourceCode: &str, span: Option<naga::Span>, message: &str) -> Diagnostic
+{
+	let location = span.filter(naga::Span::is_defined).map(|span| span.location(sourceCode));
+	Diagnostic {
+		file: Some(path.to_owned()), line: location.map(|l| l.line_number), column: location.map(|l| l.line_position),
+		severity: Severity::Error, code: None, message: message.to_owned()
+	}
+}
+
+/// Create diagnostics for a validation error, an error record with the full error chain as its message followed by a
+/// note for every labelled span. Spans are turned into file, line and location via the given function.
+fn validationRecords (
+	err: &naga::WithSpan<naga::valid::ValidationError>,
+	mut locate: impl FnMut(naga::Span)->(PathBuf, u32, naga::SourceLocation)
+) -> Vec<Diagnostic>
+{
+	// Gather the full error chain, as validation errors tend to wrap the actual problem
+	let mut message = err.as_inner().to_string();
+	let mut cause = err.as_inner().source();
+	while let Some(err) = cause {
+		message += &format!(": {err}");
+		cause = err.source();
+	}
+
+	// Create the records, locating the error itself at the narrowest span as the outer ones just provide context
+	let mut spans = err.spans().filter(|(span, _)| span.is_defined()).map(|(span, label)| {
+		let (file, line, location) = locate(*span);
+		(location.length, Diagnostic {
+			file: Some(file), line: Some(line), column: Some(location.line_position), severity: Severity::Note,
+			code: None, message: label.clone()
+		})
+	}).collect::<Vec<_>>();
+	let mut records = vec![match spans.iter().min_by_key(|(length, _)| *length) {
+		Some((_, narrowest)) => Diagnostic { severity: Severity::Error, message, ..narrowest.clone() },
+		None => Diagnostic { file: None, line: None, column: None, severity: Severity::Error, code: None, message }
+	}];
+	spans.retain(|(_, d)| !d.message.is_empty());
+	records.extend(spans.into_iter().map(|(_, d)| d));
+	records
+}
+
+/// Turn diagnostic records into a [`compile::LoadModuleError`].
+fn compilationError (records: Vec<Diagnostic>, raw: String) -> compile::LoadModuleError {
+	compile::LoadModuleError::CompilationError(Diagnostics::fromRecords(records, raw).into())
+}
+
+/// Turn diagnostic records into an [`anyhow::Error`] carrying them as its root cause, prefixing its message with the
+/// given `context`.
+fn withDiagnostics (context: &str, records: Vec<Diagnostic>) -> anyhow::Error {
+	let raw = records.iter().map(Diagnostic::to_string).collect::<Vec<_>>().join("\n");
+	let diagnostics = Diagnostics::fromRecords(records, raw);
+	let message = format!("{context}: {diagnostics}");
+	anyhow::Error::new(diagnostics).context(message)
+}
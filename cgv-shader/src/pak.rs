@@ -27,6 +27,26 @@ use crate::*;
 
 
 
+//////
+//
+// Constants
+//
+
+/// The container [format](container::Format) of serialized [`Package`]s.
+pub const PACKAGE_FORMAT: container::Format = container::Format {
+	name: "shader package", magic: *b"CGV-SPK\x1A", version: 3,
+	migrations: &[
+		// Legacy header-less packages encode the package exactly like version 1 does
+		container::Migration::identity(0),
+		// Version 1 packages did not have variants yet
+		container::Migration { from: 1, migrate: PackageV1::migrate },
+		// Version 2 packages could only hold instances for *WGPU* source types
+		container::Migration { from: 2, migrate: PackageV2::migrate }
+	]
+};
+
+
+
 //////
 //
 // Errors
@@ -42,7 +62,11 @@ pub enum CreateShaderModuleError
 
 	/// The package from which a module was requested does not contain an instance in the requested source type. Holds
 	/// the requested `SourceType` that was unavailable.
-	InvalidSourceType(WgpuSourceType)
+	InvalidSourceType(WgpuSourceType),
+
+	/// The package from which a module was requested does not contain the requested [variant](compile::Variant). Holds
+	/// the key of the unavailable variant.
+	InvalidVariant(String)
 }
 #[cfg(feature="wgpu_runtime")]
 impl Display for CreateShaderModuleError
@@ -50,7 +74,8 @@ impl Display for CreateShaderModuleError
 	fn fmt (&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 		let desc = match self {
 			Self::InvalidEntryPoint(ep) => format!("invalid entry point: `{ep}`"),
-			Self::InvalidSourceType(st) => format!("invalid source type: `{st}`")
+			Self::InvalidSourceType(st) => format!("invalid source type: `{st}`"),
+			Self::InvalidVariant(key) => format!("invalid variant: `{key}`")
 		};
 		write!(formatter, "CreateShaderModuleError[{desc}]")
 	}
@@ -64,9 +89,9 @@ impl std::error::Error for CreateShaderModuleError {}
 #[derive(Debug)]
 pub enum ProgramInstanceCreationError
 {
-	/// A [`compile::Context`] was supposed to compile a program instance for the given [`WgpuSourceType`] but did not
-	/// support the corresponding [compilation target](compile::Target).
-	IncompatibleContext(WgpuSourceType),
+	/// A [`compile::Context`] was supposed to compile a program instance for the given
+	/// [compilation target](compile::Target) but did not support it.
+	IncompatibleContext(Target),
 
 	#[doc=include_str!("_doc/_InvalidEntryPoint_withString.md")]
 	InvalidEntryPoint(String),
@@ -80,7 +105,7 @@ impl Display for ProgramInstanceCreationError
 	fn fmt (&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
 	{
 		let desc = match self {
-			Self::IncompatibleContext(srcType) => format!("context cannot compile to {srcType}"),
+			Self::IncompatibleContext(target) => format!("context cannot compile to {target}"),
 			Self::InvalidEntryPoint(ep) => format!("invalid entry point: `{ep}`"),
 			Self::Backend(err) => format!("backend error: {err}")
 		};
@@ -88,17 +113,21 @@ impl Display for ProgramInstanceCreationError
 	}
 }
 #[cfg(feature="compilation")]
-impl std::error::Error for ProgramInstanceCreationError {}
+impl std::error::Error for ProgramInstanceCreationError {
+	fn source (&self) -> Option<&(dyn std::error::Error+'static)> {
+		match self {
+			Self::Backend(err) => Some(err.as_ref()),
+			_ => None
+		}
+	}
+}
 
 
 ///
 #[cfg(feature="compilation")]
 #[derive(Debug)]
 pub enum PackageFromProgramError {
-	/// The program was built for a compilation target does not match any [`WgpuSourceType`].
-	IncompatibleProgram(compile::Target),
-
-	/// Some problem instantiating the shader program for a source type occurred.
+	/// Some problem instantiating the shader program for its target occurred.
 	InstanceCreation(ProgramInstanceCreationError)
 }
 #[cfg(feature="compilation")]
@@ -107,10 +136,6 @@ impl Display for PackageFromProgramError
 	fn fmt (&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
 	{
 		match self {
-			Self::IncompatibleProgram(target) => write!(
-				formatter, "PackageFromProgramError[program uses incompatible code type {target}]"
-			),
-
 			Self::InstanceCreation(err) => write!(
 				formatter, "PackageFromProgramError[`{err}`]"
 			)
@@ -118,7 +143,65 @@ impl Display for PackageFromProgramError
 	}
 }
 #[cfg(feature="compilation")]
-impl std::error::Error for PackageFromProgramError {}
+impl std::error::Error for PackageFromProgramError {
+	fn source (&self) -> Option<&(dyn std::error::Error+'static)> {
+		match self {
+			Self::InstanceCreation(err) => Some(err)
+		}
+	}
+}
+
+
+
+//////
+//
+// Enums
+//
+
+/// The encodable counterpart of [`Target`] that packages key their instances by.
+#[derive(Debug,Clone,Copy,PartialEq,Eq,PartialOrd,Ord,bitcode::Encode,bitcode::Decode)]
+enum StoredTarget {
+	SPIRV,
+	WGSL,
+	DXIL,
+	GLSL,
+	HLSL,
+	CudaCpp,
+	Metal,
+	Custom { uuid: u128, binary: bool }
+}
+impl From<Target> for StoredTarget {
+	fn from (target: Target) -> Self {
+		match target {
+			Target::SPIRV => Self::SPIRV,
+			Target::WGSL => Self::WGSL,
+			Target::DXIL => Self::DXIL,
+			Target::GLSL => Self::GLSL,
+			Target::HLSL => Self::HLSL,
+			Target::CudaCpp => Self::CudaCpp,
+			Target::Metal => Self::Metal,
+			Target::Custom(uuid, format) => Self::Custom {
+				uuid: uuid.as_u128(), binary: matches!(format, TargetFormat::Binary)
+			}
+		}
+	}
+}
+impl From<StoredTarget> for Target {
+	fn from (target: StoredTarget) -> Self {
+		match target {
+			StoredTarget::SPIRV => Self::SPIRV,
+			StoredTarget::WGSL => Self::WGSL,
+			StoredTarget::DXIL => Self::DXIL,
+			StoredTarget::GLSL => Self::GLSL,
+			StoredTarget::HLSL => Self::HLSL,
+			StoredTarget::CudaCpp => Self::CudaCpp,
+			StoredTarget::Metal => Self::Metal,
+			StoredTarget::Custom { uuid, binary } => Self::Custom(
+				util::uuid::Uuid::from_u128(uuid), if binary { TargetFormat::Binary } else { TargetFormat::Text }
+			)
+		}
+	}
+}
 
 
 
@@ -127,8 +210,8 @@ impl std::error::Error for PackageFromProgramError {}
 // Classes
 //
 
-/// Represents a single *WGPU* compatible shader program for one or multiple shader stages, potentially with different
-/// specializations for each of its various entry points.
+/// Represents a single shader program in the representation of one [compilation target](Target) for one or multiple
+/// shader stages, potentially with different specializations for each of its various entry points.
 #[derive(bitcode::Encode,bitcode::Decode)]
 pub struct ProgramInstance {
 	entryPoints: BTreeMap<Option<String>, Vec<u8>>,
@@ -153,6 +236,12 @@ impl ProgramInstance
 		self.entryPoints.insert(name.map(|name| name.to_owned()), code);
 	}
 
+	/// Iterate over the names of all entry points the instance contains specialized code for, with `None` referring to
+	/// the generic code containing all code paths for all entry points.
+	pub fn entryPoints (&self) -> impl Iterator<Item=Option<&str>> {
+		self.entryPoints.keys().map(|name| name.as_deref())
+	}
+
 	///
 	pub fn code (&self, entryPointName: Option<&str>) -> Option<&[u8]> {
 		self.entryPoints.get(&entryPointName.map(|name| name.to_owned())).map(
@@ -162,17 +251,100 @@ impl ProgramInstance
 }
 
 /// Represents a package of one or more ready-to-use instances of a shader program compiled to different representations
-/// (e.g. *SPIR-V* or *WGSL*).
+/// (e.g. *SPIR-V* or *WGSL*). Instances can be held for any [compilation target](Target) – including targets *WGPU*
+/// can't consume like *HLSL*, *Metal* or [custom](Target::Custom) ones – while only those matching a
+/// [`WgpuSourceType`] can be turned into shader modules. A package can hold several [variants](compile::Variant) of the
+/// program, each with its own set of instances, stored under the variant [key](compile::Variant::key). All methods
+/// that don't explicitly take a variant key refer to the [default variant](Self::DEFAULT_VARIANT).
+///
+/// Everywhere a target is expected, a [`WgpuSourceType`] can be passed as well.
 #[derive(bitcode::Encode,bitcode::Decode)]
 pub struct Package {
 	name: String,
-	instances: BTreeMap<WgpuSourceType, ProgramInstance>
+	variants: BTreeMap<String, BTreeMap<StoredTarget, ProgramInstance>>
 }
 impl Package
 {
+	/// The key of the default variant, i.e. the program compiled without any variant-specific defines or
+	/// specializations.
+	pub const DEFAULT_VARIANT: &'static str = "";
+
+
 	////
 	// Internal helpers
 
+	/// Translate the given linked composite into program instances for all the given targets.
+	#[cfg(feature="compilation")]
+	fn linkedCompositeIntoInstances<'outer, CompileContext> (
+		targets: &[impl Into<Target>+Copy], context: &'outer CompileContext,
+		linkedComposite: &CompileContext::LinkedCompositeType<'outer>, entryPoints: Option<&BTreeSet<Option<&str>>>
+	) -> Result<BTreeMap<StoredTarget, ProgramInstance>, ProgramInstanceCreationError>
+	where
+		CompileContext: compile::Context
+	{
+		let mut instances = BTreeMap::new();
+		for &target in targets
+		{
+			// Check compilation target
+			let target = target.into();
+			if !context.supportsTarget(target) {
+				return Err(ProgramInstanceCreationError::IncompatibleContext(target));
+			}
+
+			// Translate code
+			let program = Program::fromLinkedComposite(context, target, linkedComposite).map_err(
+				ProgramInstanceCreationError::Backend
+			)?;
+
+			// Instantiate program instance
+			instances.insert(target.into(), Self::programIntoInstance(program, entryPoints)?);
+		}
+		Ok(instances)
+	}
+
+	/// Compile, link and translate the given program source code under the given variant.
+	#[cfg(feature="compilation")]
+	fn variantIntoInstances<CompileContext> (
+		targets: &[impl Into<Target>+Copy], context: &CompileContext, programPath: &Path, sourceCode: &str,
+		variant: &compile::Variant, entryPoints: Option<&BTreeSet<Option<&str>>>
+	) -> Result<BTreeMap<StoredTarget, ProgramInstance>, ProgramInstanceCreationError>
+	where
+		CompileContext: compile::Context
+	{
+		// Compile the main module with the variant defines applied
+		use compile::Module;
+		let modulePath = variant.modulePath(programPath);
+		let module = context.compileFromNamedSource(
+			&modulePath, &variant.preprocessedSource(&modulePath, sourceCode)
+		).map_err(
+			|err| ProgramInstanceCreationError::Backend(err.into())
+		)?;
+
+		// Link, binding the specialization points if the variant has specialization arguments
+		let linked = if let Some(specializationSource) = variant.specializationModuleSource()
+		{
+			let specializationModule = context.compileFromNamedSource(
+				variant.specializationModulePath(programPath), &specializationSource
+			).map_err(
+				|err| ProgramInstanceCreationError::Backend(err.into())
+			)?;
+			let mut components = vec![
+				compile::ComponentRef::Module(&module), compile::ComponentRef::Module(&specializationModule)
+			];
+			components.extend(module.entryPoints().iter().map(compile::ComponentRef::EntryPoint));
+			let composite = context.createComposite(&components).map_err(
+				|err| ProgramInstanceCreationError::Backend(err.into())
+			)?;
+			context.linkComposite(&composite).map_err(|err| ProgramInstanceCreationError::Backend(err.into()))?
+		}
+		else {
+			compile::buildModule(context, &module).map_err(|err| ProgramInstanceCreationError::Backend(err.into()))?
+		};
+
+		// Translate
+		Self::linkedCompositeIntoInstances(targets, context, &linked, entryPoints)
+	}
+
 	#[cfg(feature="compilation")]
 	fn programIntoInstance (prog: Program, entryPoints: Option<&BTreeSet<Option<&str>>>)
 		-> Result<ProgramInstance, ProgramInstanceCreationError>
@@ -209,27 +381,81 @@ impl Package
 	////
 	// Public API
 
-	/// Deserialize from the given bytes.
+	/// Deserialize from the given bytes, which must be a container of the [package format](PACKAGE_FORMAT).
 	pub fn deserialize (bytes: &[u8]) -> anyhow::Result<Self> {
-		Ok(bitcode::decode(bytes)?)
+		Ok(PACKAGE_FORMAT.decode(bytes, |payload| Ok(bitcode::decode(payload)?))?)
 	}
 
 	/// Deserialize from the file.
 	pub fn fromFile (filename: impl AsRef<Path>) -> anyhow::Result<Self> {
-		Ok(bitcode::decode(std::fs::read(filename)?.as_slice())?)
+		Self::deserialize(std::fs::read(filename)?.as_slice())
 	}
 
-	/// Construct with the given [`ProgramInstance`] as the sole instance of type `sourceType` in the package.
-	pub fn fromSingleInstance (sourceType: WgpuSourceType, instance: ProgramInstance, name: Option<String>) -> Self {
-		Self {
-			name: name.unwrap_or_else(|| uniqueAnonymousName()), instances: BTreeMap::from([(sourceType, instance)])
-		}
+	/// Reference the name of the package.
+	#[inline(always)]
+	pub fn name (&self) -> &str {
+		&self.name
+	}
+
+	/// Compute a digest of the actual contents of the package, i.e. its name and all its variants and instances. Equal
+	/// packages always have the same digest, which makes it suitable for keying caches of objects created from them.
+	pub fn contentDigest (&self) -> util::uuid::Uuid {
+		util::uuid::Uuid::new_v5(util::meta::uuidNamespace(), &bitcode::encode(self))
+	}
+
+	/// Reference the program instance of the given target, if the package contains one.
+	#[inline(always)]
+	pub fn instance (&self, target: impl Into<Target>) -> Option<&ProgramInstance> {
+		self.variantInstance(Self::DEFAULT_VARIANT, target)
 	}
 
+	/// Iterate over all program instances in the package along with their targets.
+	#[inline(always)]
+	pub fn instances (&self) -> impl Iterator<Item=(Target, &ProgramInstance)> {
+		self.variantInstances(Self::DEFAULT_VARIANT)
+	}
+
+	/// Iterate over the keys of all variants the package contains instances for.
+	#[inline(always)]
+	pub fn variants (&self) -> impl Iterator<Item=&str> {
+		self.variants.keys().map(|key| key.as_str())
+	}
+
+	/// Check whether the package contains instances for the variant with the given key.
+	#[inline(always)]
+	pub fn hasVariant (&self, variant: &str) -> bool {
+		self.variants.contains_key(variant)
+	}
+
+	/// Reference the program instance of the given target for the variant with the given key, if the package contains
+	/// one.
+	#[inline(always)]
+	pub fn variantInstance (&self, variant: &str, target: impl Into<Target>) -> Option<&ProgramInstance> {
+		self.variants.get(variant)?.get(&target.into().into())
+	}
+
+	/// Iterate over all program instances of the variant with the given key along with their targets.
+	pub fn variantInstances (&self, variant: &str) -> impl Iterator<Item=(Target, &ProgramInstance)> {
+		self.variants.get(variant).into_iter().flat_map(
+			|instances| instances.iter().map(|(&target, instance)| (target.into(), instance))
+		)
+	}
+
+	/// Construct with the given [`ProgramInstance`] as the sole instance for `target` in the package.
+	pub fn fromSingleInstance (target: impl Into<Target>, instance: ProgramInstance, name: Option<String>) -> Self {
+		Self::fromInstances(BTreeMap::from([(target.into().into(), instance)]), name)
+	}
+
+	/// Construct with the given instances as the default variant.
+	fn fromInstances (instances: BTreeMap<StoredTarget, ProgramInstance>, name: Option<String>) -> Self { Self {
+		name: name.unwrap_or_else(uniqueAnonymousName),
+		variants: BTreeMap::from([(Self::DEFAULT_VARIANT.to_owned(), instances)])
+	}}
+
 	///
 	#[cfg(feature="compilation")]
 	pub fn fromLinkedCompositeMultipleTypes<'outer, CompileContext> (
-		sourceTypes: &[WgpuSourceType], context: &'outer CompileContext,
+		targets: &[impl Into<Target>+Copy], context: &'outer CompileContext,
 		linkedComposite: &CompileContext::LinkedCompositeType<'outer>, name: Option<String>,
 		entryPoints: Option<BTreeSet<Option<&str>>>
 	) -> Result<Self, ProgramInstanceCreationError>
@@ -237,34 +463,17 @@ impl Package
 		CompileContext: compile::Context
 	{
 		// Instantiate for every source type
-		let mut instances = BTreeMap::new();
-		for &sourceType in sourceTypes
-		{
-			// Check compilation target
-			let target = sourceType.into();
-			if !context.supportsTarget(target) {
-				return Err(ProgramInstanceCreationError::IncompatibleContext(sourceType));
-			}
-
-			// Translate code
-			let program = Program::fromLinkedComposite(context, target, &linkedComposite).map_err(
-				|err| ProgramInstanceCreationError::Backend(err)
-			)?;
-
-			// Instantiate program instance
-			instances.insert(sourceType, Self::programIntoInstance(program, entryPoints.as_ref())?);
-		}
-
-		Ok(Self {
-			name: name.unwrap_or_else(|| uniqueAnonymousName()),
-			instances
-		})
+		let instances = Self::linkedCompositeIntoInstances(
+			targets, context, linkedComposite, entryPoints.as_ref()
+		)?;
+		Ok(Self::fromInstances(instances, name))
 	}
 
 	///
 	#[cfg(feature="compilation")]
 	pub fn fromModuleMultipleTypes<'outer, CompileContext> (
-		sourceTypes: &[WgpuSourceType], context: &'outer CompileContext, module: &CompileContext::ModuleType<'outer>,
+		targets: &[impl Into<Target>+Copy], context: &'outer CompileContext,
+		module: &CompileContext::ModuleType<'outer>,
 		entryPoints: Option<BTreeSet<Option<&str>>>
 	) -> Result<Self, ProgramInstanceCreationError>
 	where
@@ -278,7 +487,7 @@ impl Package
 
 		// Delegate
 		Self::fromLinkedCompositeMultipleTypes(
-			sourceTypes, context, &linkedProg,
+			targets, context, &linkedProg,
 			/* name: */Some(unsafe {
 				// SAFETY:
 				// If we're here, then the module could be built from the filename (or was assigned a virtual one), so
@@ -290,11 +499,11 @@ impl Package
 		)
 	}
 
-	/// Create the package from the given *Slang* shader source file, compiling it under several contexts to produce
-	/// different instances for the [source types](SourceType) each [`slang::Context`] is set up for.
+	/// Create the package from the given shader source file, compiling it to produce instances for each of the given
+	/// [targets](Target).
 	#[cfg(feature="compilation")]
 	pub fn fromSourceFileMultipleTypes<CompileContext> (
-		sourceTypes: &[WgpuSourceType], context: &CompileContext, filename: impl AsRef<Path>,
+		targets: &[impl Into<Target>+Copy], context: &CompileContext, filename: impl AsRef<Path>,
 		entryPoints: Option<BTreeSet<Option<&str>>>
 	) -> Result<Self, ProgramInstanceCreationError>
 	where
@@ -305,15 +514,15 @@ impl Package
 			|err| ProgramInstanceCreationError::Backend(err.into())
 		)?;
 
-		// Package an instance for every source type
-		Self::fromModuleMultipleTypes(sourceTypes, context, &module, entryPoints)
+		// Package an instance for every target
+		Self::fromModuleMultipleTypes(targets, context, &module, entryPoints)
 	}
 
-	/// Create the package from the given *Slang* shader source code, compiling it under several contexts to produce
-	/// different instances for the [source types](SourceType) each [`slang::Context`] is set up for.
+	/// Create the package from the given shader source code, compiling it to produce instances for each of the given
+	/// [targets](Target).
 	#[cfg(feature="compilation")]
 	pub fn fromSourceMultipleTypes<CompileContext> (
-		sourceTypes: &[WgpuSourceType], context: &CompileContext, programName: impl AsRef<Path>,
+		targets: &[impl Into<Target>+Copy], context: &CompileContext, programName: impl AsRef<Path>,
 		sourceCode: impl AsRef<str>, entryPoints: Option<BTreeSet<Option<&str>>>
 	) -> Result<Self, ProgramInstanceCreationError>
 	where
@@ -326,8 +535,50 @@ impl Package
 			|err| ProgramInstanceCreationError::Backend(err.into())
 		)?;
 
-		// Package an instance for every source type
-		Self::fromModuleMultipleTypes(sourceTypes, context, &module, entryPoints)
+		// Package an instance for every target
+		Self::fromModuleMultipleTypes(targets, context, &module, entryPoints)
+	}
+
+	/// Create the package from the given shader source code, compiling it once for every given
+	/// [variant](compile::Variant) to produce instances for all targets in each. The instances of each variant are
+	/// stored under the [variant key](compile::Variant::key).
+	#[cfg(feature="compilation")]
+	pub fn fromSourceVariantsMultipleTypes<CompileContext> (
+		targets: &[impl Into<Target>+Copy], context: &CompileContext, programName: impl AsRef<Path>,
+		sourceCode: impl AsRef<str>, variants: &[compile::Variant], entryPoints: Option<BTreeSet<Option<&str>>>
+	) -> Result<Self, ProgramInstanceCreationError>
+	where
+		CompileContext: compile::Context
+	{
+		let programPath = programName.as_ref();
+		let mut package = Self {
+			name: programPath.file_name().map_or_else(uniqueAnonymousName, |name| name.to_string_lossy().into()),
+			variants: BTreeMap::new()
+		};
+		for variant in variants {
+			let instances = Self::variantIntoInstances(
+				targets, context, programPath, sourceCode.as_ref(), variant, entryPoints.as_ref()
+			)?;
+			package.variants.insert(variant.key(), instances);
+		}
+		Ok(package)
+	}
+
+	/// Create the package from the given shader source file, compiling it once for every given
+	/// [variant](compile::Variant) to produce instances for all targets in each. The instances of each variant are
+	/// stored under the [variant key](compile::Variant::key).
+	#[cfg(feature="compilation")]
+	pub fn fromSourceFileVariantsMultipleTypes<CompileContext> (
+		targets: &[impl Into<Target>+Copy], context: &CompileContext, filename: impl AsRef<Path>,
+		variants: &[compile::Variant], entryPoints: Option<BTreeSet<Option<&str>>>
+	) -> Result<Self, ProgramInstanceCreationError>
+	where
+		CompileContext: compile::HasFileSystemAccess
+	{
+		let sourceCode = std::fs::read_to_string(&filename).map_err(
+			|err| ProgramInstanceCreationError::Backend(err.into())
+		)?;
+		Self::fromSourceVariantsMultipleTypes(targets, context, filename, sourceCode, variants, entryPoints)
 	}
 
 	/// Create the package with the given [`Program`] as the sole instance.
@@ -335,27 +586,25 @@ impl Package
 	pub fn fromProgram (program: Program, name: Option<String>, entryPoints: Option<BTreeSet<Option<&str>>>)
 		-> Result<Self, PackageFromProgramError>
 	{
-		let srcType = program.target().intoWgpuSourceType().ok_or_else(
-			|| PackageFromProgramError::IncompatibleProgram(program.target())
-		)?;
+		let target = program.target();
 		let instance = Self::programIntoInstance(program, entryPoints.as_ref()).map_err(
 			|err| PackageFromProgramError::InstanceCreation(err)
 		)?;
-		Ok(Self::fromSingleInstance(srcType, instance, name))
+		Ok(Self::fromSingleInstance(target, instance, name))
 	}
 
 	/// Create the package from the given [`compile::LinkedComposite`].
 	#[cfg(feature="compilation")]
 	#[inline(always)]
 	pub fn fromLinkedComposite<'outer, CompileContext> (
-		sourceType: WgpuSourceType, context: &'outer CompileContext,
+		target: impl Into<Target>+Copy, context: &'outer CompileContext,
 		linkedComposite: &CompileContext::LinkedCompositeType<'outer>, name: Option<String>,
 		entryPoints: Option<BTreeSet<Option<&str>>>
 	) -> Result<Self, ProgramInstanceCreationError>
 	where
 		CompileContext: compile::Context
 	{
-		Self::fromLinkedCompositeMultipleTypes(&[sourceType], context, linkedComposite, name, entryPoints)
+		Self::fromLinkedCompositeMultipleTypes(&[target], context, linkedComposite, name, entryPoints)
 	}
 
 
@@ -363,43 +612,50 @@ impl Package
 	#[cfg(feature="compilation")]
 	#[inline(always)]
 	pub fn fromModule<'outer, CompileContext> (
-		sourceType: WgpuSourceType, context: &'outer CompileContext, module: &CompileContext::ModuleType<'outer>,
+		target: impl Into<Target>+Copy, context: &'outer CompileContext, module: &CompileContext::ModuleType<'outer>,
 		entryPoints: Option<BTreeSet<Option<&str>>>
 	) -> Result<Self, ProgramInstanceCreationError>
 	where
 		CompileContext: compile::Context
 	{
-		Self::fromModuleMultipleTypes(&[sourceType], context, module, entryPoints)
+		Self::fromModuleMultipleTypes(&[target], context, module, entryPoints)
 	}
 
 	/// Create the package from the given shader source file.
 	#[cfg(feature="compilation")]
 	#[inline(always)]
 	pub fn fromSourceFile<CompileContext> (
-		sourceType: WgpuSourceType, context: &CompileContext, filename: impl AsRef<Path>,
+		target: impl Into<Target>+Copy, context: &CompileContext, filename: impl AsRef<Path>,
 		entryPoints: Option<BTreeSet<Option<&str>>>
 	) -> Result<Self, ProgramInstanceCreationError>
 	where
 		CompileContext: compile::HasFileSystemAccess
 	{
-		Self::fromSourceFileMultipleTypes(&[sourceType], context, filename, entryPoints)
+		Self::fromSourceFileMultipleTypes(&[target], context, filename, entryPoints)
 	}
 
 	/// Create the package from the given *Slang* shader source code string.
 	#[cfg(feature="compilation")]
 	#[inline(always)]
 	pub fn fromSource<CompileContext> (
-		sourceType: WgpuSourceType, context: &CompileContext, programName: impl AsRef<Path>,
+		target: impl Into<Target>+Copy, context: &CompileContext, programName: impl AsRef<Path>,
 		sourceCode: impl AsRef<str>, entryPoints: Option<BTreeSet<Option<&str>>>
 	) -> Result<Self, ProgramInstanceCreationError>
 	where CompileContext: compile::Context {
-		Self::fromSourceMultipleTypes(&[sourceType], context, programName, sourceCode, entryPoints)
+		Self::fromSourceMultipleTypes(&[target], context, programName, sourceCode, entryPoints)
 	}
 
-	/// Set the instance of the program for the given source type to the package. If there is already an instance for
-	/// the given source type, it will be replaced.
-	pub fn setInstance (&mut self, sourceType: WgpuSourceType, instance: ProgramInstance) {
-		self.instances.insert(sourceType, instance);
+	/// Set the instance of the program for the given target to the package. If there is already an instance for the
+	/// given target, it will be replaced.
+	#[inline(always)]
+	pub fn setInstance (&mut self, target: impl Into<Target>, instance: ProgramInstance) {
+		self.setVariantInstance(Self::DEFAULT_VARIANT, target, instance)
+	}
+
+	/// Set the instance of the program for the given target in the variant with the given key. If the variant already
+	/// has an instance for the given target, it will be replaced.
+	pub fn setVariantInstance (&mut self, variant: &str, target: impl Into<Target>, instance: ProgramInstance) {
+		self.variants.entry(variant.to_owned()).or_default().insert(target.into().into(), instance);
 	}
 
 	/// Create a *WGPU* shader module ready for binding to a pipeline from the contained program instance of the given
@@ -419,13 +675,40 @@ impl Package
 	/// A ready-to-use *WGPU* shader module if the requested source type and a specialization for the requested entry
 	/// point exist, or an error describing the encountered [failure condition](CreateShaderModuleError).
 	#[cfg(feature="wgpu_runtime")]
+	#[inline(always)]
 	pub fn createShaderModule (
 		&self, device: &wgpu::Device, sourceType: WgpuSourceType, entryPointName: Option<&str>, label: Option<&str>
+	) -> Result<wgpu::ShaderModule, CreateShaderModuleError> {
+		self.createVariantShaderModule(device, Self::DEFAULT_VARIANT, sourceType, entryPointName, label)
+	}
+
+	/// Create a *WGPU* shader module ready for binding to a pipeline from the program instance of the given source type
+	/// contained in the given variant.
+	///
+	/// # Arguments
+	///
+	/// * `device` – The *WGPU* device on which to create the shader module.
+	/// * `variant` – The [key](compile::Variant::key) of the desired program variant.
+	/// * `sourceType` – The desired type of shader source.
+	/// * `entryPointName` – Optionally get the program specialization for the entry point of this name. If no entry
+	///                      point is specified, the generic code containing all code paths for all entry points will be
+	///                      used if available or a [`CreateShaderModuleError::InvalidEntryPoint`] will be emitted.
+	/// * `label` – The string to internally label the GPU-side shader module object with.
+	///
+	/// # Returns
+	///
+	/// A ready-to-use *WGPU* shader module if the requested variant, source type and a specialization for the requested
+	/// entry point exist, or an error describing the encountered [failure condition](CreateShaderModuleError).
+	#[cfg(feature="wgpu_runtime")]
+	pub fn createVariantShaderModule (
+		&self, device: &wgpu::Device, variant: &str, sourceType: WgpuSourceType, entryPointName: Option<&str>,
+		label: Option<&str>
 	) -> Result<wgpu::ShaderModule, CreateShaderModuleError>
 	{
 		// Find requested entry point in the requested instance
-
-		let progInstance = self.instances.get(&sourceType).ok_or(
+		let progInstance = self.variants.get(variant).ok_or_else(
+			|| CreateShaderModuleError::InvalidVariant(variant.to_owned())
+		)?.get(&Target::from(sourceType).into()).ok_or(
 			CreateShaderModuleError::InvalidSourceType(sourceType)
 		)?;
 		let code = progInstance.code(entryPointName).ok_or_else(||
@@ -503,8 +786,24 @@ impl Package
 	/// `Some` ready-to-use *WGPU* shader module if the package contains a suitable instance and a specialization for
 	/// the requested entry point exists, 'None' otherwise.
 	#[cfg(feature="wgpu_runtime")]
+	#[inline(always)]
 	pub fn createShaderModuleFromBestInstance (
 		&self, device: &wgpu::Device, entryPointName: Option<&str>, label: Option<&str>
+	) -> Option<wgpu::ShaderModule> {
+		self.createVariantShaderModuleFromBestInstance(device, Self::DEFAULT_VARIANT, entryPointName, label)
+	}
+
+	/// Create a *WGPU* shader module ready for binding to a pipeline from the most suitable program instance contained
+	/// in the given variant. Source types are preferred in the same order as in
+	/// [`createShaderModuleFromBestInstance`](Self::createShaderModuleFromBestInstance).
+	///
+	/// # Returns
+	///
+	/// `Some` ready-to-use *WGPU* shader module if the package contains the variant with a suitable instance and a
+	/// specialization for the requested entry point exists, 'None' otherwise.
+	#[cfg(feature="wgpu_runtime")]
+	pub fn createVariantShaderModuleFromBestInstance (
+		&self, device: &wgpu::Device, variant: &str, entryPointName: Option<&str>, label: Option<&str>
 	) -> Option<wgpu::ShaderModule>
 	{
 		// Define feasible source types from most to least suitable
@@ -523,7 +822,7 @@ impl Package
 		// Try to get an instance from the feasible source types
 		for sourceType in sourceTypes
 		{
-			match self.createShaderModule(device, sourceType, entryPointName, label)
+			match self.createVariantShaderModule(device, variant, sourceType, entryPointName, label)
 			{
 				Ok(shaderModule) => return Some(shaderModule),
 				Err(err) => {
@@ -532,7 +831,11 @@ impl Package
 						CreateShaderModuleError::InvalidSourceType(_) => continue, // try next source type
 						CreateShaderModuleError::InvalidEntryPoint(ep) => tracing::warn!(
 							"Shader entry point `{ep}` not found in {sourceType} program instance"
-						)
+						),
+						CreateShaderModuleError::InvalidVariant(key) => {
+							tracing::warn!("Shader variant `{key}` not found in package `{}`", self.name);
+							return None;
+						}
 					}
 				}
 			}
@@ -542,9 +845,16 @@ impl Package
 		None
 	}
 
-	/// Serialize the package into a series of bytes (e.g. for storing in a file).
+	/// Serialize the package into a series of bytes (e.g. for storing in a file), using the
+	/// [default compression](container::Compression::default).
+	#[inline(always)]
 	pub fn serialize (&self) -> Vec<u8> {
-		bitcode::encode(self)
+		self.serializeWithCompression(container::Compression::default())
+	}
+
+	/// Serialize the package into a series of bytes, compressing the payload with the given method.
+	pub fn serializeWithCompression (&self, compression: container::Compression) -> Vec<u8> {
+		PACKAGE_FORMAT.pack(&bitcode::encode(self), compression)
 	}
 
 	/// Save the package to a file.
@@ -552,3 +862,38 @@ impl Package
 		Ok(std::fs::write(filename, self.serialize())?)
 	}
 }
+
+/// The layout of [`Package`]s in version 1 of the [package format](PACKAGE_FORMAT), which did not support variants yet.
+#[derive(bitcode::Encode,bitcode::Decode)]
+pub(crate) struct PackageV1 {
+	pub(crate) name: String,
+	pub(crate) instances: BTreeMap<WgpuSourceType, ProgramInstance>
+}
+impl PackageV1
+{
+	/// Migrate an encoded version 1 package to version 2 by turning its instances into the default variant.
+	fn migrate (payload: Vec<u8>) -> anyhow::Result<Vec<u8>> {
+		let Self { name, instances } = bitcode::decode(&payload)?;
+		Ok(bitcode::encode(&PackageV2 {
+			name, variants: BTreeMap::from([(Package::DEFAULT_VARIANT.to_owned(), instances)])
+		}))
+	}
+}
+
+/// The layout of [`Package`]s in version 2 of the [package format](PACKAGE_FORMAT), which keyed instances by
+/// [`WgpuSourceType`].
+#[derive(bitcode::Encode,bitcode::Decode)]
+pub(crate) struct PackageV2 {
+	pub(crate) name: String,
+	pub(crate) variants: BTreeMap<String, BTreeMap<WgpuSourceType, ProgramInstance>>
+}
+impl PackageV2
+{
+	/// Migrate an encoded version 2 package to version 3 by re-keying its instances by target.
+	fn migrate (payload: Vec<u8>) -> anyhow::Result<Vec<u8>> {
+		let Self { name, variants } = bitcode::decode(&payload)?;
+		Ok(bitcode::encode(&Package { name, variants: variants.into_iter().map(|(key, instances)| (
+			key, instances.into_iter().map(|(sourceType, instance)| (Target::from(sourceType).into(), instance)).collect()
+		)).collect()}))
+	}
+}
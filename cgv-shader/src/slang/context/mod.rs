@@ -218,3 +218,17 @@ pub fn uniqueAnonymousSlangFilename () -> PathBuf {
 	baseName.set_extension("slang");
 	baseName
 }
+
+/// Parse the diagnostic output contained in a *Slang* error into structured [`compile::Diagnostics`].
+#[inline(always)]
+fn diagnosticsFrom (err: impl std::fmt::Display) -> compile::Diagnostics {
+	compile::Diagnostics::fromBackendError(err)
+}
+
+/// Turn a *Slang* error into an [`anyhow::Error`] carrying the parsed diagnostics as its root cause, prefixing its
+/// message with the given `context`.
+fn withDiagnostics (context: &str, err: impl std::fmt::Display) -> anyhow::Error {
+	let diagnostics = diagnosticsFrom(err);
+	let message = format!("{context}: {diagnostics}");
+	anyhow::Error::new(diagnostics).context(message)
+}
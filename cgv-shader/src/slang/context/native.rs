@@ -129,6 +129,11 @@ impl<'this> compile::Module<EntryPoint<'this>> for Module<'this>
 	fn entryPoints (&self) -> &[EntryPoint<'this>] {
 		&self.entryPoints
 	}
+
+	fn fileDependencies (&self) -> Vec<PathBuf> {
+		// Slang also reports the virtual paths of modules loaded from source strings, so only keep actual files
+		self.component.dependency_file_paths().map(PathBuf::from).filter(|path| path.is_file()).collect()
+	}
 }
 
 ///
@@ -248,6 +253,7 @@ impl From<&slang::Module> for EnvModule {
 struct SessionConfig {
 	searchPaths: Vec<std::ffi::CString>,
 	targets: util::ds::HashUniqueVec<compile::Target>,
+	defines: BTreeMap<String, String>,
 	compilerOptions: slang::CompilerOptions,
 	profile: slang::ProfileID,
 }
@@ -263,6 +269,7 @@ pub struct ContextBuilder<'ctx> {
 	targets: util::ds::HashUniqueVec<compile::Target>,
 	debug: bool,
 	searchPath: util::ds::HashUniqueVec<PathBuf>,
+	defines: BTreeMap<String, String>,
 	lifetimePhantom: std::marker::PhantomData<&'ctx ()>
 }
 impl ContextBuilder<'_>
@@ -295,10 +302,14 @@ impl ContextBuilder<'_>
 			.debug_information(
 				if self.debug { slang::DebugInfoLevel::Maximal } else { slang::DebugInfoLevel::None }
 			);
+		// - preprocessor defines
+		let compilerOptions = self.defines.iter().fold(
+			compilerOptions, |options, (name, value)| options.macro_define(name, value)
+		);
 
 		// Store in reusable session config
 		let sessionConfig = SessionConfig {
-			targets: self.targets, compilerOptions,
+			targets: self.targets, defines: self.defines, compilerOptions,
 			searchPaths: self.searchPath.iter().map(|p| unsafe {
 				std::ffi::CString::from_vec_unchecked(p.to_string_lossy().as_bytes().to_vec())
 			}).collect::<Vec<std::ffi::CString>>(),
@@ -324,6 +335,7 @@ impl Default for ContextBuilder<'_> {
 		targets: vec![compile::mostSuitableTarget()].into(),
 		debug: cfg!(debug_assertions),
 		searchPath: Default::default(),
+		defines: Default::default(),
 		lifetimePhantom: Default::default()
 	}}
 }
@@ -350,6 +362,12 @@ impl<'ctx> compile::ContextBuilder for ContextBuilder<'ctx>
 		self
 	}
 
+	#[inline(always)]
+	fn addDefines (mut self, defines: &[(&str, &str)]) -> Self {
+		self.defines.extend(defines.iter().map(|&(name, value)| (name.to_owned(), value.to_owned())));
+		self
+	}
+
 	#[inline(always)]
 	fn build (self) -> Result<Self::Context, compile::CreateContextError> {
 		self.buildWithGlobalSession(obtainGlobalSession())
@@ -404,6 +422,10 @@ impl<'this> compile::Context for Context<'this>
 		self.session.activeTargetsMap[target.slot()].is_some()
 	}
 
+	fn defines (&self) -> impl Iterator<Item=(&str, &str)> {
+		self.sessionConfig.defines.iter().map(|(name, value)| (name.as_str(), value.as_str()))
+	}
+
 	#[inline]
 	fn compileFromSource<'ctx> (&self, sourceCode: &str) -> Result<Module<'ctx>, compile::LoadModuleError>
 		where 'this: 'ctx
@@ -423,7 +445,7 @@ impl<'this> compile::Context for Context<'this>
 		let module =  self.session.load_module_from_source_string(
 			targetPath, targetPath, sourceCode
 		).or_else(
-			|err| Err(compile::LoadModuleError::CompilationError(err.into()))
+			|err| Err(compile::LoadModuleError::CompilationError(diagnosticsFrom(err).into()))
 		)?;
 
 		// Enumerate and save entry points
@@ -460,7 +482,7 @@ impl<'this> compile::Context for Context<'this>
 	{
 		// Link
 		let componentType = composite.component.link().or_else(|err| Err(
-			compile::LinkError::Backend(anyhow!("link failure: {err}"))
+			compile::LinkError::Backend(withDiagnostics("link failure", err))
 		))?;
 
 		// Enumerate all entry points. We blanket-use the very first target, as names and ordering of entry points
@@ -503,7 +525,7 @@ impl compile::HasFileSystemAccess for Context<'_>
 		let module =  self.session.load_module(
 			sourceFile.as_ref().to_string_lossy().as_ref()
 		).or_else(
-			|err| Err(compile::LoadModuleError::CompilationError(err.into()))
+			|err| Err(compile::LoadModuleError::CompilationError(diagnosticsFrom(err).into()))
 		)?;
 
 		// Enumerate and save entry points
@@ -558,7 +580,7 @@ impl compile::EnvironmentEnabled for Context<'_>
 		// Load the IR bytecode blob into the Slang session
 		let irBlob = slang::ComPtr::new(slang::VecBlob::from_slice(bytes));
 		self.session.load_module_from_ir_blob(targetPath_str, targetPath_str, &irBlob).or_else(
-			|err| Err(compile::LoadModuleError::CompilationError(err.into()))
+			|err| Err(compile::LoadModuleError::CompilationError(diagnosticsFrom(err).into()))
 		)?;
 
 		// Store the IR module in the environment
@@ -594,7 +616,7 @@ impl compile::EnvironmentEnabled for Context<'_>
 					EnvModule::SourceCode(sourceCode) =>
 						newSession.load_module_from_source_string(&path, "", sourceCode).or_else(|err|Err(
 							compile::SetEnvironmentError::Backend(
-								compile::LoadModuleError::CompilationError(err.into()).into()
+								compile::LoadModuleError::CompilationError(diagnosticsFrom(err).into()).into()
 							)
 						))?,
 
@@ -602,7 +624,7 @@ impl compile::EnvironmentEnabled for Context<'_>
 						let irBlob = slang::ComPtr::new(slang::VecBlob::from_slice(bytes));
 						newSession.load_module_from_ir_blob(&path, "", &irBlob).or_else(|err|Err(
 							compile::SetEnvironmentError::Backend(
-								compile::LoadModuleError::CompilationError(err.into()).into()
+								compile::LoadModuleError::CompilationError(diagnosticsFrom(err).into()).into()
 							)
 						))?
 					}
@@ -58,7 +58,10 @@ impl GlobalSession
 			sessionConfig.targets.iter().enumerate().for_each(
 				|(idx, target)| activeTargetsMap[target.slot()] = Some(idx as u32)
 			);
-			Ok(Session { handle: handle as u64, activeTargetsMap, gsPhantom: Default::default() })
+			Ok(Session {
+				handle: handle as u64, activeTargetsMap, defines: sessionConfig.defines.clone(),
+				gsPhantom: Default::default()
+			})
 		}
 		else {
 			Err(CreateSessionError::Generic)
@@ -101,6 +104,7 @@ impl Drop for ComponentList {
 pub struct Session<'this> {
 	handle: u64,
 	activeTargetsMap: ActiveTargetsMap,
+	defines: BTreeMap<String, String>,
 	gsPhantom: PhantomData<&'this GlobalSession>
 }
 impl<'this> Session<'this>
@@ -111,11 +115,17 @@ impl<'this> Session<'this>
 		// Make sure we get a valid target path
 		let targetPath = validateModulePath(virtualFilepath.as_ref())?;
 
+		// The JavaScript bridge does not forward compiler options, so apply the session defines to the source directly
+		let sourceCode = compile::variant::sourceWithDefines(
+			virtualFilepath.as_ref(), self.defines.iter().map(|(name, value)| (name.as_str(), value.as_str())),
+			sourceCode
+		);
+
 		// Compile via JavaScript bridge
 		tracing::warn!("Session #{}: Compiling module `{targetPath}` via JavaScript bridge", self.handle);
-		let moduleHandle = slangjs_Session_loadModuleFromSource(self.handle, targetPath, targetPath, sourceCode);
+		let moduleHandle = slangjs_Session_loadModuleFromSource(self.handle, targetPath, targetPath, &sourceCode);
 		if moduleHandle < 0 {
-			return Err(compile::LoadModuleError::CompilationError(anyhow!("Failed to compile module `{targetPath}`")))
+			return Err(compile::LoadModuleError::CompilationError(diagnosticsFrom(slangjs_lastErrorMessage()).into()))
 		}
 
 		// Return resulting module
@@ -177,6 +187,11 @@ impl<'this> compile::Module<EntryPoint<'this>> for Module<'this>
 	fn entryPoints (&self) -> &[EntryPoint<'this>] {
 		&self.entryPoints
 	}
+
+	fn fileDependencies (&self) -> Vec<PathBuf> {
+		// WASM contexts have no filesystem access, so all modules come from source strings or the environment
+		Vec::new()
+	}
 }
 impl compile::Component for Module<'_> {
 	type Id = u64;
@@ -346,13 +361,15 @@ impl compile::LinkedComposite for LinkedComposite<'_>
 /// as [`compile::Environment`] compatibility checking.
 #[derive(Clone)]
 struct SessionConfig {
-	targets: util::ds::HashUniqueVec<compile::Target>
+	targets: util::ds::HashUniqueVec<compile::Target>,
+	defines: BTreeMap<String, String>
 }
 
 
 ///
 pub struct ContextBuilder<'ctx> {
 	targets: util::ds::HashUniqueVec<compile::Target>,
+	defines: BTreeMap<String, String>,
 	lifetimePhantom: PhantomData<&'ctx ()>
 }
 impl ContextBuilder<'_>
@@ -370,7 +387,7 @@ impl ContextBuilder<'_>
 		}
 
 		// Populate reusable session config
-		let sessionConfig = SessionConfig { targets: self.targets };
+		let sessionConfig = SessionConfig { targets: self.targets, defines: self.defines };
 
 		// Create the stateful Slang compiler session
 		let session = Context::freshSession(&globalSession, &sessionConfig).map_err(
@@ -387,7 +404,8 @@ impl ContextBuilder<'_>
 }
 impl Default for ContextBuilder<'_> {
 	fn default () -> Self { Self {
-		targets: vec![compile::mostSuitableTarget()].into(), lifetimePhantom: Default::default()
+		targets: vec![compile::mostSuitableTarget()].into(), defines: Default::default(),
+		lifetimePhantom: Default::default()
 	}}
 }
 impl<'ctx> compile::ContextBuilder for ContextBuilder<'ctx> {
@@ -411,6 +429,12 @@ impl<'ctx> compile::ContextBuilder for ContextBuilder<'ctx> {
 		self
 	}
 
+	#[inline(always)]
+	fn addDefines (mut self, defines: &[(&str, &str)]) -> Self {
+		self.defines.extend(defines.iter().map(|&(name, value)| (name.to_owned(), value.to_owned())));
+		self
+	}
+
 	#[inline(always)]
 	fn build (self) -> Result<Context<'ctx>, compile::CreateContextError> {
 		self.buildWithGlobalSession(&GLOBAL_SESSION)
@@ -450,6 +474,10 @@ impl<'this> compile::Context for Context<'this>
 		self.session.activeTargetsMap[target.slot()].is_some()
 	}
 
+	fn defines (&self) -> impl Iterator<Item=(&str, &str)> {
+		self.sessionConfig.defines.iter().map(|(name, value)| (name.as_str(), value.as_str()))
+	}
+
 	#[inline]
 	fn compileFromSource<'ctx> (&self, sourceCode: &str) -> Result<Module<'ctx>, compile::LoadModuleError>
 		where 'this: 'ctx
@@ -496,7 +524,7 @@ impl<'this> compile::Context for Context<'this>
 		// Link
 		let handle = slangjs_Composite_link(composite.handle);
 		if handle < 0 {
-			return Err(compile::LinkError::Backend(anyhow!("Slang link error")));
+			return Err(compile::LinkError::Backend(withDiagnostics("link failure", slangjs_lastErrorMessage())));
 		}
 
 		// Build entry point name->index map
@@ -634,6 +662,8 @@ pub fn obtainGlobalSession () -> &'static GlobalSession {
 /// API prototypes of the JavaScript bridge.
 #[wasm_bindgen]
 extern "C" {
+	fn slangjs_lastErrorMessage () -> String;
+
 	fn slangjs_createGlobalSession () -> i64;
 	fn slangjs_dropGlobalSession (handle: u64);
 
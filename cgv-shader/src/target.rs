@@ -0,0 +1,240 @@
+//////
+//
+// Imports
+//
+
+// Standard library
+use std::fmt::{Display, Formatter};
+
+// GUID library
+use cgv_util::uuid;
+
+// CGV-rs core libraries
+use cgv_util as util;
+
+// Local imports
+use crate::*;
+
+
+
+//////
+//
+// Enums
+//
+
+/// Enum describing possible formats of a [`Target`].
+#[derive(Debug,Clone,Copy,PartialEq,Eq,Hash)]
+pub enum TargetFormat {
+	/// A sequence of arbitrary bytes.
+	Binary,
+
+	/// UTF-8 encoded text.
+	Text
+}
+impl Display for TargetFormat
+{
+	fn fmt (&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Binary => write!(f, "binary"),
+			Self::Text => write!(f, "text")
+		}
+	}
+}
+
+
+/// Enum describing possible known shader compilation targets as well as a [`Custom`](Target::Custom) target for
+/// supporting targets not currently known to *CGV-rs*.
+#[repr(u8)]
+#[derive(Debug,Clone,Copy,PartialEq,Eq,Hash)]
+pub enum Target
+{
+	/// Compile shaders to *SPIR-V*, specifying whether they should be debuggable or not.
+	SPIRV,
+
+	/// Transpile shaders to *WebGPU Shading Language*.
+	WGSL,
+
+	/// Transpile shaders to *DirectX Intermediate Language*.
+	DXIL,
+
+	/// Transpile shaders to *GL Shading Language*.
+	GLSL,
+
+	/// Transpile shaders to *High-level Shading Language*.
+	HLSL,
+
+	/// Transpile shaders to *Cuda-C++*.
+	CudaCpp,
+
+	/// Transpile shaders to the *Metal* shading language.
+	Metal,
+
+	/// Compile to a custom target that the [`compile::Context`] supports. Implementations should make a best effort to
+	/// select a globally unique [UUID](uuid) (first value) to identify their custom target. The second value indicates
+	/// the format of the target. If implementations cannot guarantee [text targets](TargetFormat::Text) are UTF-8, they
+	/// might want to choose marking it as a [binary format](TargetFormat::Binary) instead.
+	///
+	/// If a target comprises several formats (e.g. one text and one binary variant), implementations should introduce
+	/// distinct `Custom` targets for them.
+	Custom(uuid::Uuid, TargetFormat)
+}
+impl Target
+{
+	/// The highest slot any `Target` corresponds to. *CGV-rs* shall adopt the convention that this will always
+	/// be equal to the [discriminant](std::mem::discriminant) of the [`Custom`](Target::Custom) variant.
+	///
+	/// The type is intentionally kept as `u8` (thus requiring an explicit cast to `usize` for most practical purposes)
+	/// to emphasize that this number will always be quite small. Its value will always be one less than
+	/// [`Target::NUM_SLOTS`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use cgv_util::uuid::Uuid;
+	/// # use cgv_shader::{Target, TargetFormat};
+	/// let someCustomTarget = Target::Custom(
+	/// 	Uuid::from_u64_pair(42, 0xBADF00D), TargetFormat::Binary
+	/// );
+	/// assert_eq!(someCustomTarget.slot(), Target::MAX_SLOT as usize);
+	/// ```
+	pub const MAX_SLOT: u8 = {
+		// Ensure we stay informed about the primitive representation used for `Target` in case it ever gets
+		// changed
+		util::assert_eq_size!(std::mem::Discriminant<Target>, u8);
+
+		const DUMMY_CUSTOM: Target = Target::Custom(uuid::Uuid::from_u128(u128::MAX), TargetFormat::Text);
+		const MAX: u8 = DUMMY_CUSTOM.slot() as u8;
+
+		// Ensure we stay informed about the highest discriminant value whenever we change `Target`
+		util::const_assert_eq!(MAX, 7);
+		MAX
+	};
+
+	/// The number of slots that an array storing one value per variant of the `Target` enum would have. Will
+	/// always be one more than the related [`Target::MAX_SLOT`] constant.
+	///
+	/// The type is intentionally kept as `u8` (thus requiring an explicit cast to `usize` for most practical purposes)
+	/// to emphasize that this number will always be quite small.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// // A map enabling lightning-fast O(1) checks if a compilation target is active, and if yes, which index it
+	/// // corresponds to.
+	/// # use cgv_shader::Target;
+	/// type ActiveTargetsMap = [Option<u32>; Target::NUM_SLOTS as usize];
+	/// ```
+	pub const NUM_SLOTS: u8 = Target::MAX_SLOT + 1;
+
+	///
+	pub fn vecFromWgpuSourceTypes (wgpuSourceTypes: &[WgpuSourceType]) -> Vec<Self> {
+		wgpuSourceTypes.iter().map(|&srcType| srcType.into()).collect()
+	}
+
+	/// The corresponding *slot* of a certain target. This will always be one less than [`Target::NUM_SLOTS`]
+	/// less-than-or-equal to [`Target::MAX_SLOT`].
+	#[inline(always)]
+	pub const fn slot (&self) -> usize
+	{
+		unsafe {
+			// SAFETY:
+			// `Target` is a `repr(u8)`, and the Rust specification states that the discriminants of enums with
+			// primitive representation may be obtained via pointer casting even if the enum is complex:
+			// https://doc.rust-lang.org/reference/items/enumerations.html#pointer-casting
+			*(self as *const Target as *const u8) as usize
+		}
+	}
+
+	///
+	#[inline(always)]
+	pub fn isSPIRV (&self) -> bool {
+		matches!(self, Self::SPIRV)
+	}
+
+	///
+	#[inline(always)]
+	pub fn isWGSL (&self) -> bool {
+		matches!(self, Self::WGSL)
+	}
+
+	///
+	#[inline(always)]
+	pub fn isGLSL (&self) -> bool {
+		matches!(self, Self::GLSL)
+	}
+
+	///
+	#[inline(always)]
+	pub fn isCustom (&self) -> bool {
+		matches!(self, Self::Custom(_, _))
+	}
+
+	///
+	#[inline]
+	pub fn isText (&self) -> bool {
+		match self {
+			Self::WGSL | Self::GLSL | Self::HLSL | Self::CudaCpp | Self::Metal => true,
+			Self::SPIRV | Self::DXIL => false,
+			Self::Custom(_, format) => matches!(format, TargetFormat::Text)
+		}
+	}
+
+	///
+	#[inline]
+	pub fn isBinary (&self) -> bool {
+		match self {
+			Self::WGSL | Self::GLSL | Self::HLSL | Self::CudaCpp | Self::Metal => false,
+			Self::SPIRV | Self::DXIL => true,
+			Self::Custom(_, format) => matches!(format, TargetFormat::Binary)
+		}
+	}
+
+	/// Returns the corresponding [`WgpuSourceType`] if it exists.
+	#[inline]
+	pub fn asWgpuSourceType (&self) -> Option<WgpuSourceType> {
+		match self {
+			Self::WGSL => Some(WgpuSourceType::WGSL),
+			Self::GLSL => Some(WgpuSourceType::GLSL),
+			Self::SPIRV => Some(WgpuSourceType::SPIRV),
+			_ => None
+		}
+	}
+
+	/// Consumes `self` and produces the corresponding [`WgpuSourceType`] if it exists.
+	#[inline(always)]
+	pub fn intoWgpuSourceType (self) -> Option<WgpuSourceType> {
+		self.asWgpuSourceType()
+	}
+}
+impl From<WgpuSourceType> for Target {
+	fn from (value: WgpuSourceType) -> Self {
+		match value {
+			WgpuSourceType::SPIRV => Self::SPIRV,
+			WgpuSourceType::WGSL => Self::WGSL,
+			WgpuSourceType::GLSL => Self::GLSL
+		}
+	}
+}
+impl Display for Target
+{
+	fn fmt (&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+	{
+		match self {
+			Self::SPIRV => write!(f, "SPIR-V"),
+			Self::WGSL => write!(f, "WGSL"),
+			Self::DXIL => write!(f, "DXIL"),
+			Self::GLSL => write!(f, "GLSL"),
+			Self::HLSL => write!(f, "HLSL"),
+			Self::CudaCpp => write!(f, "Cuda-C++"),
+			Self::Metal => write!(f, "Metal"),
+			Self::Custom(uuid, fmt) => write!(f, "Custom[uuid={uuid},{fmt}]")
+		}
+	}
+}
+impl util::ds::UniqueVecElement for Target {
+	type Key = std::mem::Discriminant<Self>;
+
+	fn key (&self) -> Self::Key {
+		std::mem::discriminant(self)
+	}
+}
@@ -0,0 +1,101 @@
+
+//////
+//
+// Imports
+//
+
+// Local imports
+use crate::{*, cache::*, pak::ProgramInstance};
+
+
+
+//////
+//
+// Tests
+//
+
+/// Helper for creating a unique, initially non-existing directory for a test cache.
+fn tempCacheDir () -> std::path::PathBuf {
+	std::env::temp_dir().join(format!("cgv-shader-test-cache{}", uniqueAnonymousName()))
+}
+
+/// Helper for creating a dummy package.
+fn dummyPackage (code: &[u8]) -> Package {
+	Package::fromSingleInstance(WgpuSourceType::WGSL, ProgramInstance::generic(code.to_vec()), Some("dummy".into()))
+}
+
+#[test]
+fn test_KeyBuilder()
+{
+	let key = || KeyBuilder::new().optionsDigest(42).sourceType(WgpuSourceType::WGSL).source("a.slang", "code");
+	assert_eq!(key().build(), key().build());
+	assert_ne!(key().build(), key().entryPoint(None).build());
+	assert_ne!(key().build(), KeyBuilder::new().optionsDigest(43).sourceType(WgpuSourceType::WGSL).source(
+		"a.slang", "code"
+	).build());
+
+	// Chunk boundaries must not be ambiguous
+	assert_ne!(
+		KeyBuilder::new().source("a.slang", "bc").build(), KeyBuilder::new().source("a.slangb", "c").build()
+	);
+}
+
+#[test]
+fn test_Cache_roundtrip()
+{
+	let dir = tempCacheDir();
+	let cache = Cache::inDirectory(&dir);
+	let key = KeyBuilder::new().source("test.slang", "void main() {}").build();
+	assert!(cache.get(&key).is_none());
+
+	let package = dummyPackage(&[1, 2, 3]);
+	cache.insert(&key, &package, &[] as &[&str]).unwrap();
+	assert_eq!(cache.get(&key).map(|p| p.serialize()), Some(package.serialize()));
+
+	// Built packages are only requested on misses
+	let other = cache.getOrInsertWith(&key, || panic!("cache entry should have been found"));
+	assert_eq!(other.serialize(), package.serialize());
+
+	cache.remove(&key);
+	assert!(cache.get(&key).is_none());
+	std::fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn test_Cache_invalidation()
+{
+	let dir = tempCacheDir();
+	std::fs::create_dir_all(&dir).unwrap();
+	let dependency = dir.join("import.slang");
+	std::fs::write(&dependency, "module import;").unwrap();
+
+	let cache = Cache::inDirectory(&dir);
+	let key = KeyBuilder::new().source("test.slang", "import import;").build();
+	cache.insert(&key, &dummyPackage(&[4, 5]), &[&dependency]).unwrap();
+	assert!(cache.get(&key).is_some());
+
+	// Touching an imported file without changing it keeps the entry, and so does changing it back and forth
+	let touch = |file: &std::path::Path, secondsAgo: u64| std::fs::File::options().write(true).open(file).unwrap()
+		.set_modified(std::time::SystemTime::now() - std::time::Duration::from_secs(secondsAgo)).unwrap();
+	touch(&dependency, 60);
+	assert!(cache.get(&key).is_some());
+	std::fs::write(&dependency, "module IMPORT;").unwrap();
+	std::fs::write(&dependency, "module import;").unwrap();
+	assert!(cache.get(&key).is_some());
+
+	// Changing an imported file invalidates the entry, even if its size stays the same
+	std::fs::write(&dependency, "module IMPORT;").unwrap();
+	touch(&dependency, 30);
+	assert!(cache.get(&key).is_none());
+	cache.insert(&key, &dummyPackage(&[4, 5]), &[&dependency]).unwrap();
+	std::fs::write(&dependency, "module import; // changed").unwrap();
+	assert!(cache.get(&key).is_none());
+	let rebuilt = cache.getOrInsertWith(&key, || (dummyPackage(&[6]), vec![dependency.clone()]));
+	assert_eq!(rebuilt.serialize(), dummyPackage(&[6]).serialize());
+	assert_eq!(cache.getWithFileDependencies(&key).map(|(_, files)| files), Some(vec![dependency.clone()]));
+
+	// Corrupted entries are discarded
+	std::fs::write(dir.join(format!("{key}.spkc")), b"garbage").unwrap();
+	assert!(cache.get(&key).is_none());
+	std::fs::remove_dir_all(dir).ok();
+}
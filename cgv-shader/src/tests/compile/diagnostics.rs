@@ -0,0 +1,109 @@
+
+//////
+//
+// Imports
+//
+
+// Standard library
+use std::path::{Path, PathBuf};
+
+// Local imports
+use crate::compile::*;
+
+
+
+//////
+//
+// Tests
+//
+
+const SLANG_OUTPUT: &str = "\
+shader/test.slang(12): error 30015: undefined identifier 'foo'.
+    foo = 1;
+    ^~~
+shader/lib/other.slang(3,7): warning 39001: implicit conversion
+internal error: something went wrong
+";
+
+#[test]
+fn test_Diagnostics_parse()
+{
+	let diagnostics = Diagnostics::parse(SLANG_OUTPUT);
+	assert_eq!(diagnostics.len(), 3);
+
+	// Location with column inferred from the marker line
+	assert_eq!(diagnostics[0], Diagnostic {
+		file: Some(PathBuf::from("shader/test.slang")), line: Some(12), column: Some(5), severity: Severity::Error,
+		code: Some(30015), message: "undefined identifier 'foo'.".into()
+	});
+
+	// Explicit column
+	assert_eq!(diagnostics[1].line, Some(3));
+	assert_eq!(diagnostics[1].column, Some(7));
+	assert_eq!(diagnostics[1].severity, Severity::Warning);
+	assert!(!diagnostics[1].isError());
+
+	// No location and no code
+	assert_eq!(diagnostics[2].file, None);
+	assert_eq!(diagnostics[2].code, None);
+	assert_eq!(diagnostics[2].severity, Severity::Fatal);
+
+	// Queries
+	assert_eq!(diagnostics.errors().count(), 2);
+	assert_eq!(diagnostics.inFile(Path::new("test.slang")).count(), 1);
+	assert_eq!(diagnostics.raw(), SLANG_OUTPUT.trim_end());
+}
+
+#[test]
+fn test_Diagnostic_render()
+{
+	let diagnostics = Diagnostics::parse(SLANG_OUTPUT);
+	let source = "\n\n\n\n\n\n\n\n\n\n\n    foo = 1;\n";
+	assert_eq!(
+		diagnostics[0].render(Some(source)),
+		"error[30015]: undefined identifier 'foo'.\n  --> shader/test.slang:12:5\n   |\n12 |     foo = 1;\n   |     ^"
+	);
+	assert_eq!(diagnostics[2].render(None), "fatal error: something went wrong");
+}
+
+#[test]
+fn test_Diagnostics_renderFromFiles()
+{
+	// Set up the referenced file inside a search path directory
+	let dir = std::env::temp_dir().join(format!("cgv-shader-test-diagnostics{}", crate::uniqueAnonymousName()));
+	std::fs::create_dir_all(dir.join("shader")).unwrap();
+	std::fs::write(dir.join("shader/test.slang"), "\n\n\n\n\n\n\n\n\n\n\n    foo = 1;\n").unwrap();
+
+	// Excerpts are only included if the file can be found
+	let diagnostics = Diagnostics::parse(SLANG_OUTPUT);
+	assert!(diagnostics.renderFromFiles(&[&dir]).contains("12 |     foo = 1;"));
+	assert!(!diagnostics.renderFromFiles(&[] as &[&Path]).contains("12 |"));
+	std::fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn test_Diagnostic_render_fileLevel()
+{
+	// Line and column 0 refer to the file as a whole and must not produce an excerpt
+	let diagnostics = Diagnostics::parse("shader/test.slang(0,0): error 1: module not found\n");
+	assert_eq!((diagnostics[0].line, diagnostics[0].column), (Some(0), Some(0)));
+	assert_eq!(
+		diagnostics[0].render(Some("    foo = 1;\n")), "error[1]: module not found\n  --> shader/test.slang:0:0"
+	);
+
+	// Column 0 on an actual line still shows the excerpt, but without marker
+	let diagnostic = Diagnostic { line: Some(1), ..diagnostics[0].clone() };
+	assert_eq!(
+		diagnostic.render(Some("    foo = 1;\n")),
+		"error[1]: module not found\n  --> shader/test.slang:1:0\n  |\n1 |     foo = 1;"
+	);
+}
+
+#[test]
+fn test_diagnosticsOf()
+{
+	let diagnostics = Diagnostics::parse(SLANG_OUTPUT);
+	let err = LoadModuleError::CompilationError(anyhow::Error::new(diagnostics).context("compiling"));
+	assert_eq!(err.diagnostics().map(|d| d.len()), Some(3));
+	assert!(LoadModuleError::DuplicatePath("x".into()).diagnostics().is_none());
+}
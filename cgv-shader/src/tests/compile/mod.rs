@@ -7,6 +7,12 @@
 /// Tests for the `environment` module.
 mod environment;
 
+/// Tests for the `diagnostics` module.
+mod diagnostics;
+
+/// Tests for the `variant` module.
+mod variant;
+
 
 
 //////
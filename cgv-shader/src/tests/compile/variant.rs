@@ -0,0 +1,75 @@
+
+//////
+//
+// Imports
+//
+
+// Standard library
+use std::path::Path;
+
+// Local imports
+use crate::compile::*;
+
+
+
+//////
+//
+// Tests
+//
+
+#[test]
+fn test_Variant_key()
+{
+	// The default variant maps to the empty key
+	assert_eq!(Variant::new().key(), crate::Package::DEFAULT_VARIANT);
+
+	// Derived keys are canonical regardless of insertion order
+	let a = Variant::new().define("WITH_COLORS", "1").define("ALPHA", "0").specializeType("Material", "IMaterial", "Phong");
+	let b = Variant::new().specializeType("Material", "IMaterial", "Phong").define("ALPHA", "0").define("WITH_COLORS", "1");
+	assert_eq!(a.key(), "ALPHA=0,WITH_COLORS=1,Material->Phong");
+	assert_eq!(a.key(), b.key());
+
+	// Named variants use their name
+	assert_eq!(Variant::named("colored").define("WITH_COLORS", "1").key(), "colored");
+}
+
+#[test]
+fn test_Variant_source()
+{
+	let programPath = Path::new("shader/test.slang");
+
+	// The default variant leaves everything untouched
+	let default = Variant::new();
+	assert_eq!(default.modulePath(programPath), programPath);
+	assert_eq!(default.preprocessedSource(programPath, "code"), "code");
+	assert_eq!(default.specializationModuleSource(), None);
+
+	// Defines are prepended, keeping line numbers intact
+	let variant = Variant::new().define("WITH_COLORS", "1");
+	let modulePath = variant.modulePath(programPath);
+	assert_ne!(modulePath, programPath);
+	assert_eq!(modulePath.parent(), programPath.parent());
+	assert_eq!(
+		variant.preprocessedSource(&modulePath, "code"),
+		format!("#define WITH_COLORS 1\n#line 1 \"{}\"\ncode", modulePath.display())
+	);
+	assert_eq!(variant.specializationModuleSource(), None);
+
+	// Specialization arguments generate a linkable module
+	let variant = Variant::new()
+		.import("lib/material.slang")
+		.specializeType("Material", "IMaterial", "Phong")
+		.specializeConstant("NUM_LIGHTS", "int", "4");
+	assert!(variant.hasSpecializations());
+	assert_eq!(variant.specializationModuleSource().unwrap(), "\
+import \"lib/material.slang\";
+export struct Material: IMaterial = Phong;
+export static const int NUM_LIGHTS = 4;
+");
+	assert_ne!(variant.specializationModulePath(programPath), variant.modulePath(programPath));
+
+	// The specialization module is always Slang, while the main module keeps the language of the program
+	let wgslPath = Path::new("shader/test.wgsl");
+	assert_eq!(variant.modulePath(wgslPath).extension().unwrap(), "wgsl");
+	assert_eq!(variant.specializationModulePath(wgslPath).extension().unwrap(), "slang");
+}
@@ -0,0 +1,177 @@
+
+//////
+//
+// Imports
+//
+
+// Standard library
+use std::collections::BTreeMap;
+
+// Local imports
+use crate::{*, container::*};
+
+
+
+//////
+//
+// Helpers
+//
+
+/// A test format at version 2 that can be migrated to from version 1 (by appending a byte), but not from legacy data.
+const TEST_FORMAT: Format = Format {
+	name: "test payload", magic: *b"CGV-TST\x1A", version: 2,
+	migrations: &[Migration { from: 1, migrate: |mut bytes| { bytes.push(0xFF); Ok(bytes) } }]
+};
+
+/// The test format in its previous version.
+const TEST_FORMAT_V1: Format = Format { version: 1, migrations: &[], ..TEST_FORMAT };
+
+/// Create a package with some easily recognizable code in it.
+fn dummyPackage () -> Package {
+	Package::fromSingleInstance(
+		WgpuSourceType::SPIRV, ProgramInstance::generic([1u8, 2, 3, 4].repeat(64)), Some("dummy".into())
+	)
+}
+
+
+
+//////
+//
+// Tests
+//
+
+#[test]
+fn test_Format_roundtrip ()
+{
+	let payload = b"some payload that repeats, repeats, repeats, repeats, repeats".to_vec();
+	for compression in [Compression::None, Compression::Lz4]
+	{
+		let bytes = TEST_FORMAT.pack(&payload, compression);
+		let header = TEST_FORMAT.header(&bytes).unwrap().unwrap();
+		assert_eq!(header.compression, compression);
+		assert_eq!(header.formatVersion, 2);
+		assert_eq!(header.payloadLength, payload.len() as u64);
+		assert_eq!(TEST_FORMAT.unpack(&bytes).unwrap(), (payload.clone(), 2));
+	}
+}
+
+#[test]
+fn test_Format_corruption ()
+{
+	let mut bytes = TEST_FORMAT.pack(b"payload", Compression::None);
+	*bytes.last_mut().unwrap() ^= 0x01;
+	assert!(matches!(TEST_FORMAT.unpack(&bytes), Err(UnpackError::ChecksumMismatch{..})));
+
+	// Header fields are covered by the checksum too
+	let mut bytes = TEST_FORMAT.pack(b"payload", Compression::None);
+	bytes[10] ^= 0x01;
+	assert!(matches!(TEST_FORMAT.unpack(&bytes), Err(UnpackError::ChecksumMismatch{..})));
+	let mut bytes = TEST_FORMAT.pack(b"payload", Compression::Lz4);
+	bytes[27] = 0x7F;
+	assert!(matches!(TEST_FORMAT.unpack(&bytes), Err(UnpackError::ChecksumMismatch{..})));
+
+	let mut bytes = TEST_FORMAT.pack(b"payload", Compression::None);
+	bytes[12] = 42;
+	assert!(matches!(TEST_FORMAT.unpack(&bytes), Err(UnpackError::UnknownCompression(42))));
+
+	let mut bytes = TEST_FORMAT.pack(b"payload", Compression::None);
+	bytes[8] = 99;
+	assert!(matches!(TEST_FORMAT.unpack(&bytes), Err(UnpackError::UnsupportedContainerVersion(99))));
+}
+
+#[test]
+fn test_Format_hostileLength ()
+{
+	// Re-seal a container after tampering with its announced payload length, like a hostile writer would
+	let withLength = |compression, length: u64| {
+		let mut bytes = TEST_FORMAT.pack(b"payload", compression);
+		bytes[20..28].copy_from_slice(&length.to_le_bytes());
+		let mut hasher = crc32fast::Hasher::new();
+		hasher.update(&bytes[..16]);
+		hasher.update(&bytes[20..]);
+		bytes[16..20].copy_from_slice(&hasher.finalize().to_le_bytes());
+		bytes
+	};
+	assert!(matches!(
+		TEST_FORMAT.unpack(&withLength(Compression::Lz4, u64::MAX >> 8)),
+		Err(UnpackError::InconsistentLength{..})
+	));
+	assert!(matches!(
+		TEST_FORMAT.unpack(&withLength(Compression::None, 8)), Err(UnpackError::InconsistentLength{..})
+	));
+}
+
+#[test]
+fn test_Format_versions ()
+{
+	// Older version with a migration path
+	let old = TEST_FORMAT_V1.pack(b"old", Compression::Lz4);
+	assert_eq!(TEST_FORMAT.unpack(&old).unwrap(), (b"old\xFF".to_vec(), 1));
+
+	// Newer version
+	let new = TEST_FORMAT.pack(b"new", Compression::None);
+	assert!(matches!(
+		TEST_FORMAT_V1.unpack(&new), Err(UnpackError::IncompatibleVersion { found: 2, supported: 1, .. })
+	));
+
+	// Legacy data without a migration path
+	assert!(matches!(
+		TEST_FORMAT.unpack(b"legacy"), Err(UnpackError::IncompatibleVersion { found: 0, supported: 2, .. })
+	));
+
+	// Container of another format
+	let package = dummyPackage().serialize();
+	assert!(matches!(TEST_FORMAT.unpack(&package), Err(UnpackError::WrongFormat("test payload"))));
+}
+
+#[test]
+fn test_Package_serialize ()
+{
+	let package = dummyPackage();
+	let bytes = package.serialize();
+	assert!(bytes.starts_with(&PACKAGE_FORMAT.magic));
+	assert!(bytes.len() < package.serializeWithCompression(Compression::None).len());
+	assert_eq!(Package::deserialize(&bytes).unwrap().serialize(), bytes);
+
+	// Legacy header-less packages and version 1 packages are migrated, turning their instances into the default variant
+	let instances = || package.instances().map(|(target, instance)| (
+		target.intoWgpuSourceType().unwrap(), ProgramInstance::generic(instance.code(None).unwrap().to_vec())
+	)).collect::<BTreeMap<_, _>>();
+	let v1 = bitcode::encode(&crate::pak::PackageV1 { name: "dummy".into(), instances: instances() });
+	assert_eq!(Package::deserialize(&v1).unwrap().serialize(), bytes);
+	let v1 = Format { version: 1, migrations: &[], ..PACKAGE_FORMAT }.pack(&v1, Compression::Lz4);
+	let migrated = Package::deserialize(&v1).unwrap();
+	assert_eq!(migrated.variants().collect::<Vec<_>>(), [Package::DEFAULT_VARIANT]);
+	assert_eq!(migrated.serialize(), bytes);
+
+	// Version 2 packages are re-keyed by target
+	let v2 = bitcode::encode(&crate::pak::PackageV2 {
+		name: "dummy".into(), variants: [(Package::DEFAULT_VARIANT.to_owned(), instances())].into()
+	});
+	let v2 = Format { version: 2, migrations: &[], ..PACKAGE_FORMAT }.pack(&v2, Compression::None);
+	assert_eq!(Package::deserialize(&v2).unwrap().serialize(), bytes);
+
+	// Garbage is reported as such
+	let err = Package::deserialize(b"definitely not a package").err().unwrap();
+	assert!(err.to_string().contains("legacy"));
+}
+
+#[test]
+fn test_Package_anyTarget ()
+{
+	// Packages hold instances for targets WGPU can't consume just as well
+	const CUSTOM: Target = Target::Custom(util::uuid::Uuid::from_u128(0x0123_4567_89AB_CDEF), TargetFormat::Binary);
+	let mut package = dummyPackage();
+	const HLSL_CODE: &[u8] = b"float4 main () : SV_Target { return 1; }";
+	package.setInstance(Target::HLSL, ProgramInstance::generic(HLSL_CODE.to_vec()));
+	package.setVariantInstance("custom", CUSTOM, ProgramInstance::generic(vec![0xAB; 16]));
+	let package = Package::deserialize(&package.serialize()).unwrap();
+	assert_eq!(
+		package.instances().map(|(target, _)| target).collect::<Vec<_>>(), [Target::SPIRV, Target::HLSL]
+	);
+	assert_eq!(package.instance(Target::HLSL).unwrap().code(None).unwrap(), HLSL_CODE);
+	assert_eq!(package.variantInstance("custom", CUSTOM).unwrap().code(None).unwrap(), [0xAB; 16]);
+	assert!(package.variantInstance("custom", Target::Custom(
+		util::uuid::Uuid::from_u128(0x0123_4567_89AB_CDEF), TargetFormat::Text
+	)).is_none());
+}
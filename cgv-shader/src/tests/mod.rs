@@ -8,10 +8,21 @@
 #[cfg(feature="compilation")]
 mod compile;
 
+/// Tests for the `container` module.
+mod container;
+
+/// Tests for the `cache` module.
+#[cfg(feature="compilation")]
+mod cache;
+
 /// Tests for the `slang_runtime` module.
 #[cfg(feature="slang_runtime")]
 mod slang;
 
+/// Tests for the `naga_runtime` module.
+#[cfg(feature="naga_runtime")]
+mod naga;
+
 
 
 //////
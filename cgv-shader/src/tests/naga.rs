@@ -0,0 +1,169 @@
+
+//////
+//
+// Imports
+//
+
+// Local imports
+use crate::{*, compile::{ContextBuilder, Context, Module, ComponentRef}};
+
+
+
+//////
+//
+// Helpers
+//
+
+/// A *WGSL* shader with a vertex and a fragment entry point.
+const TRIANGLE_WGSL: &str = "\
+@vertex
+fn vertexMain (@builtin(vertex_index) idx: u32) -> @builtin(position) vec4f {
+	let x = f32(i32(idx) - 1);
+	let y = f32(i32(idx & 1u) * 2 - 1);
+	return vec4f(x, y, 0., 1.);
+}
+
+@fragment
+fn fragmentMain () -> @location(0) vec4f {
+	return vec4f(1., 0.5, 0., 1.);
+}
+";
+
+/// A *GLSL* compute shader whose workgroup size is controlled by the `GROUP_SIZE` define.
+const FILL_GLSL: &str = "\
+#version 450
+layout(local_size_x = GROUP_SIZE) in;
+layout(set = 0, binding = 0) buffer Data { uint values[]; };
+void main () {
+	values[gl_GlobalInvocationID.x] = gl_GlobalInvocationID.x;
+}
+";
+
+/// Create a *Naga* compilation context for both targets it supports.
+fn createContext () -> crate::naga::Context {
+	crate::naga::ContextBuilder::withTargets(&[compile::Target::SPIRV, compile::Target::WGSL]).build()
+		.expect("failed to create Naga compilation context")
+}
+
+/// Check that the given bytes are a *SPIR-V* module.
+fn isSPIRV (code: &[u8]) -> bool {
+	code.starts_with(&0x07230203u32.to_le_bytes())
+}
+
+
+
+//////
+//
+// Tests
+//
+
+#[test]
+fn test_compilation_wgsl ()
+{
+	let context = createContext();
+	let module = context.compileFromNamedSource("triangle.wgsl", TRIANGLE_WGSL).expect("failed to compile shader");
+	assert_eq!(module.entryPoints().len(), 2);
+	assert_eq!(module.entryPoint("vertexMain").unwrap().stage(), ::naga::ShaderStage::Vertex);
+	assert_eq!(module.entryPoint("fragmentMain").unwrap().stage(), ::naga::ShaderStage::Fragment);
+	assert!(module.fileDependencies().is_empty());
+
+	// Unsupported targets are rejected up-front
+	assert!(matches!(
+		crate::naga::ContextBuilder::withTarget(compile::Target::HLSL).build(),
+		Err(compile::CreateContextError::UnsupportedTarget(compile::Target::HLSL))
+	));
+}
+
+#[test]
+fn test_Package_wgsl ()
+{
+	let context = createContext();
+	let package = Package::fromSourceMultipleTypes(
+		&[WgpuSourceType::SPIRV, WgpuSourceType::WGSL], &context, "triangle.wgsl", TRIANGLE_WGSL,
+		Some([None, Some("vertexMain"), Some("fragmentMain")].into())
+	).expect("failed to create package");
+	assert_eq!(package.name(), "triangle.wgsl");
+
+	// SPIR-V
+	let spirv = package.instance(WgpuSourceType::SPIRV).unwrap();
+	assert!(isSPIRV(spirv.code(None).unwrap()));
+	assert!(isSPIRV(spirv.code(Some("vertexMain")).unwrap()));
+
+	// WGSL, specialized instances only contain their own entry point
+	let wgsl = package.instance(WgpuSourceType::WGSL).unwrap();
+	let all = str::from_utf8(wgsl.code(None).unwrap()).unwrap();
+	assert!(all.contains("fn vertexMain") && all.contains("fn fragmentMain"));
+	let fragment = str::from_utf8(wgsl.code(Some("fragmentMain")).unwrap()).unwrap();
+	assert!(fragment.contains("fn fragmentMain") && !fragment.contains("fn vertexMain"));
+}
+
+#[test]
+fn test_Package_glslVariants ()
+{
+	let context = createContext();
+	let package = Package::fromSourceVariantsMultipleTypes(
+		&[WgpuSourceType::WGSL], &context, "fill.comp", FILL_GLSL, &[
+			compile::Variant::new().define("GROUP_SIZE", "64"),
+			compile::Variant::named("wide").define("GROUP_SIZE", "256")
+		], None
+	).expect("failed to create package");
+	let wgsl = |variant| String::from_utf8(
+		package.variantInstance(variant, WgpuSourceType::WGSL).unwrap().code(None).unwrap().to_vec()
+	).unwrap();
+	assert!(wgsl("GROUP_SIZE=64").contains("@workgroup_size(64"));
+	assert!(wgsl("wide").contains("@workgroup_size(256"));
+
+	// WGSL has no preprocessor to apply defines with
+	let context = crate::naga::ContextBuilder::default().define("FOO", "1").build().unwrap();
+	assert!(context.compileFromNamedSource("triangle.wgsl", TRIANGLE_WGSL).is_err());
+}
+
+#[test]
+fn test_diagnostics ()
+{
+	let context = createContext();
+
+	// Parse error
+	let err = context.compileFromNamedSource(
+		"broken.wgsl", "@fragment\nfn main () -> @location(0) vec4f {\n\treturn 1.\n}"
+	).err().expect("compilation should have failed");
+	let diagnostics = err.diagnostics().expect("missing diagnostics");
+	let first = diagnostics.errors().next().unwrap();
+	assert!(first.isInFile("broken.wgsl"));
+	assert_eq!(first.line, Some(4));
+
+	// Validation error
+	let err = context.compileFromNamedSource(
+		"invalid.wgsl", "fn f () -> f32 {\n\treturn 1.;\n}\nfn g () -> u32 {\n\treturn f();\n}"
+	).err().expect("validation should have failed");
+	let first = err.diagnostics().expect("missing diagnostics").errors().next().unwrap();
+	assert_eq!(first.line, Some(5));
+}
+
+#[test]
+fn test_linking ()
+{
+	let context = createContext();
+	let (vertex, fragment) = TRIANGLE_WGSL.split_at(TRIANGLE_WGSL.find("@fragment").unwrap());
+	let vertex = context.compileFromNamedSource("vertex.wgsl", vertex).unwrap();
+	let fragment = context.compileFromNamedSource("fragment.wgsl", fragment).unwrap();
+
+	// Selected entry points only
+	let composite = context.createComposite(&[
+		ComponentRef::Module(&vertex), ComponentRef::Module(&fragment),
+		ComponentRef::EntryPoint(fragment.entryPoint("fragmentMain").unwrap())
+	]).unwrap();
+	let program = Program::fromLinkedComposite(
+		&context, compile::Target::WGSL, &context.linkComposite(&composite).unwrap()
+	).unwrap();
+	assert_eq!(program.entryPointProgs().len(), 1);
+	assert!(program.entryPointProg("fragmentMain").is_some());
+
+	// Conflicting definitions are reported in the module they stem from
+	let clash = context.compileFromNamedSource("clash.wgsl", "\n\nfn vertexMain () {}").unwrap();
+	let composite = context.createComposite(&[ComponentRef::Module(&vertex), ComponentRef::Module(&clash)]).unwrap();
+	let err = context.linkComposite(&composite).err().expect("linking should have failed");
+	let diagnostics = err.diagnostics().expect("missing diagnostics");
+	assert!(diagnostics.errors().next().unwrap().isInFile("clash.wgsl"));
+	assert_eq!(diagnostics.errors().next().unwrap().line, Some(3));
+}
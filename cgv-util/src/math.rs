@@ -76,6 +76,90 @@ impl<S: Sized, T: glm::Number+From<S>> Number<S> for T {}
 
 
 
+//////
+//
+// Structs
+//
+
+/// An axis-aligned bounding box in 3D space.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Aabb
+{
+	/// The corner of the box with the smallest coordinates.
+	pub min: glm::Vec3,
+
+	/// The corner of the box with the largest coordinates.
+	pub max: glm::Vec3
+}
+impl Aabb
+{
+	/// Create the bounding box spanned by the given corners.
+	#[inline(always)]
+	pub fn new (min: glm::Vec3, max: glm::Vec3) -> Self {
+		Self { min, max }
+	}
+
+	/// Compute the smallest bounding box containing all of the given points.
+	///
+	/// # Arguments
+	///
+	/// * `points` – The points to enclose.
+	///
+	/// # Returns
+	///
+	/// The bounding box, or `None` if there were no points.
+	pub fn fromPoints (points: impl IntoIterator<Item=glm::Vec3>) -> Option<Self>
+	{
+		let mut points = points.into_iter();
+		let first = points.next()?;
+		let mut aabb = Self::new(first, first);
+		points.for_each(|point| aabb.extend(&point));
+		Some(aabb)
+	}
+
+	/// Grow the box just enough to contain the given point.
+	#[inline]
+	pub fn extend (&mut self, point: &glm::Vec3) {
+		self.min = glm::min2(&self.min, point);
+		self.max = glm::max2(&self.max, point);
+	}
+
+	/// Compute the smallest bounding box containing both this and the given box.
+	#[inline]
+	pub fn merge (&self, other: &Self) -> Self {
+		Self::new(glm::min2(&self.min, &other.min), glm::max2(&self.max, &other.max))
+	}
+
+	/// The center point of the box.
+	#[inline(always)]
+	pub fn center (&self) -> glm::Vec3 {
+		0.5*(self.min + self.max)
+	}
+
+	/// The side lengths of the box.
+	#[inline(always)]
+	pub fn extent (&self) -> glm::Vec3 {
+		self.max - self.min
+	}
+
+	/// The length of the diagonal of the box, i.e. the diameter of its circumsphere.
+	#[inline(always)]
+	pub fn diameter (&self) -> f32 {
+		self.extent().norm()
+	}
+
+	/// The eight corner points of the box.
+	pub fn corners (&self) -> [glm::Vec3; 8] {
+		std::array::from_fn(|i| glm::vec3(
+			if i & 1 == 0 { self.min.x } else { self.max.x },
+			if i & 2 == 0 { self.min.y } else { self.max.y },
+			if i & 4 == 0 { self.min.z } else { self.max.z }
+		))
+	}
+}
+
+
+
 //////
 //
 // Functions
@@ -144,3 +228,27 @@ pub fn smoothLerp<S: Number, V: Copy+Mul<S>+Add> (v1: V, v2: V, t_linear: S) ->
 {
 	lerp(v1, v2, smoothstep(t_linear))
 }
+
+/// Evaluates the Bézier curve given by the provided control points at parameter `t` using the algorithm of
+/// *de Casteljau*. Mirrors `cgv::math::poly::evalBezier` from the *Slang* shader library.
+///
+/// # Arguments
+///
+/// * `coeffs` – The control points of the curve, the degree of which is one less than their number. Must not be
+///              empty.
+/// * `t` – The curve parameter, with the curve interpolating the first and last control points at `0` and `1`.
+///
+/// # Returns
+///
+/// The point on the curve at parameter `t`.
+pub fn evalBezier<S: glm::Number, V: Copy+Mul<S>+Add, const N: usize> (mut coeffs: [V; N], t: S) -> V
+	where <V as Mul<S>>::Output: Copy+Add<Output=V>
+{
+	// Successively reduce the degree of the curve by one until only the function value remains
+	for l in 1..N {
+		for i in 0..N-l {
+			coeffs[i] = lerp(coeffs[i], coeffs[i+1], t);
+		}
+	}
+	coeffs[0]
+}
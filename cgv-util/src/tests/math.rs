@@ -200,3 +200,42 @@ fn test_smoothLerp_vec2_interpolates_componentwise() {
 	assert_close_vec2(&smoothLerp(v1, v2, t), &lerp(v1, v2, smoothstep(t)));
 	assert_close_vec2(&smoothLerp(v1, v2, t), &glm::vec2(-0.75, 2.125));
 }
+
+#[test]
+fn test_evalBezier_interpolates_endpoints() {
+	let coeffs = [glm::vec2(0.0, 0.0), glm::vec2(1.0, 2.0), glm::vec2(3.0, 2.0), glm::vec2(4.0, 0.0)];
+	assert_close_vec2(&evalBezier(coeffs, 0.0f32), &coeffs[0]);
+	assert_close_vec2(&evalBezier(coeffs, 1.0f32), &coeffs[3]);
+}
+
+#[test]
+fn test_evalBezier_cubic_reference_values() {
+	let coeffs = [glm::vec2(0.0, 0.0), glm::vec2(1.0, 2.0), glm::vec2(3.0, 2.0), glm::vec2(4.0, 0.0)];
+	assert_close_vec2(&evalBezier(coeffs, 0.5f32), &glm::vec2(2.0, 1.5));
+	assert_close_f32(evalBezier([0.0f32, 0.0, 0.0, 1.0], 0.5), 0.125);
+}
+
+#[test]
+fn test_evalBezier_linear_matches_lerp() {
+	assert_close_f32(evalBezier([-4.0f32, 8.0], 0.25), lerp(-4.0f32, 8.0, 0.25));
+	assert_close_f32(evalBezier([3.5f32], 0.75), 3.5);
+}
+
+#[test]
+fn test_Aabb_fromPoints_encloses_all_points() {
+	assert!(Aabb::fromPoints(std::iter::empty()).is_none());
+	let aabb = Aabb::fromPoints([glm::vec3(1.0, -2.0, 0.0), glm::vec3(-1.0, 2.0, 4.0), glm::vec3(0.0, 0.0, 2.0)])
+		.unwrap();
+	assert_eq!(aabb, Aabb::new(glm::vec3(-1.0, -2.0, 0.0), glm::vec3(1.0, 2.0, 4.0)));
+	assert_eq!(aabb.center(), glm::vec3(0.0, 0.0, 2.0));
+	assert_close_f32(aabb.diameter(), 6.0);
+	assert!(aabb.corners().iter().all(|c| aabb.merge(&Aabb::new(*c, *c)) == aabb));
+}
+
+#[test]
+fn test_Aabb_merge_is_union() {
+	let a = Aabb::new(glm::vec3(0.0, 0.0, 0.0), glm::vec3(1.0, 1.0, 1.0));
+	let b = Aabb::new(glm::vec3(-1.0, 0.5, 2.0), glm::vec3(0.5, 0.5, 3.0));
+	assert_eq!(a.merge(&b), Aabb::new(glm::vec3(-1.0, 0.0, 0.0), glm::vec3(1.0, 1.0, 3.0)));
+	assert_eq!(a.merge(&b), b.merge(&a));
+}
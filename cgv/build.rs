@@ -80,20 +80,22 @@ fn main() -> cgv_build::Result<()>
 			env.addModule(recommendedStorage, "cgv/media/geom.slang")?;
 			env.addModule(recommendedStorage, "cgv/color/blending.slang")?;
 			env.addModule(recommendedStorage, "cgv/geom/common.slang")?;
+			env.addModule(recommendedStorage, "cgv/geom/input.slang")?;
 			env.addModule(recommendedStorage, "cgv/api/uniforms.slang")?;
 			env.addModule(recommendedStorage, "cgv/geom/aabox.slang")?;
 			env.addModule(recommendedStorage, "cgv/geom/billboard.slang")?;
 			env.addModule(recommendedStorage, "cgv/geom/intersect/common.slang")?;
 			env.addModule(recommendedStorage, "cgv/geom/splat/sphere.slang")?;
 			env.addModule(recommendedStorage, "cgv/geom/splat/hermite.slang")?;
+			env.addModule(recommendedStorage, "cgv/renderer/spheres.slang")?;
 			env.addModule(recommendedStorage, "cgv/gpu/filter/kernel.slang")?;
 			env.addModule(recommendedStorage, "cgv/gpu/filter.slang")
 		}
 	)?;
 
-	// Compile our internally used shaders
+	// Compile our internally used shaders (except the ones with specialization points that get linked at runtime)
 	cgv_build::prepareShaders(
-		&buildSetup, None, "shader", /* exclude: */Some(&["common", "lib"])
+		&buildSetup, None, "shader", /* exclude: */Some(&["common", "lib", "renderer/runtime"])
 	)?;
 
 	// Done!
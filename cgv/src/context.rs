@@ -81,6 +81,7 @@ pub struct Context {
 
 	samplerCache: DashMap<SamplerDescriptor, wgpu::Sampler>,
 	pub(crate) mipmapPipelineCache: gpu::mipmap::PipelineCache,
+	pub(crate) computePipelineCache: gpu::compute::PipelineCache,
 }
 impl Context
 {
@@ -94,14 +95,20 @@ impl Context
 	/// # Returns
 	///
 	/// A new graphics context owning references to the *WGPU* HAL objects the *egui_wgpu* `RenderState` provided.
-	pub(crate) fn new (eguiRS: &egui_wgpu::RenderState) -> Self { Self {
+	pub(crate) fn new (eguiRS: &egui_wgpu::RenderState) -> Self {
+		// WGPU HAL objects are internally reference counted, so cloning just creates a new owned reference
+		Self::fromHal(eguiRS.adapter.clone(), eguiRS.device.clone(), eguiRS.queue.clone())
+	}
+
+	/// Create a context for the given *WGPU* HAL objects, e.g. ones that were set up without any surface for
+	/// [headless](Player::runHeadless) rendering.
+	pub(crate) fn fromHal (adapter: wgpu::Adapter, device: wgpu::Device, queue: wgpu::Queue) -> Self { Self {
 		// Hardware objects
-		adapter: eguiRS.adapter.clone(), // WGPU HAL objects are internally
-		device: eguiRS.device.clone(),   // reference counted, so cloning just
-		queue: eguiRS.queue.clone(),     // creates a new owned reference
+		adapter, device, queue,
 		// Caches
 		samplerCache: DashMap::with_capacity(8),
 		mipmapPipelineCache: DashMap::with_capacity(8),
+		computePipelineCache: DashMap::with_capacity(8),
 	}}
 
 	/// Reference the *WGPU* instance.
@@ -0,0 +1,374 @@
+//////
+//
+// Imports
+//
+
+// Standard library
+use std::hash::{Hash, Hasher};
+
+// Dashmap library
+use dashmap::DashMap;
+
+// WGPU API
+use wgpu;
+
+// Local imports
+use crate::*;
+
+
+
+//////
+//
+// Structs and enums
+//
+
+/// Compute pipelines are cached per [kernel content](Kernel::pipelineKey) and per combination of formats and view
+/// dimensionalities of the textures bound to it, since both are baked into the bind group layout of the pipeline.
+pub(crate) type PipelineCache = DashMap<
+	(u64, Vec<(wgpu::TextureFormat, wgpu::TextureViewDimension)>), Box<ComputePipelineInfo>
+>;
+
+/// Stores a compute pipeline and associated objects the pipeline references.
+pub struct ComputePipelineInfo {
+	bindGroupLayout: wgpu::BindGroupLayout,
+	pipeline: wgpu::ComputePipeline
+}
+
+/// Describes the kind of resource a [`Kernel`] expects at one of its binding slots. Bindings are numbered in the order
+/// they are declared, and all of them live in bind group `0`.
+#[derive(Copy,Clone,Debug,PartialEq,Eq,Hash)]
+pub enum Binding
+{
+	/// A uniform buffer.
+	Uniform,
+
+	/// A storage buffer, which the kernel may only read from if `readOnly` is `true`.
+	Storage { readOnly: bool },
+
+	/// A texture the kernel reads texels from without a sampler. The sample type and view dimensionality are inferred
+	/// from the texture bound to the slot.
+	Texture,
+
+	/// A storage texture with the given access mode. The format and view dimensionality are inferred from the texture
+	/// bound to the slot.
+	StorageTexture(wgpu::StorageTextureAccess)
+}
+
+/// A resource to bind to one of the [binding slots](Binding) of a [`Kernel`].
+#[derive(Clone,Debug)]
+pub enum Resource<'res>
+{
+	/// A buffer bound in its entirety.
+	Buffer(&'res wgpu::Buffer),
+
+	/// A sub-range of a buffer.
+	BufferRange(wgpu::BufferBinding<'res>),
+
+	/// A texture, bound via its [full view](hal::Texture::view).
+	Texture(&'res hal::Texture),
+
+	/// A single mipmap level of a texture.
+	TextureLevel(&'res hal::Texture, usize)
+}
+impl Resource<'_>
+{
+	/// Get the texture view the resource refers to along with its format and view dimensionality, or `None` if the
+	/// resource is a buffer.
+	fn textureView (&self) -> Option<(&wgpu::TextureView, wgpu::TextureFormat, wgpu::TextureViewDimension)>
+	{
+		let (texture, view) = match self {
+			Self::Texture(texture) => (*texture, &texture.view),
+			Self::TextureLevel(texture, level) => (*texture, &texture.mipLevels[*level].view),
+			Self::Buffer(_) | Self::BufferRange(_) => return None
+		};
+		let dimension = texture.mipLevels[0].desc.dimension.expect(
+			"Texture mip level views should be created with explicit dimensionality!"
+		);
+		Some((view, texture.descriptor.format, dimension))
+	}
+
+	/// Turn into a *WGPU* binding resource.
+	fn bindingResource (&self) -> wgpu::BindingResource<'_> {
+		match self {
+			Self::Buffer(buffer) => buffer.as_entire_binding(),
+			Self::BufferRange(binding) => wgpu::BindingResource::Buffer(binding.clone()),
+			Self::Texture(_) | Self::TextureLevel(..) =>
+				wgpu::BindingResource::TextureView(self.textureView().unwrap().0)
+		}
+	}
+}
+
+
+
+//////
+//
+// Classes
+//
+
+/// A general-purpose compute kernel, i.e. a compute shader entry point in a [shader package](shader::Package) along
+/// with the layout of the resources it operates on.
+///
+/// Kernels don't hold any GPU objects themselves, so they can be created once (e.g. in a `static`) and used with any
+/// [`Context`]. The actual compute pipelines are created on first use and cached in the context, one for every
+/// combination of texture formats the kernel gets dispatched with. The cache is keyed by what the kernel consists of
+/// rather than by the kernel object, so kernels that get re-created over and over all share the same pipelines.
+pub struct Kernel<'pkg> {
+	packageDigest: util::uuid::Uuid,
+	package: &'pkg shader::Package,
+	variant: String,
+	entryPoint: String,
+	workgroupSize: glm::UVec3,
+	bindings: Vec<Binding>,
+	constants: Vec<(String, f64)>,
+	label: String
+}
+impl<'pkg> Kernel<'pkg>
+{
+	/// Create a kernel for the given entry point of the given shader package.
+	///
+	/// # Arguments
+	///
+	/// * `package` – The shader package containing the kernel.
+	/// * `entryPoint` – The name of the compute shader entry point implementing the kernel.
+	/// * `workgroupSize` – The workgroup size the entry point declares, used for computing the number of workgroups
+	///                     to dispatch.
+	/// * `bindings` – The resources the kernel expects in bind group `0`, in the order of their binding indices.
+	pub fn new (package: &'pkg shader::Package, entryPoint: &str, workgroupSize: glm::UVec3, bindings: &[Binding])
+		-> Self
+	{
+		assert!(workgroupSize.iter().all(|&size| size > 0), "Compute workgroup size must not be zero!");
+		Self {
+			packageDigest: package.contentDigest(), package, variant: shader::Package::DEFAULT_VARIANT.to_owned(),
+			entryPoint: entryPoint.to_owned(), workgroupSize, bindings: bindings.to_vec(), constants: Vec::new(),
+			label: format!("CGV__gpu_computeKernel_{}_{entryPoint}", package.name())
+		}
+	}
+
+	/// Use the instances of the package variant with the given [key](shader::compile::Variant::key) instead of the
+	/// default variant.
+	pub fn variant (mut self, key: impl Into<String>) -> Self {
+		self.variant = key.into();
+		self
+	}
+
+	/// Set the pipeline-overridable constant with the given name to the given value.
+	pub fn constant (mut self, name: impl Into<String>, value: f64) -> Self {
+		self.constants.push((name.into(), value));
+		self
+	}
+
+	/// Report the workgroup size of the kernel.
+	#[inline(always)]
+	pub fn workgroupSize (&self) -> glm::UVec3 {
+		self.workgroupSize
+	}
+
+	/// Report the binding slots of the kernel.
+	#[inline(always)]
+	pub fn bindings (&self) -> &[Binding] {
+		&self.bindings
+	}
+
+	/// Compute the number of workgroups needed for covering the given number of invocations in each dimension.
+	#[inline(always)]
+	pub fn numWorkgroups (&self, numInvocations: glm::UVec3) -> glm::UVec3 {
+		numWorkgroups(numInvocations, self.workgroupSize)
+	}
+
+	/// Compute the key identifying the pipelines of the kernel in the pipeline cache, covering everything that goes
+	/// into creating them apart from the bound textures.
+	fn pipelineKey (&self) -> u64
+	{
+		let mut hasher = std::hash::DefaultHasher::new();
+		self.packageDigest.hash(&mut hasher);
+		self.variant.hash(&mut hasher);
+		self.entryPoint.hash(&mut hasher);
+		self.bindings.hash(&mut hasher);
+		for (name, value) in &self.constants {
+			name.hash(&mut hasher);
+			value.to_bits().hash(&mut hasher);
+		}
+		hasher.finish()
+	}
+
+	/// Obtain the compute pipeline suitable for dispatching the kernel on the given resources, creating it if it is not
+	/// in the pipeline cache of the context yet.
+	fn ensureComputePipeline<'ctx> (&self, context: &'ctx Context, resources: &[Resource]) -> &'ctx ComputePipelineInfo
+	{
+		use dashmap::Entry;
+
+		// Match the resources against the binding slots of the kernel
+		assert_eq!(
+			resources.len(), self.bindings.len(),
+			"Compute kernel `{}` expects {} resources!", self.entryPoint, self.bindings.len()
+		);
+		let textures = self.bindings.iter().zip(resources).filter_map(|(binding, resource)| match binding {
+			Binding::Uniform | Binding::Storage{..} => {
+				assert!(resource.textureView().is_none(), "Texture bound to buffer slot of compute kernel!");
+				None
+			},
+			Binding::Texture | Binding::StorageTexture(_) => {
+				let (_, format, dimension) = resource.textureView().expect(
+					"Buffer bound to texture slot of compute kernel!"
+				);
+				Some((format, dimension))
+			}
+		}).collect::<Vec<_>>();
+
+		// Query the cache
+		let entry = match context.computePipelineCache.entry((self.pipelineKey(), textures)) {
+			// We already have a suitable pipeline for this combination
+			Entry::Occupied(entry) => return unsafe {
+				// SAFETY: - The values are boxed, so their addresses are stable even when iterators are invalidated.
+				//         - Entries remain cached until the context is dropped, which must happen after 'ctx.
+				// TODO: Replace with Box::as_ptr once it is stable.
+				&*(entry.get().as_ref() as *const _)
+			},
+			// We need a new pipeline for this combination!
+			Entry::Vacant(entry) => entry
+		};
+
+		// Set up bind group layout
+		let mut textures = entry.key().1.iter();
+		let bindGroupLayoutEntries = self.bindings.iter().enumerate().map(|(slot, binding)| wgpu::BindGroupLayoutEntry {
+			binding: slot as u32,
+			visibility: wgpu::ShaderStages::COMPUTE,
+			ty: match *binding {
+				Binding::Uniform => wgpu::BindingType::Buffer {
+					ty: wgpu::BufferBindingType::Uniform, has_dynamic_offset: false, min_binding_size: None
+				},
+				Binding::Storage { readOnly } => wgpu::BindingType::Buffer {
+					ty: wgpu::BufferBindingType::Storage { read_only: readOnly }, has_dynamic_offset: false,
+					min_binding_size: None
+				},
+				Binding::Texture => {
+					let &(format, view_dimension) = textures.next().unwrap();
+					wgpu::BindingType::Texture {
+						multisampled: false,
+						// We never bind samplers, so unfilterable is always sufficient and compatible with everything
+						sample_type: match format.sample_type(None, None).expect(
+							"Combined depth/stencil textures cannot be bound to compute kernels!"
+						) {
+							wgpu::TextureSampleType::Float{..} => wgpu::TextureSampleType::Float { filterable: false },
+							sampleType => sampleType
+						},
+						view_dimension
+					}
+				},
+				Binding::StorageTexture(access) => {
+					let &(format, view_dimension) = textures.next().unwrap();
+					wgpu::BindingType::StorageTexture { access, format, view_dimension }
+				}
+			},
+			count: None
+		}).collect::<Vec<_>>();
+		let bindGroupLayout = context.device().create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+			entries: bindGroupLayoutEntries.as_slice(),
+			label: Some(&format!("{}_bindGroupLayout", self.label))
+		});
+
+		// Create pipeline
+		// - shader
+		let shader = self.package.createVariantShaderModuleFromBestInstance(
+			context.device(), &self.variant, None, Some(&format!("{}_shaderModule", self.label))
+		).unwrap_or_else(|| panic!(
+			"Shader package `{}` should contain a compute kernel `{}`", self.package.name(), self.entryPoint
+		));
+		// - pipeline
+		let constants = self.constants.iter().map(|(name, value)| (name.as_str(), *value)).collect::<Vec<_>>();
+		let pipeline = context.device().create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+			module: &shader,
+			entry_point: Some(&self.entryPoint),
+			layout: Some(&context.device().create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+				bind_group_layouts: &[Some(&bindGroupLayout)],
+				label: Some(&format!("{}_pipelineLayout", self.label)),
+				immediate_size: 0
+			})),
+			compilation_options: wgpu::PipelineCompilationOptions {
+				constants: &constants, ..Default::default()
+			},
+			cache: None,
+			label: Some(&format!("{}_pipeline", self.label)),
+		});
+		let entry = entry.insert(Box::new(ComputePipelineInfo { bindGroupLayout, pipeline }));
+		unsafe {
+			// SAFETY: - The values are boxed, so their addresses are stable even when iterators are invalidated.
+			//         - Entries remain cached until the context is dropped, which must happen after 'ctx.
+			// TODO: Replace with Box::as_ptr once it is stable.
+			&*(entry.value().as_ref() as *const _)
+		}
+	}
+
+	/// Record a dispatch of the kernel into the given compute pass.
+	///
+	/// # Arguments
+	///
+	/// * `context` – The graphics context.
+	/// * `pass` – The pass to record the dispatch into. Must be a [compute pass](gpu::Pass::Compute).
+	/// * `resources` – The resources to bind, one for each [binding slot](Self::bindings) of the kernel.
+	/// * `numInvocations` – The total number of kernel invocations in each dimension. Since whole workgroups are
+	///                      dispatched, the kernel must check itself whether an invocation is out of bounds if the
+	///                      numbers are not multiples of the [workgroup size](Self::workgroupSize).
+	pub fn dispatch<'encoder> (
+		&self, context: &Context, pass: &mut gpu::Pass<'encoder>, resources: &[Resource], numInvocations: glm::UVec3
+	){
+		// Obtain pipeline suitable for the given resources
+		let pi = self.ensureComputePipeline(context, resources);
+		let entries = resources.iter().enumerate().map(|(slot, resource)| wgpu::BindGroupEntry {
+			binding: slot as u32, resource: resource.bindingResource()
+		}).collect::<Vec<_>>();
+		let bindGroup = context.device().create_bind_group(&wgpu::BindGroupDescriptor {
+			layout: &pi.bindGroupLayout, entries: &entries, label: Some(&format!("{}_bindGroup", self.label))
+		});
+
+		// Record compute calls
+		let pass = pass.refCompute();
+		let workgroups = self.numWorkgroups(numInvocations);
+		pass.set_pipeline(&pi.pipeline);
+		pass.set_bind_group(0, &bindGroup, &[]);
+		pass.dispatch_workgroups(workgroups.x, workgroups.y, workgroups.z);
+	}
+
+	/// Record a dispatch of the kernel into a new compute pass of the given command encoder.
+	pub fn dispatchWithEncoder (
+		&self, context: &Context, encoder: &mut wgpu::CommandEncoder, resources: &[Resource],
+		numInvocations: glm::UVec3
+	){
+		let mut pass = gpu::Pass::Compute(encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+			label: Some(&self.label), timestamp_writes: None
+		}));
+		self.dispatch(context, &mut pass, resources, numInvocations);
+	}
+
+	/// Dispatch the kernel using a throw-away command encoder that gets submitted to the queue right away.
+	pub fn dispatchAdhoc (&self, context: &Context, resources: &[Resource], numInvocations: glm::UVec3)
+	{
+		// Create throw-away command encoder
+		let mut encoder = context.device().create_command_encoder(
+			&wgpu::CommandEncoderDescriptor::default()
+		);
+
+		// Encode the dispatch
+		self.dispatchWithEncoder(context, &mut encoder, resources, numInvocations);
+
+		// Submit
+		context.queue().submit([encoder.finish()]);
+	}
+}
+
+
+
+//////
+//
+// Functions
+//
+
+/// Compute the number of workgroups of the given size needed for covering the given number of invocations in each
+/// dimension.
+#[inline]
+pub fn numWorkgroups (numInvocations: glm::UVec3, workgroupSize: glm::UVec3) -> glm::UVec3 {
+	glm::vec3(
+		numInvocations.x.div_ceil(workgroupSize.x), numInvocations.y.div_ceil(workgroupSize.y),
+		numInvocations.z.div_ceil(workgroupSize.z)
+	)
+}
@@ -11,6 +11,10 @@ pub mod DEPRECATED_data;
 /// Submodule providing utilities for fast mip map computation
 pub mod mipmap;
 
+/// Submodule providing general-purpose compute kernels
+pub mod compute;
+pub use compute::Kernel; // re-export
+
 
 
 //////
@@ -13,6 +13,18 @@ use hal::texture;
 
 
 
+//////
+//
+// Constants
+//
+
+/// The texture format of the optional [object ID attachment](Framebuffer::ids). Each texel stores an object ID in its
+/// red and the index of the element within that object in its green channel, with an object ID of *0* denoting that
+/// nothing was rendered there.
+pub const ID_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rg32Uint;
+
+
+
 //////
 //
 // Structs and enums
@@ -183,6 +195,32 @@ impl Framebuffer
 	pub fn depthStencil (&self) -> Option<&hal::Texture> {
 		self.depthStencil.as_ref()
 	}
+
+	/// Reference the object ID attachment, which is always the color attachment in slot *1* if present.
+	///
+	/// # Returns
+	///
+	/// `Some` reference to the ID texture if the framebuffer has one, `None` otherwise.
+	pub fn ids (&self) -> Option<&hal::Texture> {
+		self.color(1).filter(|texture| texture.descriptor.format == ID_FORMAT)
+	}
+
+	/// Add or remove the [object ID attachment](Self::ids), leaving all other attachments untouched. The framebuffer
+	/// must have exactly one other color attachment.
+	pub fn setIdAttachment (&mut self, context: &Context, enabled: bool)
+	{
+		assert!(!self.color.is_empty(), "framebuffer needs a main color attachment to add object IDs to");
+		if !enabled {
+			self.color.truncate(1);
+		}
+		else if self.color.len() < 2 {
+			let label = self.color0().name.as_deref().map(|name| format!("{name}_ids"));
+			self.color.push(hal::Texture::createEmpty(
+				context, glm::vec3(self.dims.x, self.dims.y, 1), ID_FORMAT, 1, texture::AlphaUsage::DontCare,
+				wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC, label.as_deref()
+			));
+		}
+	}
 }
 
 /// A builder for [`Framebuffer`] instances.
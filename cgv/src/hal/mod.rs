@@ -17,9 +17,15 @@
 /// Submodule providing the [`Framebuffer`](texture::Framebuffer) facilities.
 mod framebuffer;
 // - re-exports
-pub use framebuffer::{Framebuffer, FramebufferBuilder, DynamicFramebuffer, DepthStencilFormat};
+pub use framebuffer::{Framebuffer, FramebufferBuilder, DynamicFramebuffer, DepthStencilFormat, ID_FORMAT};
 pub use framebuffer::{decodeDepth, decodeDepthU16, decodeDepthU32};
 
+/// Submodule providing facilities for reading back GPU buffer contents.
+mod readback;
+pub use readback::readbackBufferAsync; // re-export
+#[cfg(not(target_arch="wasm32"))]
+pub use readback::readbackBuffer; // re-export
+
 /// Submodule providing the [`ShaderProgram`](shaderprog::ShaderProgram) facilities.
 mod shaderprog;
 pub use shaderprog::ShaderProgram;
@@ -33,6 +39,16 @@ pub use texture::{
 	numMipLevels1D
 };
 
+/// Submodule providing the [`TextureBindGroup`](texturebinding::TextureBindGroup) facilities.
+mod texturebinding;
+pub use texturebinding::{
+	TextureBindGroup, SampledTexture, textureBindingType, descriptorBindingType, samplerBindingType
+}; // re-export
+
 /// Submodule providing the [`UniformGroup`](uniformgroup::UniformGroup) facilities.
 mod uniformgroup;
 pub use uniformgroup::UniformGroup; // re-export
+
+/// Submodule providing the [`VertexLayout`](vertexlayout::VertexLayout) facilities.
+mod vertexlayout;
+pub use vertexlayout::VertexLayout; // re-export (both the trait and the derive macro)
@@ -0,0 +1,113 @@
+//////
+//
+// Imports
+//
+
+// Standard library
+use std::ops::Range;
+
+// WGPU API
+use wgpu;
+
+// Local imports
+use crate::*;
+
+
+
+//////
+//
+// Functions
+//
+
+/// Copy the given range of the given buffer into a fresh staging buffer and map it for reading, invoking the callback
+/// with the mapped bytes once they become available. Empty ranges invoke the callback right away.
+fn stageAndMap<Closure: FnOnce(&[u8]) + wgpu::WasmNotSend + 'static> (
+	context: &Context, buffer: &wgpu::Buffer, range: Range<wgpu::BufferAddress>, callback: Closure
+){
+	// Sanity checks
+	assert!(
+		buffer.usage().contains(wgpu::BufferUsages::COPY_SRC),
+		"buffer must have been created with `COPY_SRC` usage to be read back"
+	);
+	let size = range.end - range.start;
+	assert!(
+		range.start % wgpu::COPY_BUFFER_ALIGNMENT == 0 && size % wgpu::COPY_BUFFER_ALIGNMENT == 0,
+		"readback range must be aligned to `wgpu::COPY_BUFFER_ALIGNMENT`"
+	);
+	if size == 0 {
+		callback(&[]);
+		return;
+	}
+
+	// Copy into a mappable staging buffer
+	let staging = context.device().create_buffer(&wgpu::BufferDescriptor {
+		label: Some("CGV__hal_readbackStagingBuffer"), size,
+		usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+		mapped_at_creation: false
+	});
+	let mut enc = context.device().create_command_encoder(
+		&wgpu::CommandEncoderDescriptor {label: Some("CGV__hal_readbackCommandEncoder")}
+	);
+	enc.copy_buffer_to_buffer(buffer, range.start, &staging, 0, size);
+	context.queue().submit(Some(enc.finish()));
+
+	// Map
+	let staging_cb = staging.clone();
+	staging.slice(..).map_async(wgpu::MapMode::Read, move |result| {
+		if result.is_ok() {
+			let view = staging_cb.slice(..).get_mapped_range();
+			callback(&view);
+			drop(view);
+			staging_cb.unmap();
+		}
+		else {
+			tracing::error!("readback staging buffer could not be mapped");
+		}
+	});
+}
+
+/// Read back the given range of the given buffer, interpreting its contents as a tightly packed array of `T`. Like
+/// [`Texture::readbackAsync`](hal::Texture::readbackAsync), the callback gets invoked once the GPU has finished copying
+/// the data. On native platforms, this happens the next time the device gets [polled](wgpu::Device::poll) or work is
+/// submitted to the queue (e.g. when the [`Player`] renders the next frame), on the web via the browser event loop.
+///
+/// # Arguments
+///
+/// * `context` – The graphics context.
+/// * `buffer` – The buffer to read from. Must have been created with [`wgpu::BufferUsages::COPY_SRC`].
+/// * `range` – The range of bytes to read. Must be aligned to [`wgpu::COPY_BUFFER_ALIGNMENT`] and a multiple of the
+///             size of `T`.
+/// * `callback` – The closure receiving the values.
+pub fn readbackBufferAsync<T: bytemuck::Pod, Closure: FnOnce(&[T]) + wgpu::WasmNotSend + 'static> (
+	context: &Context, buffer: &wgpu::Buffer, range: Range<wgpu::BufferAddress>, callback: Closure
+){
+	assert!(
+		(range.end - range.start) % size_of::<T>() as wgpu::BufferAddress == 0,
+		"readback range must be a multiple of the element size"
+	);
+	stageAndMap(context, buffer, range, move |bytes| match bytemuck::try_cast_slice(bytes) {
+		Ok(values) => callback(values),
+		// wgpu does not technically guarantee any alignment of mapped ranges, see
+		// https://github.com/gfx-rs/wgpu/issues/3508
+		Err(_) => callback(
+			&bytes.chunks_exact(size_of::<T>()).map(bytemuck::pod_read_unaligned).collect::<Vec<T>>()
+		)
+	});
+}
+
+/// Read back the given range of the given buffer, blocking until the data is available. See
+/// [`readbackBufferAsync`] for details on the arguments.
+///
+/// # Returns
+///
+/// The values read back, or an error if the device could not be polled or the data could not be mapped.
+#[cfg(not(target_arch="wasm32"))]
+pub fn readbackBuffer<T: bytemuck::Pod> (
+	context: &Context, buffer: &wgpu::Buffer, range: Range<wgpu::BufferAddress>
+) -> Result<Vec<T>>
+{
+	let (sender, receiver) = std::sync::mpsc::channel();
+	readbackBufferAsync(context, buffer, range, move |values: &[T]| { sender.send(values.to_vec()).ok(); });
+	context.device().poll(wgpu::PollType::wait_indefinitely())?;
+	receiver.recv().map_err(|_| anyhow!("readback buffer could not be mapped"))
+}
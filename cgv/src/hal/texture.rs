@@ -530,6 +530,143 @@ impl Texture
 			}
 		);
 	}
+
+	/// Read back the level-0 mipmap of a color texture into an image. Texels of 8-bit formats are provided as
+	/// [`image::DynamicImage::ImageRgba8`] (with *BGRA* formats swizzled accordingly), texels of floating point formats
+	/// as [`image::DynamicImage::ImageRgba32F`]. As with [`Self::readbackAsync`], the texture must have been created
+	/// with [`wgpu::TextureUsages::COPY_SRC`], and the callback gets invoked once the GPU has finished copying the
+	/// data.
+	pub fn readbackImageAsync<Closure: FnOnce(Result<image::DynamicImage>) + wgpu::WasmNotSend + 'static> (
+		&self, context: &Context, callback: Closure
+	){
+		// Sanity checks
+		use wgpu::TextureFormat::*;
+		let format = self.descriptor.format;
+		let Some(dst) = self.readbackView_buf() else {
+			return callback(Err(anyhow!("texture was not created with `COPY_SRC` usage")));
+		};
+		if !matches!(format, Rgba8Unorm | Rgba8UnormSrgb | Bgra8Unorm | Bgra8UnormSrgb | Rgba16Float | Rgba32Float) {
+			return callback(Err(anyhow!("readback into an image not implemented for texture format {format:?}")));
+		}
+
+		// Copy into the readback buffer
+		let dims = self.dimsWH();
+		let rowStride = dst.layout.bytes_per_row.unwrap() as usize;
+		let rowBytes = dims.x as usize * numBytesFromFormat(format);
+		let mut enc = context.device().create_command_encoder(
+			&wgpu::CommandEncoderDescriptor {label: Some("CGV__hal_readbackImageCommandEncoder")}
+		);
+		enc.copy_texture_to_buffer(
+			wgpu::TexelCopyTextureInfo {
+				texture: &self.texture, mip_level: 0, origin: Default::default(), aspect: wgpu::TextureAspect::All
+			},
+			dst, wgpu::Extent3d {depth_or_array_layers: 1, ..self.descriptor.size}
+		);
+		context.queue().submit(Some(enc.finish()));
+
+		// Map and convert
+		let buf = self.readbackBuffer.as_ref().unwrap();
+		let buf_cb = buf.clone();
+		buf.slice(..).map_async(wgpu::MapMode::Read, move |result| {
+			if result.is_err() {
+				return callback(Err(anyhow!("readback buffer could not be mapped")));
+			}
+			let bufView = buf_cb.slice(..).get_mapped_range();
+			let rows = || bufView.chunks(rowStride).take(dims.y as usize).map(|row| &row[..rowBytes]);
+			let image = match format {
+				Rgba8Unorm | Rgba8UnormSrgb => image::DynamicImage::ImageRgba8(image::RgbaImage::from_raw(
+					dims.x, dims.y, rows().flatten().copied().collect()
+				).unwrap()),
+				Bgra8Unorm | Bgra8UnormSrgb => image::DynamicImage::ImageRgba8(image::RgbaImage::from_raw(
+					dims.x, dims.y, rows().flat_map(|row| row.chunks_exact(4).flat_map(|t| [t[2], t[1], t[0], t[3]]))
+						.collect()
+				).unwrap()),
+				Rgba16Float => image::DynamicImage::ImageRgba32F(image::Rgba32FImage::from_raw(
+					dims.x, dims.y, rows().flat_map(|row| row.chunks_exact(2).map(
+						|h| half::f16::from_ne_bytes([h[0], h[1]]).to_f32()
+					)).collect()
+				).unwrap()),
+				_ /* Rgba32Float */ => image::DynamicImage::ImageRgba32F(image::Rgba32FImage::from_raw(
+					dims.x, dims.y, rows().flat_map(|row| row.chunks_exact(4).map(
+						|f| f32::from_ne_bytes([f[0], f[1], f[2], f[3]])
+					)).collect()
+				).unwrap())
+			};
+			drop(bufView);
+			buf_cb.unmap();
+			callback(Ok(image));
+		});
+	}
+
+	/// Read back a single texel of the level-0 mipmap, interpreting its bytes as a `T` (which must have the same size
+	/// as a texel of the texture format). Unlike the other readback methods, this uses a fresh staging buffer, so any
+	/// number of texel readbacks may be in flight at the same time. The texture must have been created with
+	/// [`wgpu::TextureUsages::COPY_SRC`].
+	///
+	/// # Arguments
+	///
+	/// * `context` – The graphics context.
+	/// * `coords` – The coordinates of the texel to read back. Must lie within the texture.
+	/// * `callback` – The closure receiving the texel once the GPU has finished copying it, or `None` if the data
+	///                could not be mapped.
+	pub fn readbackTexelAsync<T: bytemuck::Pod, Closure: FnOnce(Option<T>) + wgpu::WasmNotSend + 'static> (
+		&self, context: &Context, coords: glm::UVec2, callback: Closure
+	){
+		// Sanity checks
+		assert!(
+			self.descriptor.usage.contains(wgpu::TextureUsages::COPY_SRC),
+			"texture must have been created with `COPY_SRC` usage to be read back"
+		);
+		assert_eq!(
+			size_of::<T>(), numBytesFromFormat(self.descriptor.format), "texel type does not match the texture format"
+		);
+		let dims = self.dimsWH();
+		assert!(coords.x < dims.x && coords.y < dims.y, "texel coordinates {coords:?} out of bounds");
+
+		// Copy the texel into a mappable staging buffer
+		let staging = context.device().create_buffer(&wgpu::BufferDescriptor {
+			label: Some("CGV__hal_texelReadbackBuffer"), size: wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as u64,
+			usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+			mapped_at_creation: false
+		});
+		let mut enc = context.device().create_command_encoder(
+			&wgpu::CommandEncoderDescriptor {label: Some("CGV__hal_texelReadbackCommandEncoder")}
+		);
+		enc.copy_texture_to_buffer(
+			wgpu::TexelCopyTextureInfo {
+				texture: &self.texture, mip_level: 0, origin: wgpu::Origin3d {x: coords.x, y: coords.y, z: 0},
+				aspect: wgpu::TextureAspect::All
+			},
+			wgpu::TexelCopyBufferInfo {buffer: &staging, layout: Default::default()},
+			wgpu::Extent3d {width: 1, height: 1, depth_or_array_layers: 1}
+		);
+		context.queue().submit(Some(enc.finish()));
+
+		// Map
+		let staging_cb = staging.clone();
+		staging.slice(..).map_async(wgpu::MapMode::Read, move |result| {
+			if result.is_err() {
+				tracing::error!("texel readback buffer could not be mapped");
+				return callback(None);
+			}
+			let view = staging_cb.slice(..).get_mapped_range();
+			let texel = bytemuck::pod_read_unaligned(&view[..size_of::<T>()]);
+			drop(view);
+			staging_cb.unmap();
+			callback(Some(texel));
+		});
+	}
+
+	/// Read back the level-0 mipmap of a color texture into an image, blocking until the data is available. See
+	/// [`Self::readbackImageAsync`] for details.
+	#[cfg(not(target_arch="wasm32"))]
+	pub fn readbackImage (&self, context: &Context) -> Result<image::DynamicImage>
+	{
+		let (sender, receiver) = std::sync::mpsc::channel();
+		self.readbackImageAsync(context, move |image| { sender.send(image).ok(); });
+		context.device().poll(wgpu::PollType::wait_indefinitely())?;
+		receiver.recv().map_err(|_| anyhow!("texture readback did not complete"))?
+	}
 }
 
 
@@ -559,6 +696,10 @@ pub fn numBytesFromFormat (format: wgpu::TextureFormat) -> usize
 		Rgba8Unorm | Rgba8UnormSrgb | Bgra8Unorm | Bgra8UnormSrgb | Depth24PlusStencil8 | Depth32Float
 		=> 4,
 
+		Rgba16Float | Rg32Uint => 8,
+
+		Rgba32Float => 16,
+
 		_ => panic!("Unsupported or unimplemented texture format: {:?}", format)
 	}
 }
@@ -569,10 +710,10 @@ pub fn hasAlpha (format: wgpu::TextureFormat) -> bool
 	use wgpu::TextureFormat::*;
 	match format
 	{
-		Depth16Unorm | Depth24Plus | Depth24PlusStencil8 | Depth32Float | Depth32FloatStencil8
+		Depth16Unorm | Depth24Plus | Depth24PlusStencil8 | Depth32Float | Depth32FloatStencil8 | Rg32Uint
 		=> false,
 
-		Rgba8Unorm | Rgba8UnormSrgb | Bgra8Unorm | Bgra8UnormSrgb
+		Rgba8Unorm | Rgba8UnormSrgb | Bgra8Unorm | Bgra8UnormSrgb | Rgba16Float | Rgba32Float
 		=> true,
 
 		_ => panic!("Unsupported or unimplemented texture format: {:?}", format)
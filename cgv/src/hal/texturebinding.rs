@@ -0,0 +1,299 @@
+//////
+//
+// Imports
+//
+
+// Standard library
+/* nothing here yet */
+
+// WGPU API
+use crate::wgpu;
+
+// Local imports
+use crate::*;
+
+
+
+//////
+//
+// Structs
+//
+
+/// Describes a texture that should be bound for sampling, optionally accompanied by a sampler that will be bound to the
+/// slot directly following it.
+#[derive(Clone)]
+pub struct SampledTexture<'a>
+{
+	/// The texture to sample from.
+	pub texture: &'a hal::Texture,
+
+	/// The configuration of the sampler to bind together with the texture, if any. The actual sampler object will be
+	/// obtained through [`Context::refSampler`].
+	pub sampler: Option<wgpu::SamplerDescriptor<'a>>
+}
+impl<'a> SampledTexture<'a>
+{
+	/// Bind the given texture together with a sampler of the given configuration.
+	#[inline(always)]
+	pub fn withSampler (texture: &'a hal::Texture, sampler: wgpu::SamplerDescriptor<'a>) -> Self {
+		Self { texture, sampler: Some(sampler) }
+	}
+
+	/// Bind just the given texture without any sampler.
+	#[inline(always)]
+	pub fn textureOnly (texture: &'a hal::Texture) -> Self {
+		Self { texture, sampler: None }
+	}
+}
+
+
+
+//////
+//
+// Classes
+//
+
+/// A bind group (and its layout) for sampling from one or more [textures](hal::Texture) in a shader. The binding
+/// layout is inferred solely from the textures themselves: view dimension, sample type and multisampling are taken from
+/// the texture descriptors, and the type of each sampler binding is chosen to match the provided sampler configuration.
+///
+/// Bindings are assigned in order, with every texture occupying the next free slot, and its sampler (if any) occupying
+/// the slot directly following it. Binding a texture and a sampler thus results in the shader-side layout
+/// `binding 0: texture, binding 1: sampler`.
+#[derive(Debug)]
+pub struct TextureBindGroup
+{
+	/// The debug label of the bind group, if any.
+	label: Option<String>,
+
+	/// The inferred layout entries, kept for checking compatibility when [rebinding](TextureBindGroup::rebind).
+	entries: Vec<wgpu::BindGroupLayoutEntry>,
+
+	/// The samplers in use (if any) for each texture.
+	samplers: Vec<Option<wgpu::Sampler>>,
+
+	/// The layout of the bind group.
+	pub bindGroupLayout: wgpu::BindGroupLayout,
+
+	/// The bind group itself.
+	pub bindGroup: wgpu::BindGroup
+}
+impl TextureBindGroup
+{
+	/// Create the bind group for sampling the given textures.
+	///
+	/// # Arguments
+	///
+	/// * `context` – The *CGV-rs* context under which to create the bind group.
+	/// * `visibility` – The shader stages the textures and samplers should be visible to.
+	/// * `textures` – The list of textures (and their optional samplers) to bind.
+	/// * `label` – The debug label to attach to the bind group and its layout.
+	///
+	/// # Returns
+	///
+	/// The bind group and its matching layout, or an error if any of the textures cannot be bound for sampling.
+	pub fn create (
+		context: &Context, visibility: wgpu::ShaderStages, textures: &[SampledTexture], label: Option<&str>
+	) -> Result<Self>
+	{
+		// Infer layout
+		let mut entries = Vec::with_capacity(textures.len()*2);
+		let mut samplers = Vec::with_capacity(textures.len());
+		for binding in textures
+		{
+			entries.push(wgpu::BindGroupLayoutEntry {
+				binding: entries.len() as u32, visibility,
+				ty: textureBindingType(context, binding.texture).ok_or_else(|| anyhow!(
+					"texture format {:?} cannot be bound for sampling", binding.texture.descriptor.format
+				))?,
+				count: None
+			});
+			if let Some(samplerDesc) = &binding.sampler {
+				entries.push(wgpu::BindGroupLayoutEntry {
+					binding: entries.len() as u32, visibility,
+					ty: wgpu::BindingType::Sampler(samplerBindingType(samplerDesc)),
+					count: None
+				});
+				samplers.push(Some(context.refSampler(samplerDesc)));
+			}
+			else {
+				samplers.push(None);
+			}
+		}
+
+		// Create device objects
+		let bindGroupLayout = context.device().create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+			label: util::concatIfSome(&label, "_bindGroupLayout").as_deref(),
+			entries: &entries
+		});
+		let label = label.map(String::from);
+		let bindGroup = Self::createBindGroup(
+			context, &bindGroupLayout, textures.iter().map(|b| b.texture), &samplers, label.as_deref()
+		);
+
+		// Done!
+		Ok(Self { label, entries, samplers, bindGroupLayout, bindGroup })
+	}
+
+	/// Re-create the bind group for a new set of textures, keeping the layout and samplers. The new textures must be
+	/// compatible with the ones the bind group was originally created for, i.e. result in the same inferred binding
+	/// types.
+	///
+	/// # Arguments
+	///
+	/// * `context` – The *CGV-rs* context under which to create the new bind group.
+	/// * `textures` – The new textures, in the same order as the ones provided to [`TextureBindGroup::create`].
+	pub fn rebind (&mut self, context: &Context, textures: &[&hal::Texture])
+	{
+		assert_eq!(
+			textures.len(), self.samplers.len(), "Attempted to rebind texture bind group with a different number \
+			of textures"
+		);
+		debug_assert!(
+			textures.iter().zip(self.textureEntries()).all(|(tex, entry)|
+				textureBindingType(context, tex) == Some(entry.ty)
+			),
+			"Attempted to rebind texture bind group with incompatible textures"
+		);
+		self.bindGroup = Self::createBindGroup(
+			context, &self.bindGroupLayout, textures.iter().copied(), &self.samplers, self.label.as_deref()
+		);
+	}
+
+	/// Iterate over the layout entries of just the textures (i.e. skipping the samplers).
+	fn textureEntries (&self) -> impl Iterator<Item=&wgpu::BindGroupLayoutEntry> {
+		self.entries.iter().filter(|entry| matches!(entry.ty, wgpu::BindingType::Texture{..}))
+	}
+
+	/// Create the actual bind group, following the slot assignment scheme described in the type docs.
+	fn createBindGroup<'tex> (
+		context: &Context, layout: &wgpu::BindGroupLayout, textures: impl Iterator<Item=&'tex hal::Texture>,
+		samplers: &[Option<wgpu::Sampler>], label: Option<&str>
+	) -> wgpu::BindGroup
+	{
+		// Depth/stencil textures need dedicated depth-only views for sampling
+		let views: Vec<_> = textures.map(samplingView).collect();
+
+		// Assign slots
+		let mut entries = Vec::with_capacity(views.len()*2);
+		for (view, sampler) in views.iter().zip(samplers)
+		{
+			entries.push(wgpu::BindGroupEntry {
+				binding: entries.len() as u32, resource: wgpu::BindingResource::TextureView(view)
+			});
+			if let Some(sampler) = sampler {
+				entries.push(wgpu::BindGroupEntry {
+					binding: entries.len() as u32, resource: wgpu::BindingResource::Sampler(sampler)
+				});
+			}
+		}
+
+		// Create
+		context.device().create_bind_group(&wgpu::BindGroupDescriptor {
+			label: util::concatIfSome(&label, "_bindGroup").as_deref(), layout, entries: &entries
+		})
+	}
+}
+
+
+
+//////
+//
+// Functions
+//
+
+/// Determine the aspect of a texture with the given descriptor that is visible when sampling from it in a shader.
+fn samplingAspect (desc: &wgpu::TextureDescriptor) -> wgpu::TextureAspect {
+	if desc.format.is_combined_depth_stencil_format() {
+		wgpu::TextureAspect::DepthOnly
+	} else {
+		wgpu::TextureAspect::All
+	}
+}
+
+/// Determine the view dimension of the main view of a texture with the given descriptor.
+fn samplingViewDimension (desc: &wgpu::TextureDescriptor) -> wgpu::TextureViewDimension {
+	match desc.dimension {
+		wgpu::TextureDimension::D2 if desc.size.depth_or_array_layers > 1
+			=> wgpu::TextureViewDimension::D2Array,
+		dimension => hal::texture::textureViewDimensionsEquiv(dimension)
+	}
+}
+
+/// Obtain a view on the given texture suitable for binding it for sampling.
+fn samplingView (texture: &hal::Texture) -> wgpu::TextureView {
+	match samplingAspect(&texture.descriptor) {
+		wgpu::TextureAspect::All => texture.view.clone(),
+		aspect => texture.texture.create_view(&wgpu::TextureViewDescriptor {
+			aspect, dimension: Some(samplingViewDimension(&texture.descriptor)), ..Default::default()
+		})
+	}
+}
+
+/// Infer the binding type for sampling from the given texture.
+///
+/// # Arguments
+///
+/// * `context` – The *CGV-rs* context, needed to query device features that influence filterability.
+/// * `texture` – The texture to infer the binding type for.
+///
+/// # Returns
+///
+/// The [`wgpu::BindingType::Texture`] matching the texture's format, dimensions and multisampling, or `None` if the
+/// texture format cannot be sampled from.
+#[inline]
+pub fn textureBindingType (context: &Context, texture: &hal::Texture) -> Option<wgpu::BindingType> {
+	descriptorBindingType(&texture.descriptor, context.device().features())
+}
+
+/// Infer the binding type for sampling from a texture with the given descriptor.
+///
+/// # Arguments
+///
+/// * `desc` – The descriptor of the texture to infer the binding type for.
+/// * `features` – The enabled device features, which influence filterability of some formats.
+///
+/// # Returns
+///
+/// The [`wgpu::BindingType::Texture`] matching the format, dimensions and multisampling described by the descriptor,
+/// or `None` if the texture format cannot be sampled from.
+pub fn descriptorBindingType (desc: &wgpu::TextureDescriptor, features: wgpu::Features) -> Option<wgpu::BindingType>
+{
+	let sampleType = desc.format.sample_type(Some(samplingAspect(desc)), Some(features))?;
+	Some(wgpu::BindingType::Texture {
+		multisampled: desc.sample_count > 1,
+		view_dimension: samplingViewDimension(desc),
+		// Multisampled float textures can never be filtered
+		sample_type: match sampleType {
+			wgpu::TextureSampleType::Float{..} if desc.sample_count > 1
+				=> wgpu::TextureSampleType::Float { filterable: false },
+			other => other
+		}
+	})
+}
+
+/// Infer the sampler binding type matching the given sampler configuration.
+///
+/// # Arguments
+///
+/// * `desc` – The sampler configuration.
+///
+/// # Returns
+///
+/// [`wgpu::SamplerBindingType::Comparison`] for samplers with a compare function,
+/// [`wgpu::SamplerBindingType::Filtering`] for samplers using linear filtering of any kind, and
+/// [`wgpu::SamplerBindingType::NonFiltering`] otherwise.
+pub fn samplerBindingType (desc: &wgpu::SamplerDescriptor) -> wgpu::SamplerBindingType
+{
+	if desc.compare.is_some() {
+		wgpu::SamplerBindingType::Comparison
+	}
+	else if desc.mag_filter == wgpu::FilterMode::Linear || desc.min_filter == wgpu::FilterMode::Linear
+		|| desc.mipmap_filter == wgpu::MipmapFilterMode::Linear
+	{
+		wgpu::SamplerBindingType::Filtering
+	}
+	else {
+		wgpu::SamplerBindingType::NonFiltering
+	}
+}
@@ -0,0 +1,74 @@
+//////
+//
+// Imports
+//
+
+// Standard library
+/* nothing here yet */
+
+// WGPU API
+use crate::wgpu;
+
+// Local imports
+pub use cgv_derive::VertexLayout; // re-export our related procedural derive macro from cgv-derive
+
+
+
+//////
+//
+// Traits
+//
+
+/// A vertex struct that knows the [`wgpu::VertexBufferLayout`] describing a buffer containing a tightly packed array of
+/// its instances. Usually implemented via `#[derive(VertexLayout)]`, which maps fields of *glm* vector/matrix types,
+/// primitives and arrays of primitives to their matching [`wgpu::VertexFormat`]s, with offsets determined by the
+/// compiler.
+///
+/// The derive macro understands the following attributes:
+///
+/// * `#[cgv_vertexLayout(instance)]` on the struct – advance the attributes per instance instead of per vertex.
+/// * `#[cgv_vertexAttr(location=N)]` on a field – bind the field to shader location `N`. Fields without an explicit
+///   location continue counting from the previous field (starting at `0`). Matrix fields occupy one location per
+///   column.
+/// * `#[cgv_vertexAttr(normalized)]` on a field – use the normalized (`Unorm`/`Snorm`) format for 8- and 16-bit
+///   integer fields.
+/// * `#[cgv_vertexAttr(format=<VertexFormat variant>)]` on a field – override the automatically inferred format.
+/// * `#[cgv_vertexAttr(skip)]` on a field – exclude the field (e.g. explicit padding) from the layout.
+///
+/// # Example
+///
+/// ```rust
+/// # use cgv::{glm, hal::VertexLayout};
+/// #[repr(C)]
+/// #[derive(Clone, Copy, VertexLayout)]
+/// struct Vertex {
+///     pos: glm::Vec4,
+///     #[cgv_vertexAttr(normalized)] color: [u8; 4],
+///     #[cgv_vertexAttr(location=3)] texcoord: glm::Vec2,
+///     #[cgv_vertexAttr(skip)] pad: u32
+/// }
+/// assert_eq!(Vertex::ATTRIBUTES[2].shader_location, 3);
+/// assert_eq!(Vertex::layoutDesc().array_stride, 32);
+/// ```
+pub trait VertexLayout: Sized
+{
+	/// The vertex attributes making up the layout, in field order.
+	const ATTRIBUTES: &'static [wgpu::VertexAttribute];
+
+	/// Whether the attributes advance per vertex or per instance.
+	const STEP_MODE: wgpu::VertexStepMode;
+
+	/// Produce the layout descriptor for a *WGPU* vertex buffer storing a tightly packed array of `Self`.
+	///
+	/// # Returns
+	///
+	/// A [`wgpu::VertexBufferLayout`] suitable for e.g. [`wgpu::VertexState::buffers`].
+	#[inline(always)]
+	fn layoutDesc () -> wgpu::VertexBufferLayout<'static> {
+		wgpu::VertexBufferLayout {
+			array_stride: size_of::<Self>() as wgpu::BufferAddress,
+			step_mode: Self::STEP_MODE,
+			attributes: Self::ATTRIBUTES,
+		}
+	}
+}
@@ -49,6 +49,10 @@ pub mod gui;
 /// The module providing functionality related to data handling
 pub mod data;
 
+/// The module providing image-based regression testing of rendering output.
+#[cfg(not(target_arch="wasm32"))]
+pub mod regression;
+
 /// Re-export cgv-shader
 pub use cgv_shader as shader;
 
@@ -379,6 +383,16 @@ pub trait Application: Component
 	/// * `player` – The global *CGV-rs* [`Player`] instance.
 	#[expect(unused_variables)]
 	fn freeUi (&mut self, ui: &mut egui::Ui, ps: &mut Player) {}
+
+	/// Report an axis-aligned bounding box of the scene content the application renders, if known. The [player](Player)
+	/// uses it to place the initial view when the application starts (unless the application set up the camera itself
+	/// during initialization), for [zooming to fit](Player::zoomToFit) and for [adapting the clipping
+	/// planes](Player::setAutoClippingPlanes) to maximize depth precision. Called at least once per frame, so
+	/// implementations should return cached bounds, e.g. as obtained from
+	/// [`host::Data::bounds`](renderer::data::host::Data::bounds). Can be left unimplemented when not needed.
+	fn sceneBounds (&self) -> Option<util::math::Aabb> {
+		None
+	}
 }
 
 
@@ -421,6 +435,16 @@ where
 // Functions
 //
 
+/// Reference the persistent [shader cache](shader::cache::Cache) *CGV-rs* uses for packages it compiles at runtime,
+/// located at the [default location](shader::cache::Cache::inDefaultLocation) for the current platform.
+#[cfg(feature="slang_runtime")]
+pub fn obtainShaderCache () -> &'static shader::cache::Cache {
+	static SHADER_CACHE: std::sync::LazyLock<shader::cache::Cache> = std::sync::LazyLock::new(
+		shader::cache::Cache::inDefaultLocation
+	);
+	&SHADER_CACHE
+}
+
 ///
 #[cfg(feature="slang_runtime")]
 pub fn obtainShaderCompileEnvironment () -> shader::compile::Environment<shader::slang::EnvModule>
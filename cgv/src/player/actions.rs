@@ -0,0 +1,172 @@
+//////
+//
+// Imports
+//
+
+// Standard library
+/* nothing here yet */
+
+// Local imports
+use crate::*;
+use util::math;
+use super::{InputSource, Bindings, Command};
+
+
+
+//////
+//
+// Enums
+//
+
+/// High-level, modality-agnostic interaction intents that the [`Player`] derives from raw [input events](InputEvent),
+/// delivered to applications and camera interactors as [`InputEvent::Action`]. Consuming actions instead of raw events
+/// makes sure interaction works the same regardless of whether the user is on a mouse, trackpad or touchscreen.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Action
+{
+	/// Zoom by the given factor, with values greater than *1* zooming in.
+	Zoom(f32),
+
+	/// Change the field of view by the given angle, in radians.
+	FieldOfView(f32),
+
+	/// Move the view parallel to the image plane, following a pointer motion in the given direction. The amount is in
+	/// logical screen points, receivers are expected to apply their own sensitivity.
+	Pan(glm::Vec2),
+
+	/// Move the view towards or away from the focus, equivalent to a vertical pointer motion of the given amount of
+	/// logical screen points. Receivers are expected to apply their own sensitivity.
+	Dolly(f32),
+
+	/// Rotate the view, following a pointer motion in the given direction. The amount is in logical screen points,
+	/// receivers are expected to apply their own sensitivity.
+	Rotate(glm::Vec2),
+
+	/// Roll the view around the viewing direction by the given angle in radians, with positive values rotating the
+	/// scene clockwise on screen.
+	Roll(f32),
+
+	/// Roll the view around the viewing direction, following a vertical pointer motion of the given amount of logical
+	/// screen points. Receivers are expected to apply their own sensitivity, e.g. via [`Action::rollAngle`].
+	DragRoll(f32),
+
+	/// Focus the view on whatever is visible at the given pixel coordinates within the main viewport.
+	Focus(glm::UVec2)
+}
+
+
+
+impl Action
+{
+	/// The roll angle in radians implied by the action, if it is [`Action::Roll`] or [`Action::DragRoll`].
+	///
+	/// # Arguments
+	///
+	/// * `degreesPerPoint` – The roll angle, in degrees, that a [drag](Action::DragRoll) of one logical screen point
+	///   corresponds to.
+	///
+	/// # Returns
+	///
+	/// The angle with the same orientation as [`Action::Roll`], or *0* for any other action.
+	pub fn rollAngle (&self, degreesPerPoint: f32) -> f32 {
+		match self {
+			Self::Roll(angle) => *angle,
+			Self::DragRoll(amount) => math::deg2rad!(amount*degreesPerPoint),
+			_ => 0.
+		}
+	}
+}
+
+
+
+//////
+//
+// Structs
+//
+
+/// Struct containing information about an [interaction action](Action).
+#[derive(Debug, Clone, Copy)]
+pub struct ActionInfo
+{
+	/// The action to perform.
+	pub action: Action,
+
+	/// The key modifiers that were pressed when the underlying input occurred.
+	pub modifiers: egui::Modifiers,
+
+	/// The input modality the action originates from, for receivers that want to adapt to it after all.
+	pub source: InputSource
+}
+impl ActionInfo
+{
+	/// The mouse wheel amount, in logical screen points, that corresponds to zooming by a factor of *e*.
+	const WHEEL_ZOOM_SCALE: f32 = 256.;
+
+	/// The field of view change, in degrees, that one logical screen point of mouse wheel movement corresponds to.
+	const WHEEL_FOV_DEGREES: f32 = 0.125;
+
+	/// Derive the actions implied by the given raw input event. Pointer drags and mouse wheel modifiers are mapped
+	/// according to the given [bindings](Bindings) – with the default bindings, the mapping is as follows:
+	///
+	/// | Input                                  | Action                  |
+	/// |----------------------------------------|-------------------------|
+	/// | primary drag / one-finger swipe        | [`Action::Rotate`]      |
+	/// | *Shift* + primary drag                 | [`Action::DragRoll`]    |
+	/// | secondary drag / two-finger swipe      | [`Action::Pan`]         |
+	/// | middle drag                            | [`Action::Dolly`]       |
+	/// | mouse wheel / pinch                    | [`Action::Zoom`]        |
+	/// | *Alt* + mouse wheel                    | [`Action::FieldOfView`] |
+	/// | two-finger rotation                    | [`Action::Roll`]        |
+	/// | double click / double tap              | [`Action::Focus`]       |
+	///
+	/// Events that imply no action result in an empty list.
+	pub fn fromInputEvent (event: &InputEvent, bindings: &Bindings) -> Vec<Self>
+	{
+		match event
+		{
+			InputEvent::Dragged(info) => {
+				const DRAG_COMMANDS: [Command; 4] = [Command::Rotate, Command::Roll, Command::Pan, Command::Dolly];
+				let buttons = [
+					egui::PointerButton::Primary, egui::PointerButton::Secondary, egui::PointerButton::Middle,
+					egui::PointerButton::Extra1, egui::PointerButton::Extra2
+				];
+				buttons.into_iter().filter(|&button| info.button(button)).filter_map(|button| {
+					let action = match bindings.dragCommand(&DRAG_COMMANDS, button, &info.modifiers)? {
+						Command::Rotate => Action::Rotate(info.direction),
+						Command::Roll => Action::DragRoll(info.direction.y),
+						Command::Pan => Action::Pan(info.direction),
+						_ => Action::Dolly(info.direction.y)
+					};
+					Some(Self { action, modifiers: info.modifiers, source: info.source })
+				}).collect()
+			},
+
+			InputEvent::MouseWheel(info) if info.amount.y != 0. => vec![Self {
+				action: if bindings.modifiersHeld(Command::FieldOfView, &info.modifiers) {
+					Action::FieldOfView(math::deg2rad!(info.amount.y*Self::WHEEL_FOV_DEGREES))
+				} else {
+					Action::Zoom((info.amount.y/Self::WHEEL_ZOOM_SCALE).exp())
+				},
+				modifiers: info.modifiers, source: InputSource::Mouse
+			}],
+
+			InputEvent::Pinch(info) if info.zoom != 1. => vec![Self {
+				action: Action::Zoom(info.zoom), modifiers: info.modifiers, source: info.source
+			}],
+
+			InputEvent::Rotate(info) if info.angle != 0. => vec![Self {
+				action: Action::Roll(info.angle), modifiers: info.modifiers, source: InputSource::Touch
+			}],
+
+			InputEvent::MultiTouch(info) if info.translation != glm::Vec2::zeros() => vec![Self {
+				action: Action::Pan(info.translation), modifiers: info.modifiers, source: InputSource::Touch
+			}],
+
+			InputEvent::DoubleClick(info) => vec![Self {
+				action: Action::Focus(info.position), modifiers: info.modifiers, source: info.source
+			}],
+
+			_ => Vec::new()
+		}
+	}
+}
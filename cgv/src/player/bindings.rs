@@ -0,0 +1,339 @@
+//////
+//
+// Imports
+//
+
+// Standard library
+use std::collections::{BTreeMap, BTreeSet};
+
+// Serde library
+use serde;
+
+// Local imports
+use crate::*;
+
+
+
+//////
+//
+// Enums
+//
+
+/// Enumeration of all commands that can be bound to [input chords](Chord).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize)]
+pub enum Command
+{
+	/// Quit the [`Player`].
+	Quit,
+
+	/// Drag for [rotating](player::Action::Rotate) the view.
+	Rotate,
+
+	/// Drag for [rolling](player::Action::DragRoll) the view.
+	Roll,
+
+	/// Drag for [panning](player::Action::Pan) the view.
+	Pan,
+
+	/// Drag for [dollying](player::Action::Dolly) the view.
+	Dolly,
+
+	/// Modifiers turning mouse wheel zooming into [field of view](player::Action::FieldOfView) changes.
+	FieldOfView,
+
+	/// Key for moving forward in first-person navigation.
+	MoveForward,
+
+	/// Key for moving left in first-person navigation.
+	MoveLeft,
+
+	/// Key for moving backward in first-person navigation.
+	MoveBack,
+
+	/// Key for moving right in first-person navigation.
+	MoveRight,
+
+	/// Key for moving down in first-person navigation.
+	MoveDown,
+
+	/// Key for moving up in first-person navigation.
+	MoveUp,
+
+	/// Modifiers slowing down first-person navigation while held.
+	MoveSlowly,
+
+	/// Key for viewing the focus point from the front, or from the back when pressed again.
+	ViewFront,
+
+	/// Key for viewing the focus point from the right side, or from the left side when pressed again.
+	ViewSide,
+
+	/// Key for viewing the focus point from the top, or from the bottom when pressed again.
+	ViewTop,
+
+	/// Key for [zooming to fit](Player::zoomToFit) the whole scene into view.
+	ZoomToFit
+}
+impl Command
+{
+	/// All commands, in the order they are listed in the GUI.
+	pub const ALL: [Command; 17] = [
+		Self::Quit, Self::Rotate, Self::Roll, Self::Pan, Self::Dolly, Self::FieldOfView, Self::MoveForward,
+		Self::MoveLeft, Self::MoveBack, Self::MoveRight, Self::MoveDown, Self::MoveUp, Self::MoveSlowly,
+		Self::ViewFront, Self::ViewSide, Self::ViewTop, Self::ZoomToFit
+	];
+
+	/// A short, human-readable name for the command.
+	pub fn title (&self) -> &'static str {
+		match self {
+			Self::Quit => "quit",
+			Self::Rotate => "rotate",
+			Self::Roll => "roll",
+			Self::Pan => "pan",
+			Self::Dolly => "dolly",
+			Self::FieldOfView => "field of view",
+			Self::MoveForward => "move forward",
+			Self::MoveLeft => "move left",
+			Self::MoveBack => "move back",
+			Self::MoveRight => "move right",
+			Self::MoveDown => "move down",
+			Self::MoveUp => "move up",
+			Self::MoveSlowly => "move slowly",
+			Self::ViewFront => "view front",
+			Self::ViewSide => "view side",
+			Self::ViewTop => "view top",
+			Self::ZoomToFit => "zoom to fit"
+		}
+	}
+
+	/// The chord the command is bound to by default. The first-person movement and view keys are bound by physical
+	/// location, so they stay in the familiar *WASD* and number row arrangements on any keyboard layout.
+	pub fn defaultChord (&self) -> Chord
+	{
+		use egui::{Key, Modifiers, PointerButton};
+		match self {
+			Self::Quit => Chord::new(Trigger::Key(Key::Escape), Modifiers::NONE),
+			Self::Rotate => Chord::new(Trigger::Pointer(PointerButton::Primary), Modifiers::NONE),
+			Self::Roll => Chord::new(Trigger::Pointer(PointerButton::Primary), Modifiers::SHIFT),
+			Self::Pan => Chord::new(Trigger::Pointer(PointerButton::Secondary), Modifiers::NONE),
+			Self::Dolly => Chord::new(Trigger::Pointer(PointerButton::Middle), Modifiers::NONE),
+			Self::FieldOfView => Chord::new(Trigger::ModifiersOnly, Modifiers::ALT),
+			Self::MoveForward => Chord::new(Trigger::PhysicalKey(Key::W), Modifiers::NONE),
+			Self::MoveLeft => Chord::new(Trigger::PhysicalKey(Key::A), Modifiers::NONE),
+			Self::MoveBack => Chord::new(Trigger::PhysicalKey(Key::S), Modifiers::NONE),
+			Self::MoveRight => Chord::new(Trigger::PhysicalKey(Key::D), Modifiers::NONE),
+			Self::MoveDown => Chord::new(Trigger::PhysicalKey(Key::Q), Modifiers::NONE),
+			Self::MoveUp => Chord::new(Trigger::PhysicalKey(Key::E), Modifiers::NONE),
+			Self::MoveSlowly => Chord::new(Trigger::ModifiersOnly, Modifiers::SHIFT),
+			Self::ViewFront => Chord::new(Trigger::PhysicalKey(Key::Num1), Modifiers::NONE),
+			Self::ViewSide => Chord::new(Trigger::PhysicalKey(Key::Num3), Modifiers::NONE),
+			Self::ViewTop => Chord::new(Trigger::PhysicalKey(Key::Num7), Modifiers::NONE),
+			Self::ZoomToFit => Chord::new(Trigger::Key(Key::Home), Modifiers::NONE)
+		}
+	}
+}
+
+/// The non-modifier part of a [`Chord`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum Trigger
+{
+	/// A key identified by the symbol it produces in the active keyboard layout.
+	Key(egui::Key),
+
+	/// A key identified by its physical location, named after the key at that location on a US *QWERTY* layout.
+	PhysicalKey(egui::Key),
+
+	/// A pointer button.
+	Pointer(egui::PointerButton),
+
+	/// No trigger at all, only the modifiers of the chord have to be held.
+	ModifiersOnly
+}
+
+
+
+//////
+//
+// Structs
+//
+
+/// A combination of a [`Trigger`] and the key modifiers that have to be held along with it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Chord
+{
+	/// What triggers the chord.
+	pub trigger: Trigger,
+
+	/// The modifiers that have to be held.
+	pub modifiers: egui::Modifiers
+}
+impl Chord
+{
+	/// Construct a chord from the given components.
+	#[inline(always)]
+	pub const fn new (trigger: Trigger, modifiers: egui::Modifiers) -> Self {
+		Self { trigger, modifiers }
+	}
+
+	/// Check if all modifiers of the chord are among the given ones. Additional modifiers are allowed, so that e.g.
+	/// holding the [slow movement](Command::MoveSlowly) modifier does not prevent movement keys from matching.
+	#[inline]
+	pub fn modifiersHeld (&self, modifiers: &egui::Modifiers) -> bool {
+		modifiers.contains(self.modifiers)
+	}
+
+	/// Check if the chord is triggered by the given key, identified either by its logical key code or its physical
+	/// location, and the given modifiers. Pass `None` for the modifiers to only check the key, e.g. for key releases.
+	pub fn matchesKey (&self, key: egui::Key, physicalKey: Option<egui::Key>, modifiers: Option<&egui::Modifiers>)
+		-> bool
+	{
+		let keyMatches = match self.trigger {
+			Trigger::Key(boundKey) => boundKey == key,
+			Trigger::PhysicalKey(boundKey) => physicalKey.unwrap_or(key) == boundKey,
+			Trigger::Pointer(_) | Trigger::ModifiersOnly => false
+		};
+		keyMatches && modifiers.is_none_or(|modifiers| self.modifiersHeld(modifiers))
+	}
+
+	/// Produce a human-readable description of the chord.
+	pub fn describe (&self) -> String
+	{
+		let modifiers = egui::ModifierNames::NAMES.format(&self.modifiers, cfg!(target_os="macos"));
+		let trigger = match self.trigger {
+			Trigger::Key(key) => key.name().to_owned(),
+			Trigger::PhysicalKey(key) => format!("[{}]", key.name()),
+			Trigger::Pointer(button) => format!("{button:?} button"),
+			Trigger::ModifiersOnly => return if modifiers.is_empty() { "none".into() } else { modifiers }
+		};
+		if modifiers.is_empty() { trigger } else { format!("{modifiers}+{trigger}") }
+	}
+}
+
+/// Maps [commands](Command) to the [chords](Chord) that trigger them. Consulted by the [`Player`] and the built-in
+/// [camera interactors](view::CameraInteractor), editable in the *View* side panel and persisted across sessions.
+/// Deserialized bindings are merged over the [defaults](Bindings::default), so commands that did not exist when the
+/// bindings were stored get their default chord, while commands that were explicitly unbound stay unbound.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(from="StoredBindings", into="StoredBindings")]
+pub struct Bindings {
+	map: BTreeMap<Command, Chord>
+}
+impl Bindings
+{
+	/// The key the bindings are persisted under in the *eframe* storage.
+	pub(crate) const STORAGE_KEY: &'static str = "CGV__player_bindings";
+
+	/// Reference the chord bound to the given command, if any.
+	#[inline]
+	pub fn get (&self, command: Command) -> Option<&Chord> {
+		self.map.get(&command)
+	}
+
+	/// Bind the given command to the given chord, or unbind it if `None`.
+	pub fn set (&mut self, command: Command, chord: Option<Chord>) {
+		match chord {
+			Some(chord) => self.map.insert(command, chord),
+			None => self.map.remove(&command)
+		};
+	}
+
+	/// Restore the [default chord](Command::defaultChord) of every command.
+	pub fn resetToDefaults (&mut self) {
+		*self = Self::default();
+	}
+
+	/// Check if the given key event triggers the given command. Pass `None` for the modifiers to only check the key,
+	/// e.g. for key releases.
+	#[inline]
+	pub fn matchesKey (
+		&self, command: Command, key: egui::Key, physicalKey: Option<egui::Key>, modifiers: Option<&egui::Modifiers>
+	) -> bool {
+		self.get(command).is_some_and(|chord| chord.matchesKey(key, physicalKey, modifiers))
+	}
+
+	/// Check if the modifiers of the given command, which should be bound [without trigger](Trigger::ModifiersOnly),
+	/// are held. Commands bound to chords with a trigger, or that are not bound at all, never match.
+	#[inline]
+	pub fn modifiersHeld (&self, command: Command, modifiers: &egui::Modifiers) -> bool {
+		self.get(command).is_some_and(|chord|
+			chord.trigger == Trigger::ModifiersOnly && !chord.modifiers.is_none() && chord.modifiersHeld(modifiers)
+		)
+	}
+
+	/// Find the command among the given ones that a drag with the given pointer button and modifiers triggers. If
+	/// several commands match, the one requiring the most modifiers wins, so that e.g. *Shift*+drag can be bound
+	/// differently from a plain drag with the same button.
+	pub fn dragCommand (
+		&self, candidates: &[Command], button: egui::PointerButton, modifiers: &egui::Modifiers
+	) -> Option<Command> {
+		candidates.iter().filter_map(|&command| {
+			let chord = self.get(command)?;
+			(chord.trigger == Trigger::Pointer(button) && chord.modifiersHeld(modifiers)).then_some((command, chord))
+		}).max_by_key(|(_, chord)| numModifiers(&chord.modifiers)).map(|(command, _)| command)
+	}
+
+	/// Consume the key press triggering the given command from the given input state, if there is one.
+	///
+	/// # Returns
+	///
+	/// `true` if the command was triggered, `false` otherwise.
+	pub fn consumeKeyPress (&self, command: Command, input: &mut egui::InputState) -> bool
+	{
+		let Some(chord) = self.get(command) else {
+			return false;
+		};
+		let mut triggered = false;
+		input.events.retain(|event| match event {
+			&egui::Event::Key { key, physical_key, pressed: true, modifiers, .. }
+				if !triggered && chord.matchesKey(key, physical_key, Some(&modifiers)) => { triggered = true; false },
+			_ => true
+		});
+		triggered
+	}
+}
+impl Default for Bindings {
+	fn default () -> Self { Self {
+		map: Command::ALL.iter().map(|command| (*command, command.defaultChord())).collect()
+	}}
+}
+impl From<StoredBindings> for Bindings {
+	fn from (stored: StoredBindings) -> Self {
+		let mut bindings = Self::default();
+		bindings.map.extend(stored.map);
+		for command in stored.unbound {
+			bindings.map.remove(&command);
+		}
+		bindings
+	}
+}
+
+/// The persisted form of [`Bindings`], recording unbound commands explicitly so they can be told apart from commands
+/// that did not exist yet when the bindings were stored.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct StoredBindings
+{
+	/// The bound commands.
+	map: BTreeMap<Command, Chord>,
+
+	/// The commands that were explicitly unbound.
+	#[serde(default)]
+	unbound: BTreeSet<Command>
+}
+impl From<Bindings> for StoredBindings {
+	fn from (bindings: Bindings) -> Self {
+		let unbound = Command::ALL.into_iter().filter(|command| !bindings.map.contains_key(command)).collect();
+		Self { map: bindings.map, unbound }
+	}
+}
+
+
+
+//////
+//
+// Functions
+//
+
+/// Count the modifiers that are set in the given modifier state.
+fn numModifiers (modifiers: &egui::Modifiers) -> u32 {
+	modifiers.alt as u32 + modifiers.shift as u32 + (modifiers.ctrl || modifiers.command || modifiers.mac_cmd) as u32
+}
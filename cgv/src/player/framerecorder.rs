@@ -0,0 +1,295 @@
+//////
+//
+// Imports
+//
+
+// Standard library
+use std::{fs, io::{self, Write}, path::PathBuf};
+
+// Local imports
+use crate::*;
+use super::{ImageFormat, instance, headless::{srgb8FromImage, readbackImageUnlocked}};
+
+
+
+//////
+//
+// Enums and structs
+//
+
+/// The continuous video stream formats the [frame recorder](FrameRecorder) can write in addition to (or instead of)
+/// individual images.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamFormat
+{
+	/// *YUV4MPEG2* stream with full chroma resolution (*4:4:4*) in limited range *BT.601* encoding, which *ffmpeg* and
+	/// most other video tools can read directly.
+	Y4M,
+
+	/// Headerless stream of 8-bit *sRGB* RGBA frames. Consume with e.g.
+	/// `ffmpeg -f rawvideo -pix_fmt rgba -s <width>x<height> -r <framerate> -i video.rgba`.
+	RawRgba
+}
+impl StreamFormat
+{
+	/// The file extension conventionally used for the format.
+	pub fn extension (&self) -> &'static str {
+		match self {
+			Self::Y4M => "y4m",
+			Self::RawRgba => "rgba"
+		}
+	}
+}
+
+/// Configures a [frame recording](Player::startFrameRecording) of the main viewport.
+#[derive(Debug, Clone)]
+pub struct FrameRecordingOptions
+{
+	/// The directory to write the recorded files into. Will be created if it does not exist.
+	pub directory: PathBuf,
+
+	/// The framerate of the recording, in frames per second.
+	pub framerate: u32,
+
+	/// Whether to advance the simulated time, as reported by [`State::lastFrameTime`](super::State::lastFrameTime), by
+	/// exactly one frame at the recording framerate each frame, regardless of how long rendering actually takes. This
+	/// way, no frames get dropped and animations are reproduced exactly, no matter how slow the recording machine is.
+	pub fixedTimestep: bool,
+
+	/// The format of the numbered image files (`frame00000.png` etc.) to write for each frame, if any.
+	pub images: Option<ImageFormat>,
+
+	/// The format of the continuous video stream (`video.y4m` etc.) to write, if any.
+	pub stream: Option<StreamFormat>,
+
+	/// Whether to play back the [camera path](view::CameraAnimation) from its start when the recording begins, and to
+	/// stop recording once playback finishes.
+	pub playCameraPath: bool
+}
+impl Default for FrameRecordingOptions {
+	fn default () -> Self { Self {
+		directory: PathBuf::from("recording"), framerate: 60, fixedTimestep: true, images: Some(ImageFormat::PNG),
+		stream: None, playCameraPath: false
+	}}
+}
+
+/// An ongoing recording of the frames rendered into the main viewport, as started by
+/// [`Player::startFrameRecording`].
+pub struct FrameRecorder
+{
+	options: FrameRecordingOptions,
+	numFrames: u32,
+	stream: Option<(io::BufWriter<fs::File>, glm::UVec2)>,
+	awaitingCapture: bool
+}
+impl FrameRecorder
+{
+	/// Prepare a recording according to the given options.
+	fn new (options: FrameRecordingOptions) -> Result<Self>
+	{
+		if options.images.is_none() && options.stream.is_none() {
+			return Err(anyhow!("frame recording would produce no output"));
+		}
+		if options.framerate < 1 {
+			return Err(anyhow!("invalid recording framerate: {}", options.framerate));
+		}
+		fs::create_dir_all(&options.directory)?;
+		Ok(Self { options, numFrames: 0, stream: None, awaitingCapture: false })
+	}
+
+	/// Reference the options the recording was started with.
+	#[inline(always)]
+	pub fn options (&self) -> &FrameRecordingOptions {
+		&self.options
+	}
+
+	/// The number of frames recorded so far.
+	#[inline(always)]
+	pub fn numFrames (&self) -> u32 {
+		self.numFrames
+	}
+
+	/// The duration of one frame at the recording framerate.
+	#[inline]
+	pub fn frameDuration (&self) -> time::Duration {
+		time::Duration::from_secs_f64(1. / self.options.framerate as f64)
+	}
+
+	/// Write the given image as the next frame of the recording.
+	fn writeFrame (&mut self, image: &image::DynamicImage) -> Result<()>
+	{
+		// Numbered image
+		if let Some(format) = self.options.images {
+			let path = self.options.directory.join(format!("frame{:05}.{}", self.numFrames, format.extension()));
+			format.save(image, &path)?;
+		}
+
+		// Stream, opened upon the first frame when the resolution is known
+		if let Some(format) = self.options.stream
+		{
+			let srgb = srgb8FromImage(image);
+			let dims = glm::vec2(srgb.width(), srgb.height());
+			if self.stream.is_none() {
+				let path = self.options.directory.join(format!("video.{}", format.extension()));
+				let mut writer = io::BufWriter::new(fs::File::create(path)?);
+				if format == StreamFormat::Y4M {
+					writeln!(
+						writer, "YUV4MPEG2 W{} H{} F{}:1 Ip A1:1 C444 XCOLORRANGE=LIMITED",
+						dims.x, dims.y, self.options.framerate
+					)?;
+				}
+				self.stream = Some((writer, dims));
+			}
+			let (writer, streamDims) = self.stream.as_mut().unwrap();
+			if dims != *streamDims {
+				return Err(anyhow!(
+					"viewport resolution changed from {}×{} to {}×{} during recording",
+					streamDims.x, streamDims.y, dims.x, dims.y
+				));
+			}
+			match format {
+				StreamFormat::Y4M => writeY4mFrame(writer, &srgb)?,
+				StreamFormat::RawRgba => writer.write_all(srgb.as_raw())?
+			}
+		}
+
+		// Done!
+		self.numFrames += 1;
+		Ok(())
+	}
+
+	/// Conclude the recording, flushing any pending stream output.
+	///
+	/// # Returns
+	///
+	/// The total number of recorded frames.
+	fn finish (self) -> Result<u32>
+	{
+		if let Some((mut writer, _)) = self.stream {
+			writer.flush()?;
+		}
+		Ok(self.numFrames)
+	}
+}
+
+
+
+//////
+//
+// Functions
+//
+
+/// Write the given *sRGB* image as one frame of a *YUV4MPEG2* stream in *4:4:4* planar layout.
+pub(crate) fn writeY4mFrame (writer: &mut impl Write, srgb: &image::RgbaImage) -> io::Result<()>
+{
+	// Convert to limited range BT.601 YCbCr
+	let numPixels = srgb.width() as usize * srgb.height() as usize;
+	let mut planes = vec![0u8; 3*numPixels];
+	for (i, pixel) in srgb.pixels().enumerate() {
+		let [r, g, b] = [pixel[0] as f32, pixel[1] as f32, pixel[2] as f32].map(|c| c / 255.);
+		planes[i] = (16. + 65.481*r + 128.553*g + 24.966*b).round() as u8;
+		planes[numPixels + i] = (128. - 37.797*r - 74.203*g + 112.*b).round() as u8;
+		planes[2*numPixels + i] = (128. + 112.*r - 93.786*g - 18.214*b).round() as u8;
+	}
+	writer.write_all(b"FRAME\n")?;
+	writer.write_all(&planes)
+}
+
+
+
+//////
+//
+// Classes
+//
+
+////
+// Player
+
+impl Player
+{
+	/// Start recording every frame rendered into the main viewport to disk, as described by the given options. An
+	/// ongoing recording gets concluded first. Recording keeps the scene redrawing continuously until
+	/// [stopped](Self::stopFrameRecording).
+	pub fn startFrameRecording (&mut self, options: FrameRecordingOptions) -> Result<()>
+	{
+		self.stopFrameRecording()?;
+		tracing::info!("Starting frame recording into `{}`", options.directory.display());
+		self.state.frameRecorder = Some(FrameRecorder::new(options)?);
+		self.state.pushContinuousRedrawRequest();
+		Ok(())
+	}
+
+	/// Conclude the ongoing frame recording, if any. A frame that is still being prepared when calling this will not
+	/// be part of the recording.
+	///
+	/// # Returns
+	///
+	/// The number of recorded frames, or *0* if there was no ongoing recording.
+	pub fn stopFrameRecording (&mut self) -> Result<u32>
+	{
+		let Some(recorder) = self.state.frameRecorder.take() else {
+			return Ok(0);
+		};
+		self.state.dropContinuousRedrawRequest();
+		let numFrames = recorder.finish()?;
+		tracing::info!("Stopped frame recording after {numFrames} frames");
+		Ok(numFrames)
+	}
+
+	/// Reference the ongoing [frame recording](Self::startFrameRecording), if any.
+	#[inline(always)]
+	pub fn frameRecorder (&self) -> Option<&FrameRecorder> {
+		self.state.frameRecorder.as_ref()
+	}
+
+	/// Advance the ongoing frame recording of the global player instance, if any. Must be called at the beginning of
+	/// each frame without holding the player lock, before any state that affects the scene gets updated: captures the
+	/// frame rendered last time (releasing the player while waiting for the GPU), and makes sure that the coming frame
+	/// gets rendered and uses the simulated frame time if requested.
+	pub(crate) fn advanceFrameRecording ()
+	{
+		let mut player = instance::lock();
+		let Some(recorder) = player.state.frameRecorder.as_mut() else {
+			return;
+		};
+
+		// Capture the last frame, concluding the recording on failure or when camera path playback has finished
+		if recorder.awaitingCapture
+		{
+			recorder.awaitingCapture = false;
+			let image;
+			(image, player) = readbackImageUnlocked(player, None);
+			let this = &mut*player;
+			let Some(recorder) = this.state.frameRecorder.as_mut() else {
+				// Recording was stopped while waiting for the GPU
+				return;
+			};
+			let captured = image.and_then(|image| recorder.writeFrame(&image));
+			let pathFinished = recorder.options.playCameraPath && !this.state.cameraAnimation.isPlaying();
+			if let Err(err) = &captured {
+				tracing::error!("Frame recording failed: {err}");
+			}
+			if captured.is_err() || pathFinished {
+				if let Err(err) = this.stopFrameRecording() {
+					tracing::error!("Failed to conclude frame recording: {err}");
+				}
+				return;
+			}
+		}
+
+		// Set up the coming frame, with the first one showing the start of the camera path if requested
+		let this = &mut*player;
+		let recorder = this.state.frameRecorder.as_mut().unwrap();
+		let firstFrame = recorder.numFrames == 0;
+		if recorder.options.fixedTimestep {
+			this.state.prevFrameDuration = if firstFrame { time::Duration::ZERO } else { recorder.frameDuration() };
+		}
+		recorder.awaitingCapture = true;
+		if firstFrame && recorder.options.playCameraPath {
+			let start = this.state.cameraAnimation.path().startTime();
+			this.seekCameraAnimation(start);
+			this.state.cameraAnimation.play();
+		}
+		this.state.pendingRedraw = true;
+	}
+}
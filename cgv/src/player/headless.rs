@@ -0,0 +1,373 @@
+//////
+//
+// Imports
+//
+
+// Standard library
+use std::{path::{Path, PathBuf}, sync::{mpsc, Mutex}};
+
+// WGPU API
+use wgpu;
+
+// Local imports
+use crate::*;
+use super::instance;
+
+
+
+//////
+//
+// Globals
+//
+
+/// Serializes [headless](Player::renderHeadless) rendering, since the headless player occupies the global player
+/// instance while rendering, and e.g. tests run in parallel by default.
+static HEADLESS_LOCK: Mutex<()> = Mutex::new(());
+
+
+
+//////
+//
+// Enums and structs
+//
+
+/// A single image to be produced by the [headless](Player::runHeadless) player.
+#[derive(Clone, Copy)]
+pub enum Shot
+{
+	/// Render the scene as seen with the given camera parameters. The aspect ratio of the intrinsics gets adjusted to
+	/// the [output resolution](HeadlessOptions::resolution).
+	Camera(view::CameraParameters),
+
+	/// Render the given number of consecutive frames with the current camera parameters, keeping only the last one.
+	/// Useful for applications that animate their content.
+	Frames(u32)
+}
+
+/// The image file formats the [headless](Player::runHeadless) player can write.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat
+{
+	/// 8-bit *sRGB* PNG. Floating point images get clamped to the displayable range.
+	PNG,
+
+	/// 32-bit floating point OpenEXR containing linear color values. 8-bit images are assumed to hold *sRGB* values.
+	EXR
+}
+impl ImageFormat
+{
+	/// The file extension conventionally used for the format.
+	pub fn extension (&self) -> &'static str {
+		match self {
+			Self::PNG => "png",
+			Self::EXR => "exr"
+		}
+	}
+
+	/// Write the given image (as obtained from e.g. [`hal::Texture::readbackImage`]) to the given file in this format,
+	/// converting between *sRGB* and linear color values as necessary.
+	pub fn save (&self, image: &image::DynamicImage, path: impl AsRef<Path>) -> Result<()>
+	{
+		use egui::ecolor::linear_f32_from_gamma_u8;
+		match (self, image)
+		{
+			(Self::PNG, _) => srgb8FromImage(image).save_with_format(path, image::ImageFormat::Png)?,
+
+			(Self::EXR, image::DynamicImage::ImageRgba32F(linear)) =>
+				linear.save_with_format(path, image::ImageFormat::OpenExr)?,
+
+			(Self::EXR, _) => {
+				let srgb = image.to_rgba8();
+				image::Rgba32FImage::from_fn(srgb.width(), srgb.height(), |x, y| {
+					let [r, g, b, a] = srgb.get_pixel(x, y).0;
+					image::Rgba([
+						linear_f32_from_gamma_u8(r), linear_f32_from_gamma_u8(g), linear_f32_from_gamma_u8(b),
+						a as f32 / 255.
+					])
+				}).save_with_format(path, image::ImageFormat::OpenExr)?
+			}
+		};
+		Ok(())
+	}
+}
+
+/// Configures the [headless](Player::runHeadless) player.
+#[derive(Clone)]
+pub struct HeadlessOptions
+{
+	/// The resolution of the offscreen framebuffer, in pixels.
+	pub resolution: glm::UVec2,
+
+	/// The color format of the offscreen framebuffer. Choose a floating point format like
+	/// [`wgpu::TextureFormat::Rgba16Float`] for high dynamic range output.
+	pub colorFormat: wgpu::TextureFormat,
+
+	/// Whether to only accept the fallback adapter of *WGPU*, which usually is a software rasterizer. Allows rendering
+	/// on machines without a GPU, e.g. for continuous integration.
+	pub forceFallbackAdapter: bool,
+
+	/// The simulated time that passes between two rendered frames, as reported by
+	/// [`State::lastFrameTime`](super::State::lastFrameTime).
+	pub frameDuration: time::Duration
+}
+impl Default for HeadlessOptions {
+	fn default () -> Self { Self {
+		resolution: glm::vec2(1280, 720),
+		colorFormat: wgpu::TextureFormat::Rgba8UnormSrgb,
+		forceFallbackAdapter: false,
+		frameDuration: time::Duration::from_secs_f64(1./60.)
+	}}
+}
+
+
+
+//////
+//
+// Functions
+//
+
+/// Convert the given image (as obtained from e.g. [`hal::Texture::readbackImage`]) to 8-bit *sRGB*. Floating point
+/// images are assumed to hold linear color values and get clamped to the displayable range.
+pub(super) fn srgb8FromImage (image: &image::DynamicImage) -> image::RgbaImage
+{
+	use egui::ecolor::gamma_u8_from_linear_f32;
+	match image {
+		image::DynamicImage::ImageRgba32F(linear) => image::RgbaImage::from_fn(
+			linear.width(), linear.height(), |x, y| {
+				let [r, g, b, a] = linear.get_pixel(x, y).0;
+				image::Rgba([
+					gamma_u8_from_linear_f32(r), gamma_u8_from_linear_f32(g), gamma_u8_from_linear_f32(b),
+					(a.clamp(0., 1.)*255.).round() as u8
+				])
+			}
+		),
+		_ => image.to_rgba8()
+	}
+}
+
+/// Set up the *WGPU* HAL objects for headless rendering without any surface. The usual *WGPU* environment variables
+/// (like `WGPU_BACKEND` or `WGPU_ADAPTER_NAME`) are respected.
+pub(crate) fn createHal (options: &HeadlessOptions) -> Result<(wgpu::Adapter, wgpu::Device, wgpu::Queue)>
+{
+	// Instance and adapter
+	let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
+		backends: wgpu::Backends::PRIMARY,
+		#[cfg(debug_assertions)]
+			flags: wgpu::InstanceFlags::debugging(),
+		#[cfg(not(debug_assertions))]
+			flags: wgpu::InstanceFlags::empty(),
+		..wgpu::InstanceDescriptor::new_without_display_handle()
+	}.with_env());
+	let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+		power_preference: wgpu::PowerPreference::HighPerformance,
+		force_fallback_adapter: options.forceFallbackAdapter,
+		compatible_surface: None
+	}))?;
+	let info = adapter.get_info();
+	tracing::info!("Headless adapter: {} ({:?}, {:?})", info.name, info.device_type, info.backend);
+
+	// Device - request the same features as the windowed player, as far as the adapter (which might well be a software
+	// rasterizer) supports them
+	let desiredFeatures = wgpu::Features::INDIRECT_FIRST_INSTANCE | wgpu::Features::PASSTHROUGH_SHADERS;
+	let missingFeatures = desiredFeatures - adapter.features();
+	if !missingFeatures.is_empty() {
+		tracing::warn!("Headless adapter does not support {missingFeatures:?}");
+	}
+	let (device, queue) = pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor {
+		label: Some("CGV__WgpuDevice"),
+		experimental_features: unsafe { wgpu::ExperimentalFeatures::enabled() },
+		required_features: desiredFeatures & adapter.features(),
+		..Default::default()
+	}))?;
+
+	// Done!
+	Ok((adapter, device, queue))
+}
+
+/// Render all shots with the global player instance, handing each resulting image to the sink.
+fn renderShots (
+	mut player: instance::LockGuard, options: &HeadlessOptions, shots: &[Shot],
+	sink: &mut dyn FnMut(usize, image::DynamicImage) -> Result<()>
+) -> Result<()>
+{
+	// Size the camera to the requested resolution
+	let playerRef = &mut *player;
+	playerRef.camera.resize(&playerRef.state.context, options.resolution);
+	let aspect = options.resolution.x as f32 / options.resolution.y as f32;
+
+	// Render all shots
+	for (index, shot) in shots.iter().enumerate()
+	{
+		// Set up the shot and draw it
+		let numFrames = match shot {
+			Shot::Camera(parameters) => {
+				let cameraParameters = player.camera.parameters_mut();
+				*cameraParameters = *parameters;
+				cameraParameters.intrinsics.aspect = aspect;
+				1
+			},
+			Shot::Frames(numFrames) => (*numFrames).max(1)
+		};
+		for _ in 0..numFrames {
+			player.headlessFrame(options.frameDuration);
+		}
+
+		// Read back the image, releasing the player while handing it to the sink
+		let image;
+		(image, player) = readbackImageUnlocked(player, None);
+		let image = image?;
+		drop(player);
+		sink(index, image)?;
+		player = instance::lock();
+	}
+
+	// Done!
+	Ok(())
+}
+
+/// Read back the given texture into an image, releasing the global player instance while waiting for the GPU. This
+/// way, asynchronous callbacks that access the player (like those of [picking](Player::pickAtSurfacePixel_async)) can
+/// run while waiting, instead of finding the player locked.
+///
+/// # Arguments
+///
+/// * `player` – The locked global player instance.
+/// * `texture` – The texture to read back, or `None` for the color target of the camera framebuffer.
+///
+/// # Returns
+///
+/// The image or the error that prevented reading it back, along with the re-acquired player.
+pub(super) fn readbackImageUnlocked (player: instance::LockGuard, texture: Option<&hal::Texture>)
+	-> (Result<image::DynamicImage>, instance::LockGuard)
+{
+	let (sender, receiver) = mpsc::channel();
+	texture.unwrap_or_else(|| player.camera.framebuffer().color0()).readbackImageAsync(
+		&player.state.context, move |image| { sender.send(image).ok(); }
+	);
+	let device = player.context.device().clone();
+	drop(player);
+	let image = device.poll(wgpu::PollType::wait_indefinitely()).map_err(anyhow::Error::from).and_then(
+		|_| receiver.recv().map_err(|_| anyhow!("framebuffer readback did not complete"))?
+	);
+	(image, instance::lock())
+}
+
+
+
+//////
+//
+// Classes
+//
+
+////
+// Player
+
+impl Player
+{
+	/// Render the application provided by the given factory offscreen, without creating a window or surface. Each of
+	/// the given shots produces one image that gets handed to the `sink`, together with the index of the shot. The
+	/// player is available as the global instance while rendering, so applications can access it from asynchronous
+	/// callbacks just like in the windowed player. For this reason, headless rendering cannot happen while another
+	/// player is running, and concurrent calls wait for each other to finish.
+	///
+	/// # Arguments
+	///
+	/// * `applicationFactory` – The factory creating the application to render.
+	/// * `options` – The headless rendering configuration.
+	/// * `shots` – The list of images to render.
+	/// * `sink` – Receives each rendered image, e.g. for writing it to disk or comparing it against a reference.
+	///
+	/// # Returns
+	///
+	/// `Ok` if all shots were rendered and consumed by the sink, or the first error that occurred otherwise (including
+	/// there already being a global player instance).
+	pub fn renderHeadless (
+		applicationFactory: Box<dyn ApplicationFactory>, options: &HeadlessOptions, shots: &[Shot],
+		mut sink: impl FnMut(usize, image::DynamicImage) -> Result<()>
+	) -> Result<()>
+	{
+		// Wait for any other headless rendering to finish
+		let _lock = HEADLESS_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+		// Log that we have begun the startup process
+		tracing::info!("Starting up headless...");
+		tracing::info!("Platform: {}", util::meta::platformTargetTriple());
+
+		// Create the player around a surface-less context
+		let (adapter, device, queue) = createHal(options)?;
+		let player = Self::fromParts(
+			applicationFactory, egui::Context::default(), Context::fromHal(adapter, device, queue),
+			options.colorFormat, Self::loadEnvironment()
+		)?;
+
+		// Install the player as the global instance, refusing to replace one that is already running
+		let player = instance::trySet(player).map_err(
+			|_| anyhow!("cannot render headless while another player instance is running")
+		)?;
+
+		// Render, making sure the global instance gets released even on failure or panic
+		struct ReleaseInstance;
+		impl Drop for ReleaseInstance {
+			fn drop (&mut self) { instance::reset(); }
+		}
+		let release = ReleaseInstance;
+		let result = renderShots(player, options, shots, &mut sink);
+		drop(release);
+		tracing::info!("Shutdown complete.");
+		result
+	}
+
+	/// Render the application provided by the given factory offscreen and write the resulting images into the given
+	/// directory as `frame0000.png`, `frame0001.png` etc. See [`Self::renderHeadless`] for details.
+	///
+	/// # Returns
+	///
+	/// The paths of all written images if successful, or the first error that occurred otherwise.
+	pub fn runHeadless (
+		applicationFactory: Box<dyn ApplicationFactory>, options: &HeadlessOptions, shots: &[Shot],
+		outputDirectory: impl AsRef<Path>, format: ImageFormat
+	) -> Result<Vec<PathBuf>>
+	{
+		let outputDirectory = outputDirectory.as_ref();
+		std::fs::create_dir_all(outputDirectory)?;
+		let mut paths = Vec::with_capacity(shots.len());
+		Self::renderHeadless(applicationFactory, options, shots, |index, image| {
+			let path = outputDirectory.join(format!("frame{index:04}.{}", format.extension()));
+			format.save(&image, &path)?;
+			tracing::info!("Wrote {}", path.display());
+			paths.push(path);
+			Ok(())
+		})?;
+		Ok(paths)
+	}
+
+	/// Draw one frame into the camera framebuffer, advancing the simulated time by the given duration.
+	fn headlessFrame (&mut self, frameDuration: time::Duration)
+	{
+		// Advance time
+		self.state.prevFrameDuration = frameDuration;
+		self.state.prevFrameElapsed += frameDuration;
+
+		// Prepare and draw the scene
+		self.renderImmediately();
+	}
+
+	/// Update the camera and draw the scene into its framebuffer right away, outside of the regular event loop.
+	pub(super) fn renderImmediately (&mut self)
+	{
+		if self.state.autoClippingPlanes {
+			self.adaptClippingPlanes();
+		}
+		self.camera.update();
+		let device = self.context.device().clone();
+		let queue = self.context.queue().clone();
+		let mut encoder = device.create_command_encoder(
+			&wgpu::CommandEncoderDescriptor {label: Some("CGV__HeadlessCommandEncoder")}
+		);
+		let mut cmdBuffers = self.prepare(&device, &queue, &mut encoder);
+		cmdBuffers.extend(self.redraw(&device, &queue, &mut encoder));
+		cmdBuffers.push(encoder.finish());
+		queue.submit(cmdBuffers);
+	}
+
+}
@@ -19,6 +19,44 @@ pub use rendersetup::RenderSetup; // - re-export
 mod viewportcompositor;
 use viewportcompositor::*;
 
+/// Submodule providing configurable bindings of commands to input chords.
+mod bindings;
+pub use bindings::{Bindings, Command, Chord, Trigger}; // - re-export
+
+/// Submodule providing the modality-agnostic action layer on top of raw input events.
+mod actions;
+pub use actions::{Action, ActionInfo}; // - re-export
+
+/// Submodule providing object identification for picking.
+mod picking;
+pub use picking::{ObjectId, OwnedObjectId, Pick}; // - re-export
+#[cfg(test)]
+pub(crate) use picking::setRenderingApplication; // - re-export
+
+/// Submodule providing the headless offscreen rendering mode of the [`Player`].
+#[cfg(not(target_arch="wasm32"))]
+mod headless;
+#[cfg(not(target_arch="wasm32"))]
+pub use headless::{HeadlessOptions, Shot, ImageFormat}; // - re-export
+#[cfg(all(test, not(target_arch="wasm32")))]
+pub(crate) use headless::createHal; // - re-export
+
+/// Submodule providing the recording of frame sequences and videos of the main viewport.
+#[cfg(not(target_arch="wasm32"))]
+mod framerecorder;
+#[cfg(not(target_arch="wasm32"))]
+pub use framerecorder::{FrameRecorder, FrameRecordingOptions, StreamFormat}; // - re-export
+#[cfg(all(test, not(target_arch="wasm32")))]
+pub(crate) use framerecorder::writeY4mFrame; // - re-export
+
+/// Submodule providing tiled high-resolution screenshots of the main viewport.
+#[cfg(not(target_arch="wasm32"))]
+mod screenshot;
+#[cfg(not(target_arch="wasm32"))]
+pub use screenshot::ScreenshotOptions; // - re-export
+#[cfg(all(test, not(target_arch="wasm32")))]
+pub(crate) use screenshot::downsampleTile; // - re-export
+
 
 
 //////
@@ -27,7 +65,7 @@ use viewportcompositor::*;
 //
 
 // Standard library
-use std::{any::Any, sync::Arc};
+use std::{any::Any, collections::BTreeSet, sync::Arc};
 #[cfg(not(target_arch="wasm32"))]
 use std::fs;
 
@@ -80,6 +118,10 @@ pub struct KeyInfo
 	/// The key code of the key the event relates to. See [`egui::Event::Key`] for details.
 	pub key: egui::Key,
 
+	/// The key at the physical location of the key the event relates to on a US *QWERTY* layout, if known. See
+	/// [`egui::Event::Key`] for details.
+	pub physicalKey: Option<egui::Key>,
+
 	/// Whether this is a *press* event (`true`) or *release* (`false`). See [`egui::Event::Key`] for details.
 	pub pressed: bool,
 
@@ -91,6 +133,22 @@ pub struct KeyInfo
 	pub modifiers: egui::Modifiers
 }
 
+/// Enumeration of the input modalities pointer-like events can originate from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputSource
+{
+	/// A mouse, trackpad or any other device the windowing system reports as a mouse.
+	Mouse,
+
+	/// A finger on a touchscreen.
+	Touch,
+
+	/// A stylus. Since *egui* reports stylus contacts as touches without telling them apart from fingers (many of
+	/// which report a pressure value too), touches are only considered a stylus if
+	/// [pen input](Player::setPenInputEnabled) is enabled and they report a pressure value.
+	Pen
+}
+
 /// Struct containing information about a click.
 #[derive(Debug)]
 pub struct ClickInfo {
@@ -101,7 +159,10 @@ pub struct ClickInfo {
 	pub modifiers: egui::Modifiers,
 
 	/// The pointer coordinates within the main viewport at the time of the click, in pixels.
-	pub position: glm::UVec2
+	pub position: glm::UVec2,
+
+	/// The input modality the click originated from (taps are reported as primary clicks).
+	pub source: InputSource
 }
 
 /// Struct containing information about a mouse wheel event
@@ -114,8 +175,10 @@ pub struct MouseWheelInfo {
 	pub modifiers: egui::Modifiers
 }
 
-/// Struct containing information about a drag event.
+/// Struct containing information about a drag event. Marked non-exhaustive, as drags may get reported with additional
+/// information in the future (as happened with [`position`](DragInfo::position)).
 #[derive(Debug)]
+#[non_exhaustive]
 pub struct DragInfo {
 	/// Which pointer buttons are down. Should be queried using the [`egui::PointerButton`] enum.
 	pub buttons: [bool; 5],
@@ -124,7 +187,13 @@ pub struct DragInfo {
 	pub modifiers: egui::Modifiers,
 
 	/// The direction of the drag, using logical screen points as unit.
-	pub direction: glm::Vec2
+	pub direction: glm::Vec2,
+
+	/// The pointer coordinates within the main viewport at the end of the drag motion, in pixels.
+	pub position: glm::Vec2,
+
+	/// The input modality the drag originated from (one-finger swipes are reported as primary drags).
+	pub source: InputSource
 }
 impl DragInfo {
 	/// Convenience method for querying the [`buttons`](DragInfo::buttons) field.
@@ -134,6 +203,82 @@ impl DragInfo {
 	}
 }
 
+/// Struct containing information about a single finger touching the main viewport.
+#[derive(Debug)]
+pub struct TouchInfo {
+	/// Identifies the finger across the phases of the touch.
+	pub id: u64,
+
+	/// The phase of the touch.
+	pub phase: egui::TouchPhase,
+
+	/// The key modifiers that are currently also pressed. See [`egui::Event::Key`] for details.
+	pub modifiers: egui::Modifiers,
+
+	/// The touch coordinates within the main viewport, in pixels.
+	pub position: glm::Vec2
+}
+
+/// Struct containing information about a stylus touching the main viewport.
+#[derive(Debug)]
+pub struct PenInfo {
+	/// Identifies the stylus contact across the phases of the touch.
+	pub id: u64,
+
+	/// The phase of the contact.
+	pub phase: egui::TouchPhase,
+
+	/// The key modifiers that are currently also pressed. See [`egui::Event::Key`] for details.
+	pub modifiers: egui::Modifiers,
+
+	/// The contact coordinates within the main viewport, in pixels.
+	pub position: glm::Vec2,
+
+	/// The pressure of the contact, in the range *[0..1]*.
+	pub pressure: f32
+}
+
+/// Struct containing information about the combined movement of two or more fingers on the main viewport.
+#[derive(Debug)]
+pub struct MultiTouchInfo {
+	/// The number of fingers currently touching.
+	pub numTouches: usize,
+
+	/// The key modifiers that are currently also pressed. See [`egui::Event::Key`] for details.
+	pub modifiers: egui::Modifiers,
+
+	/// The center of all touches within the main viewport, in pixels.
+	pub center: glm::Vec2,
+
+	/// The movement of the center since the last frame, using logical screen points as unit.
+	pub translation: glm::Vec2
+}
+
+/// Struct containing information about a pinch gesture, either on a touchscreen or on a trackpad.
+#[derive(Debug)]
+pub struct PinchInfo {
+	/// The zoom factor implied by the pinch since the last frame, with values greater than *1* indicating spreading
+	/// fingers.
+	pub zoom: f32,
+
+	/// The key modifiers that are currently also pressed. See [`egui::Event::Key`] for details.
+	pub modifiers: egui::Modifiers,
+
+	/// The input modality the pinch originated from.
+	pub source: InputSource
+}
+
+/// Struct containing information about a two-finger rotation gesture.
+#[derive(Debug)]
+pub struct RotateInfo {
+	/// The angle the fingers rotated by since the last frame, in radians, with positive values indicating clockwise
+	/// rotation on screen.
+	pub angle: f32,
+
+	/// The key modifiers that are currently also pressed. See [`egui::Event::Key`] for details.
+	pub modifiers: egui::Modifiers
+}
+
 /// Enumeration of input events.
 #[derive(Debug)]
 pub enum InputEvent
@@ -153,8 +298,38 @@ pub enum InputEvent
 	/// A mouse wheel / scroll event.
 	MouseWheel(MouseWheelInfo),
 
-	/// A pre-processed drag motion (including touch screen swipes).
-	Dragged(DragInfo)
+	/// A pre-processed drag motion (including touch screen swipes). Not reported while two or more fingers touch the
+	/// screen, see [`InputEvent::MultiTouch`] instead.
+	Dragged(DragInfo),
+
+	/// A single finger touching the main viewport.
+	Touch(TouchInfo),
+
+	/// A stylus touching the main viewport. Only generated while [pen input](Player::setPenInputEnabled) is enabled.
+	Pen(PenInfo),
+
+	/// Two or more fingers moving on the main viewport.
+	MultiTouch(MultiTouchInfo),
+
+	/// A pinch gesture on a touchscreen or trackpad.
+	Pinch(PinchInfo),
+
+	/// A two-finger rotation gesture.
+	Rotate(RotateInfo),
+
+	/// A high-level [interaction action](Action) derived from the raw event dispatched right before. Actions are only
+	/// dispatched if no receiver closed the raw event they were derived from.
+	Action(ActionInfo),
+
+	/// An object was hit by a primary click into the main viewport. Only occurs while
+	/// [picking is enabled](Player::setPickingEnabled), and always after the corresponding [`InputEvent::Click`] since
+	/// the object IDs have to be read back from the GPU first.
+	Pick(Pick),
+
+	/// The user finished drawing a region over the main viewport while in
+	/// [selection mode](Player::setSelectionTool). Primary drags do not produce [`InputEvent::Dragged`] events while
+	/// in selection mode.
+	RegionSelected(view::SelectionRegion)
 }
 
 /// Enumeration of possible event handling outcomes.
@@ -231,6 +406,21 @@ pub fn set (player: Player) -> LockGuard
 	return LockGuard(std::marker::PhantomData);
 }
 
+/// Store a new [`Player`] in the global instance unless there already is one.
+/// Hands the player back if the global instance is occupied, whether locked or not.
+#[inline]
+pub fn trySet (player: Player) -> Result<LockGuard, Player>
+{
+	match INSTANCE.state.compare_exchange(state::UNINIT, state::LOCKED, Ordering::Acquire, Ordering::Relaxed) {
+		Ok(_) => {
+			unsafe{&mut*INSTANCE.data.get()}.write(player);
+			Ok(LockGuard(std::marker::PhantomData))
+		},
+		Err(state::AVAILABLE | state::LOCKED) => Err(player),
+		Err(_) => panic!(msg!(BAD_STATE))
+	}
+}
+
 /// Acquire the global [`Player`] instance for exclusive access.
 /// Panics if the player is uninitialized or locked already.
 #[inline]
@@ -303,7 +493,7 @@ pub use instance::{lock, LockGuard};
 
 /// Identifies a [`Component`] stored by the [`Player`], providing access to that component outside of callbacks.
 /// Handles remain valid for the entire lifetime of the component they refer to.
-#[derive(Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Handle (usize);
 
 
@@ -416,7 +606,8 @@ pub struct State
 	pub(crate) defaultClearColor: egui::Color32,
 	prevFramebufferResolution: glm::UVec2,
 
-	quitShortcut: egui::KeyboardShortcut,
+	bindings: Bindings,
+	pub(crate) capturingBinding: Option<Command>,
 	activeSidePanel: usize,
 
 	viewportCompositor: ViewportCompositor,
@@ -425,6 +616,21 @@ pub struct State
 	continuousRedrawRequests: u32,
 	userInstantRedraw: bool,
 
+	pickingEnabled: bool,
+	selectionTool: Option<view::SelectionTool>,
+	selectionPolygon: Vec<glm::Vec2>,
+	penInputEnabled: bool,
+	penContacts: BTreeSet<u64>,
+
+	cameraAnimation: view::CameraAnimation,
+	viewBookmarks: view::ViewBookmarks,
+	animationRedrawRequested: bool,
+	autoClippingPlanes: bool,
+	#[cfg(not(target_arch="wasm32"))]
+	frameRecorder: Option<FrameRecorder>,
+	#[cfg(not(target_arch="wasm32"))]
+	pendingScreenshot: Option<(ScreenshotOptions, std::path::PathBuf, ImageFormat)>,
+
 	startInstant: time::Instant,
 	prevFrameElapsed: time::Duration,
 	prevFrameDuration: time::Duration
@@ -479,13 +685,32 @@ impl Player
 		#[cfg(target_arch="wasm32")]
 		cc.egui_ctx.options_mut(|options| options.input_options.max_click_dist *= 5.);
 
-		// Create context
-		let context = Context::new(eguiRs);
+		// Create context and construct
+		let mut player = Self::fromParts(
+			applicationFactory, cc.egui_ctx.clone(), Context::new(eguiRs), eguiRs.target_format, environment
+		)?;
 
+		// Restore persisted input bindings (deserializing merges them over the defaults)
+		if let Some(bindings) = cc.storage.and_then(|storage| eframe::get_value(storage, Bindings::STORAGE_KEY)) {
+			player.state.bindings = bindings;
+		}
+		if let Some(bookmarks) = cc.storage.and_then(|s| eframe::get_value(s, view::ViewBookmarks::STORAGE_KEY)) {
+			player.state.viewBookmarks = bookmarks;
+		}
+		Ok(player)
+	}
+
+	/// Internal helper for construction that is shared between the windowed and the [headless](Self::runHeadless)
+	/// player.
+	fn fromParts (
+		applicationFactory: Box<dyn ApplicationFactory>, egui: egui::Context, context: Context,
+		colorFormat: wgpu::TextureFormat, environment: run::Environment
+	) -> Result<Self>
+	{
 		// Log render setup
 		let defaultClearColor = egui::Rgba::from_rgb(0.0707, 0.217, 0.457);
 		let renderSetup = RenderSetup::new(
-			&context, eguiRs.target_format, eguiRs.target_format, hal::DepthStencilFormat::D32,
+			&context, colorFormat, colorFormat, hal::DepthStencilFormat::D32,
 			wgpu::Color{
 				r: defaultClearColor.r() as f64, g: defaultClearColor.g() as f64,
 				b: defaultClearColor.b() as f64, a: defaultClearColor.a() as f64
@@ -509,14 +734,16 @@ impl Player
 			cameraInteractors: CameraInteractors {
 				list: vec![
 					Some(Box::new(view::OrbitInteractor::new())),
-					Some(Box::new(view::WASDInteractor::new()))
+					Some(Box::new(view::WASDInteractor::new())),
+					Some(Box::new(view::TrackballInteractor::new()))
 				],
 				active: 0,
 			},
 			applications: Applications::NONE,
 			state: State {
-				quitShortcut: egui::KeyboardShortcut::new(egui::Modifiers::NONE, egui::Key::Escape),
-				egui: cc.egui_ctx.clone(),
+				bindings: Bindings::default(),
+				capturingBinding: None,
+				egui,
 				activeSidePanel: 0,
 
 				context,
@@ -531,13 +758,28 @@ impl Player
 				continuousRedrawRequests: 0,
 				userInstantRedraw: false,
 
+				pickingEnabled: false,
+				selectionTool: None,
+				selectionPolygon: Vec::new(),
+				penInputEnabled: false,
+				penContacts: BTreeSet::new(),
+				cameraAnimation: view::CameraAnimation::new(),
+				viewBookmarks: view::ViewBookmarks::new(),
+				animationRedrawRequested: false,
+				autoClippingPlanes: true,
+				#[cfg(not(target_arch="wasm32"))]
+				frameRecorder: None,
+				#[cfg(not(target_arch="wasm32"))]
+				pendingScreenshot: None,
+
 				startInstant: time::Instant::now(),
 				prevFrameElapsed: time::Duration::from_secs(0),
 				prevFrameDuration: time::Duration::from_secs(0),
 			}
 		};
 
-		// Init application(s)
+		// Init application(s), remembering the initial view to find out whether the application set up its own
+		let initialParameters = *player.camera.parameters();
 		let mut activeApplication = applicationFactory.create(
 			&player.state.context, &player.state.renderSetup, environment
 		)?;
@@ -549,11 +791,41 @@ impl Player
 		player.applications.list.push(Some(activeApplication));
 		player.activeSidePanel = 2;
 
+		// Get the scene into view if the application tells us where it is and left the camera alone
+		let parameters = player.camera.parameters();
+		let untouched = parameters.intrinsics == initialParameters.intrinsics
+			&& parameters.extrinsics == initialParameters.extrinsics;
+		if untouched {
+			player.zoomToFit();
+		}
+
 		// Done!
 		tracing::info!("Startup complete.");
 		Ok(player)
 	}
 
+	/// Load the runtime environment from the `ENVIRONMENT.yaml` file next to the executable, falling back to the
+	/// default environment if there is none.
+	#[cfg(not(target_arch="wasm32"))]
+	fn loadEnvironment () -> run::Environment
+	{
+		let path = util::meta::currentExeDir().join("ENVIRONMENT.yaml");
+		match fs::read(path)
+		{
+			Ok(bytes) => run::Environment::deserialize(&bytes).unwrap_or_else(|e| {
+				tracing::warn!("Failed to read ENVIRONMENT.yaml file: {}", e);
+				run::Environment::default()
+			}),
+
+			Err(e) => {
+				if e.kind() != std::io::ErrorKind::NotFound {
+					tracing::warn!("Failed to read ENVIRONMENT.yaml file: {}", e);
+				}
+				run::Environment::default()
+			}
+		}
+	}
+
 	#[cfg(not(target_arch="wasm32"))]
 	pub fn run (applicationFactory: Box<dyn ApplicationFactory>) -> Result<()>
 	{
@@ -562,23 +834,7 @@ impl Player
 		tracing::info!("Platform: {}", util::meta::platformTargetTriple());
 
 		// Set up environment
-		let environment = {
-			let path = util::meta::currentExeDir().join("ENVIRONMENT.yaml");
-			match fs::read(path)
-			{
-				Ok(bytes) => run::Environment::deserialize(&bytes).unwrap_or_else(|e| {
-					tracing::warn!("Failed to read ENVIRONMENT.yaml file: {}", e);
-					run::Environment::default()
-				}),
-
-				Err(e) => {
-					if e.kind() != std::io::ErrorKind::NotFound {
-						tracing::warn!("Failed to read ENVIRONMENT.yaml file: {}", e);
-					}
-					run::Environment::default()
-				}
-			}
-		};
+		let environment = Self::loadEnvironment();
 
 		// Prepare default player icon
 		let icon = image::load_from_memory(util::sourceBytes!("/res/ico/defaultIcon.png"))?;
@@ -766,44 +1022,94 @@ impl Player
 	}
 
 	fn prepareEvents (
-		&self, inputState: &egui::InputState, viewportResponse: &egui::Response, menubarResponse: &egui::Response,
+		&mut self, inputState: &egui::InputState, viewportResponse: &egui::Response, menubarResponse: &egui::Response,
 		sidepanelResponse: &egui::Response, highDpiScaleFactor: f32
 	) -> Vec<InputEvent>
 	{
 		// Pre-allocate event list
 		let mut preparedEvents = Vec::with_capacity(4); // <-- heuristically chosen
 
-		// Mouse wheel
-		let (didntPinch, zoom, amount)
-		 = if     viewportResponse.contains_pointer()
-		      && (inputState.smooth_scroll_delta.x != 0. || inputState.smooth_scroll_delta.y != 0.)
+		// Touches
+		let toViewport = |pos: egui::Pos2| {
+			let pos_egui = (pos-viewportResponse.rect.min) * highDpiScaleFactor;
+			glm::vec2(pos_egui.x, pos_egui.y)
+		};
+		for event in &inputState.events
 		{
-			(true, true, glm::vec2(inputState.smooth_scroll_delta.x, inputState.smooth_scroll_delta.y))
+			let &egui::Event::Touch { id, phase, pos, force, .. } = event else {continue};
+			if phase == egui::TouchPhase::Start && !viewportResponse.rect.contains(pos) {continue};
+			let (id, modifiers, position) = (id.0, inputState.modifiers, toViewport(pos));
+
+			// egui does not tell us the kind of device behind a touch, and many touchscreens report a pressure value
+			// for fingers as well – so we can only guess at a stylus if the user told us to expect one
+			let pressure = force.filter(|_| self.state.penInputEnabled);
+			if pressure.is_some() && !matches!(phase, egui::TouchPhase::End | egui::TouchPhase::Cancel) {
+				self.state.penContacts.insert(id);
+			}
+			else {
+				self.state.penContacts.remove(&id);
+			}
+			preparedEvents.push(match pressure {
+				Some(pressure) => InputEvent::Pen(PenInfo { id, phase, modifiers, position, pressure }),
+				None => InputEvent::Touch(TouchInfo { id, phase, modifiers, position })
+			});
+		}
+		if !inputState.any_touches() {
+			self.state.penContacts.clear();
 		}
-		else
+		let pointerSource = match (inputState.any_touches(), !self.state.penContacts.is_empty()) {
+			(false, _) => InputSource::Mouse,
+			(true, false) => InputSource::Touch,
+			(true, true) => InputSource::Pen
+		};
+
+		// Multi-touch gestures
+		let gesturing = if let Some(touches) = inputState.multi_touch() && viewportResponse.contains_pointer()
 		{
-			// Try pinch zoom next
-			let zoomDelta = inputState.zoom_delta_2d();
-			let forwardPinch =    viewportResponse.contains_pointer() || menubarResponse.contains_pointer()
-			                        || sidepanelResponse.contains_pointer();
-			if forwardPinch && (zoomDelta.x != 1. || zoomDelta.y != 1.) {
-				const PINCH_SENSITIVITY: f32 = 20./3.;
-				(false, true, glm::vec2(
-					PINCH_SENSITIVITY*if zoomDelta.x > 1. { zoomDelta.x } else { -1./zoomDelta.x },
-					PINCH_SENSITIVITY*if zoomDelta.y > 1. { zoomDelta.y } else { -1./zoomDelta.y }
-				))
+			if touches.translation_delta != egui::Vec2::ZERO {
+				preparedEvents.push(InputEvent::MultiTouch(MultiTouchInfo {
+					numTouches: touches.num_touches, modifiers: inputState.modifiers,
+					center: toViewport(touches.center_pos),
+					translation: glm::vec2(touches.translation_delta.x, touches.translation_delta.y)
+				}));
 			}
-			else {
-				// No zooming action at all this frame
-				(true, false, glm::Vec2::zeros())
+			if touches.rotation_delta != 0. {
+				preparedEvents.push(InputEvent::Rotate(RotateInfo {
+					angle: touches.rotation_delta, modifiers: inputState.modifiers
+				}));
 			}
+			true
+		}
+		else {
+			false
 		};
-		if zoom {
-			preparedEvents.push(InputEvent::MouseWheel(MouseWheelInfo { amount, modifiers: inputState.modifiers }));
+
+		// Pinching (either on a touchscreen or on a trackpad)
+		let zoomDelta = inputState.zoom_delta();
+		let forwardPinch =    viewportResponse.contains_pointer() || menubarResponse.contains_pointer()
+		                   || sidepanelResponse.contains_pointer();
+		if forwardPinch && zoomDelta != 1. {
+			preparedEvents.push(InputEvent::Pinch(PinchInfo {
+				zoom: zoomDelta, modifiers: inputState.modifiers,
+				source: if gesturing { InputSource::Touch } else { InputSource::Mouse }
+			}));
 		}
 
+		// Mouse wheel
+		if    viewportResponse.contains_pointer()
+		   && (inputState.smooth_scroll_delta.x != 0. || inputState.smooth_scroll_delta.y != 0.)
+		{
+			preparedEvents.push(InputEvent::MouseWheel(MouseWheelInfo {
+				amount: glm::vec2(inputState.smooth_scroll_delta.x, inputState.smooth_scroll_delta.y),
+				modifiers: inputState.modifiers
+			}));
+		}
+
+		// Region selection
+		let selecting = self.prepareSelection(inputState, viewportResponse, highDpiScaleFactor, &mut preparedEvents);
+
 		// Dragging action
-		if didntPinch && viewportResponse.dragged()
+		if !gesturing && viewportResponse.dragged() && !selecting
 		{
 			let dm = viewportResponse.drag_motion();
 			if dm.length_sq() > 0.
@@ -816,7 +1122,9 @@ impl Player
 						]
 					},
 					modifiers: inputState.modifiers,
-					direction: glm::vec2(dm.x, dm.y)
+					direction: glm::vec2(dm.x, dm.y),
+					position: inputState.pointer.latest_pos().map(toViewport).unwrap_or_default(),
+					source: pointerSource
 				}));
 			}
 		}
@@ -833,7 +1141,7 @@ impl Player
 				egui::PointerButton::Extra1, egui::PointerButton::Extra2
 			]{
 				let clickInfo = ClickInfo {
-					button, modifiers: inputState.modifiers, position: pointerPos
+					button, modifiers: inputState.modifiers, position: pointerPos, source: pointerSource
 				};
 				if viewportResponse.triple_clicked_by(button) {
 					preparedEvents.push(InputEvent::TripleClick(clickInfo));
@@ -851,7 +1159,95 @@ impl Player
 		preparedEvents
 	}
 
-	fn dispatchTranslatedEvent (&mut self, event: &InputEvent) -> bool
+	/// Helper for [`Self::prepareEvents`] that tracks the region the user is drawing while in
+	/// [selection mode](Self::setSelectionTool), emitting [`InputEvent::RegionSelected`] once the drag ends.
+	///
+	/// # Returns
+	///
+	/// `true` if a region is currently being drawn, `false` otherwise.
+	fn prepareSelection (
+		&mut self, inputState: &egui::InputState, viewportResponse: &egui::Response, highDpiScaleFactor: f32,
+		preparedEvents: &mut Vec<InputEvent>
+	) -> bool
+	{
+		let Some(tool) = self.state.selectionTool else {
+			return false;
+		};
+		let pointerPixel = viewportResponse.interact_pointer_pos().map(|pos_egui| {
+			let pos_egui = (pos_egui-viewportResponse.rect.min) * highDpiScaleFactor;
+			glm::vec2(pos_egui.x, pos_egui.y)
+		});
+		let polygon = &mut self.state.selectionPolygon;
+
+		// Start a new region
+		if viewportResponse.drag_started_by(egui::PointerButton::Primary) {
+			polygon.clear();
+			polygon.extend(pointerPixel);
+		}
+		if polygon.is_empty() {
+			return false;
+		}
+
+		// Extend the region
+		if let Some(pixel) = pointerPixel {
+			match tool {
+				view::SelectionTool::Rectangle => {
+					polygon.truncate(1);
+					polygon.push(pixel);
+				},
+				view::SelectionTool::Lasso => if glm::distance(polygon.last().unwrap(), &pixel) >= 2. {
+					polygon.push(pixel);
+				}
+			}
+		}
+
+		// Finish the region
+		if viewportResponse.drag_stopped_by(egui::PointerButton::Primary)
+		{
+			let polygon = std::mem::take(polygon);
+			let viewportDims = self.camera.framebuffer().dims();
+			let pixelCoords = glm::vec2(polygon[0].x as u32, polygon[0].y as u32);
+			let projView = self.camera.projectionAt(pixelCoords) * self.camera.viewAt(pixelCoords);
+			let mode = view::SelectionMode::fromModifiers(&inputState.modifiers);
+			match tool {
+				view::SelectionTool::Rectangle if polygon.len() == 2 => preparedEvents.push(InputEvent::RegionSelected(
+					view::SelectionRegion::rectangle(polygon[0], polygon[1], viewportDims, projView, mode)
+				)),
+				view::SelectionTool::Lasso if polygon.len() > 2 => preparedEvents.push(InputEvent::RegionSelected(
+					view::SelectionRegion::lasso(polygon, viewportDims, projView, mode)
+				)),
+				_ => {}
+			}
+		}
+		true
+	}
+
+	/// Draw the outline of the region the user is currently drawing in [selection mode](Self::setSelectionTool) over
+	/// the main viewport occupying the given rectangle.
+	fn drawSelectionOverlay (&self, painter: &egui::Painter, rect: egui::Rect, pxlsPerPoint: f32)
+	{
+		let (Some(tool), false) = (self.state.selectionTool, self.state.selectionPolygon.is_empty()) else {
+			return;
+		};
+		let toPoints = |pixel: &glm::Vec2| rect.min + egui::vec2(pixel.x, pixel.y)/pxlsPerPoint;
+		let polygon = &self.state.selectionPolygon;
+		let outline = match tool {
+			view::SelectionTool::Rectangle => {
+				let r = egui::Rect::from_two_pos(toPoints(&polygon[0]), toPoints(polygon.last().unwrap()));
+				vec![r.left_top(), r.right_top(), r.right_bottom(), r.left_bottom()]
+			},
+			view::SelectionTool::Lasso => polygon.iter().map(toPoints).collect()
+		};
+		let stroke = painter.ctx().style().visuals.selection.stroke;
+		painter.add(egui::Shape::closed_line(outline, stroke));
+	}
+
+	/// Dispatch the given event to all applications and the active camera interactor.
+	///
+	/// # Returns
+	///
+	/// A tuple of whether a full scene redraw is needed, and whether a receiver closed the event.
+	fn dispatchTranslatedEvent (&mut self, event: &InputEvent) -> (bool, bool)
 	{
 		// Keep track of whether a full scene redraw is needed
 		let mut redraw = false;
@@ -864,7 +1260,7 @@ impl Player
 
 			match outcome {
 				// Event was closed by the receiver
-				EventOutcome::HandledExclusively(redrawRequested) => return redrawRequested,
+				EventOutcome::HandledExclusively(redrawRequested) => return (redrawRequested, true),
 
 				// Event was acted upon but others may react to it too
 				EventOutcome::HandledDontClose(redrawRequested) => redraw |= redrawRequested,
@@ -883,7 +1279,7 @@ impl Player
 
 			match outcome {
 				// Event was closed by the receiver
-				EventOutcome::HandledExclusively(redrawRequested) => return redrawRequested,
+				EventOutcome::HandledExclusively(redrawRequested) => return (redrawRequested, true),
 
 				// Event was acted upon but others may react to it too
 				EventOutcome::HandledDontClose(redrawRequested) => redraw |= redrawRequested,
@@ -899,16 +1295,18 @@ impl Player
 			self.cameraInteractors.putActive(ci);
 
 			match outcome {
-				// Event was handled
-				  EventOutcome::HandledExclusively(redrawRequested)
-				| EventOutcome::HandledDontClose(redrawRequested) => redraw |= redrawRequested,
+				// Event was closed by the interactor
+				EventOutcome::HandledExclusively(redrawRequested) => return (redraw | redrawRequested, true),
+
+				// Event was acted upon but not closed
+				EventOutcome::HandledDontClose(redrawRequested) => redraw |= redrawRequested,
 
 				// Event was ignored
 				EventOutcome::NotHandled => {}
 			}
 		}
 
-		redraw
+		(redraw, false)
 	}
 
 	fn dispatchEvents (&mut self, events: &[egui::Event], complexEvents: &[InputEvent]) -> bool
@@ -917,8 +1315,8 @@ impl Player
 		let translatedEvents =  events.iter().filter_map(|event| {
 			match event
 			{
-				&egui::Event::Key { key, /*physical_key, */pressed, repeat, modifiers, .. }
-				=> Some(InputEvent::Key(KeyInfo { key, pressed, repeat, modifiers })),
+				&egui::Event::Key { key, physical_key: physicalKey, pressed, repeat, modifiers }
+				=> Some(InputEvent::Key(KeyInfo { key, physicalKey, pressed, repeat, modifiers })),
 
 				_ => None
 			}
@@ -929,8 +1327,33 @@ impl Player
 
 		// Dispatch events
 		let mut redraw = false;
-		translatedEvents.for_each(|ref event| redraw |= self.dispatchTranslatedEvent(event));
-		complexEvents.iter().for_each(|event| redraw |= self.dispatchTranslatedEvent(event));
+		translatedEvents.for_each(|ref event| redraw |= self.dispatchTranslatedEvent(event).0);
+		for event in complexEvents
+		{
+			// Raw event first, and only if no one closed it, the actions derived from it
+			let (eventRedraw, closed) = self.dispatchTranslatedEvent(event);
+			redraw |= eventRedraw;
+			if !closed {
+				for action in ActionInfo::fromInputEvent(event, &self.state.bindings) {
+					redraw |= self.dispatchTranslatedEvent(&InputEvent::Action(action)).0;
+				}
+			}
+		}
+
+		// Find out what primary clicks hit in case picking is enabled
+		if self.pickingEnabled {
+			for event in complexEvents {
+				let InputEvent::Click(click) = event else {continue};
+				if click.button != egui::PointerButton::Primary {continue};
+				self.pickAtSurfacePixel_async(click.position, |pick| {
+					let Some(pick) = pick else {return};
+					let mut player = instance::lock();
+					if player.dispatchTranslatedEvent(&InputEvent::Pick(pick)).0 {
+						player.postFullRedraw();
+					}
+				});
+			}
+		}
 		redraw
 	}
 
@@ -1004,11 +1427,13 @@ impl Player
 			renderState.viewingUniforms.upload(&self.context);
 
 			// Create the managed render pass for this global pass
+			let mut colorAttachments = vec![renderState.getMainColorAttachment(Some(&passInfo.clearColor))];
+			if let Some(idAttachment) = renderState.getIdAttachment() {
+				colorAttachments.push(Some(idAttachment));
+			}
 			let desc = wgpu::RenderPassDescriptor {
 				label: Some("CGV__ManagedSceneRenderPass"),
-				color_attachments: &[
-					renderState.getMainColorAttachment(Some(&passInfo.clearColor)),
-				],
+				color_attachments: &colorAttachments,
 				depth_stencil_attachment: renderState.getMainDepthStencilAttachment(Some(passInfo.depthClearValue)),
 				occlusion_query_set: None,
 				timestamp_writes: None,
@@ -1017,6 +1442,7 @@ impl Player
 			let mut renderPass = cmdEncoder.begin_render_pass(&desc);
 
 			// Render the active application (if any)
+			picking::setRenderingApplication(Some(self.applications.active));
 			if let Some(application) = self.applications.active_mut() {
 				application.render(&self.state.context, renderState, &mut renderPass, &passInfo);
 			}
@@ -1025,9 +1451,11 @@ impl Player
 			for idx in 0..self.applications.list.len() {
 				if idx == self.applications.active {continue};
 				let Some(mut app) = self.applications.list[idx].take() else {continue};
+				picking::setRenderingApplication(Some(idx));
 				app.render(&self.context, renderState, &mut renderPass, &passInfo);
 				self.applications.list[idx] = Some(app);
 			}
+			picking::setRenderingApplication(None);
 
 			if let Some(mut callback) = passInfo.completionCallback.take() {
 				callback(&self.context, passNr as u32);
@@ -1066,6 +1494,193 @@ impl Player
 		self.applications.putActive(app);
 	}
 
+	/// Enable or disable object picking. While enabled, the main framebuffer has an
+	/// [object ID attachment](hal::Framebuffer::ids) that renderers write the IDs of the objects they draw into, and
+	/// primary clicks into the viewport additionally produce [`InputEvent::Pick`] events. Since this changes the color
+	/// targets of the managed render passes, the pipelines of all applications get recreated – pipelines used in
+	/// managed render passes must declare their targets via [`RenderState::colorTargetStates`].
+	pub fn setPickingEnabled (&mut self, enabled: bool)
+	{
+		if enabled == self.state.pickingEnabled {return};
+		self.camera.setIdAttachment(&self.state.context, enabled);
+		self.state.pickingEnabled = enabled;
+		for app in self.applications.list.iter_mut().flatten() {
+			app.recreatePipelines(
+				&self.state.context, &self.state.renderSetup, &Self::globalPassesFromCameras(activeCameras!(self))
+			);
+		}
+		self.state.postFullRedraw();
+	}
+
+	/// Check whether [object picking](Self::setPickingEnabled) is currently enabled.
+	pub fn pickingEnabled (&self) -> bool {
+		self.state.pickingEnabled
+	}
+
+	/// Enable or disable pen input. *egui* cannot tell styluses apart from fingers, so by default all touches are
+	/// treated as [finger touches](InputEvent::Touch). While pen input is enabled, touches that report a pressure value
+	/// are treated as [stylus contacts](InputEvent::Pen) instead – which is only appropriate for touchscreens that
+	/// don't report pressure for fingers.
+	pub fn setPenInputEnabled (&mut self, enabled: bool) {
+		self.state.penInputEnabled = enabled;
+		self.state.penContacts.clear();
+	}
+
+	/// Check whether [pen input](Self::setPenInputEnabled) is currently enabled.
+	pub fn penInputEnabled (&self) -> bool {
+		self.state.penInputEnabled
+	}
+
+	/// Reference the [bindings](Bindings) of commands to input chords.
+	#[inline(always)]
+	pub fn bindings (&self) -> &Bindings {
+		&self.state.bindings
+	}
+
+	/// Mutably reference the [bindings](Bindings) of commands to input chords. Changes get persisted across sessions.
+	#[inline(always)]
+	pub fn bindings_mut (&mut self) -> &mut Bindings {
+		&mut self.state.bindings
+	}
+
+	/// Enter or leave selection mode. While a [selection tool](view::SelectionTool) is set, dragging with the primary
+	/// pointer button draws a region over the main viewport instead of producing [`InputEvent::Dragged`] events, and
+	/// applications receive the finished region as an [`InputEvent::RegionSelected`] event. Pass `None` to leave
+	/// selection mode.
+	pub fn setSelectionTool (&mut self, tool: Option<view::SelectionTool>)
+	{
+		self.state.selectionTool = tool;
+		self.state.selectionPolygon.clear();
+		self.state.egui.request_repaint();
+	}
+
+	/// Report the [selection tool](Self::setSelectionTool) currently in use, if any.
+	pub fn selectionTool (&self) -> Option<view::SelectionTool> {
+		self.state.selectionTool
+	}
+
+	/// Reference the [camera animation](view::CameraAnimation) driving the active camera.
+	#[inline(always)]
+	pub fn cameraAnimation (&self) -> &view::CameraAnimation {
+		&self.state.cameraAnimation
+	}
+
+	/// Mutably reference the [camera animation](view::CameraAnimation) driving the active camera. Playback and
+	/// recording take effect from the next frame on. Any ongoing transition onto a recalled
+	/// [view bookmark](Self::recallViewBookmark) gets cancelled, so the two don't fight over the camera.
+	#[inline(always)]
+	pub fn cameraAnimation_mut (&mut self) -> &mut view::CameraAnimation {
+		self.state.egui.request_repaint();
+		self.state.viewBookmarks.cancelTransition();
+		&mut self.state.cameraAnimation
+	}
+
+	/// Move the [camera animation](view::CameraAnimation) to the given position on its timeline, applying the camera
+	/// state there to the active camera. Cancels any ongoing transition onto a recalled view bookmark.
+	pub fn seekCameraAnimation (&mut self, time: f32)
+	{
+		self.state.egui.request_repaint();
+		self.state.viewBookmarks.cancelTransition();
+		self.state.cameraAnimation.seek(time, &mut*self.camera);
+	}
+
+	/// Reference the [view bookmarks](view::ViewBookmarks) of the player.
+	#[inline(always)]
+	pub fn viewBookmarks (&self) -> &view::ViewBookmarks {
+		&self.state.viewBookmarks
+	}
+
+	/// Mutably reference the [view bookmarks](view::ViewBookmarks) of the player. Transitions onto recalled bookmarks
+	/// take effect from the next frame on.
+	#[inline(always)]
+	pub fn viewBookmarks_mut (&mut self) -> &mut view::ViewBookmarks {
+		self.state.egui.request_repaint();
+		&mut self.state.viewBookmarks
+	}
+
+	/// Start animating the active camera onto the [view bookmark](view::ViewBookmarks) with the given index. Stops any
+	/// ongoing playback or recording of the [camera animation](Self::cameraAnimation), so the two don't fight over
+	/// the camera.
+	pub fn recallViewBookmark (&mut self, index: usize)
+	{
+		self.state.egui.request_repaint();
+		if self.state.cameraAnimation.isRecording() {
+			self.state.cameraAnimation.stopRecording(self.camera.parameters());
+		}
+		self.state.cameraAnimation.stop();
+		self.state.viewBookmarks.recall(index, &mut*self.camera);
+	}
+
+	/// Compute the bounding box of the scene, as [reported](Application::sceneBounds) by all applications.
+	///
+	/// # Returns
+	///
+	/// The smallest box enclosing the bounds of all applications, or `None` if no application reported any.
+	pub fn sceneBounds (&self) -> Option<util::math::Aabb> {
+		self.applications.list.iter().flatten().filter_map(|app| app.sceneBounds()).reduce(|a, b| a.merge(&b))
+	}
+
+	/// Move the active camera along its viewing direction such that the whole [scene](Self::sceneBounds) fits into
+	/// view, focusing on its center.
+	///
+	/// # Returns
+	///
+	/// `true` if the camera was moved, `false` if the scene bounds are unknown.
+	pub fn zoomToFit (&mut self) -> bool
+	{
+		let Some(bounds) = self.sceneBounds() else {
+			return false;
+		};
+		self.camera.parameters_mut().fitToBounds(&bounds);
+		self.state.egui.request_repaint();
+		true
+	}
+
+	/// Enable or disable automatic placement of the clipping planes of the active camera as tightly around the
+	/// [scene](Self::sceneBounds) as possible, to make the most of the available depth buffer precision. Enabled by
+	/// default, but only has an effect with applications that report their scene bounds. While in effect, the near and
+	/// far planes can't be set manually, so the camera settings GUI disables the corresponding controls.
+	pub fn setAutoClippingPlanes (&mut self, enabled: bool) {
+		self.state.autoClippingPlanes = enabled;
+		self.state.egui.request_repaint();
+	}
+
+	/// Report whether [automatic clipping planes](Self::setAutoClippingPlanes) are enabled.
+	#[inline(always)]
+	pub fn autoClippingPlanes (&self) -> bool {
+		self.state.autoClippingPlanes
+	}
+
+	/// Adapt the clipping planes of the active camera to the scene bounds, touching the camera only if they actually
+	/// change so it doesn't needlessly recalculate its internal state.
+	fn adaptClippingPlanes (&mut self)
+	{
+		let Some(bounds) = self.sceneBounds() else {
+			return;
+		};
+		let params = self.camera.parameters();
+		let mut intrinsics = params.intrinsics;
+		intrinsics.adaptDepthRange(&params.extrinsics, &bounds);
+		if intrinsics != params.intrinsics {
+			self.camera.parameters_mut().intrinsics = intrinsics;
+		}
+	}
+
+	/// Asynchronously find out which object was rendered at the given pixel of the main viewport. The callback
+	/// receives `None` if nothing was rendered there, or if [picking](Self::setPickingEnabled) is not enabled.
+	pub fn pickAtSurfacePixel_async<Closure: FnOnce(Option<Pick>) + wgpu::WasmNotSend + 'static> (
+		&self, pixelCoords: glm::UVec2, callback: Closure
+	){
+		let dims = self.camera.framebuffer().dims();
+		match self.camera.framebuffer().ids() {
+			Some(ids) if pixelCoords.x < dims.x && pixelCoords.y < dims.y =>
+				ids.readbackTexelAsync(&self.context, pixelCoords, move |texel: Option<[u32; 2]>| {
+					callback(texel.and_then(|texel| Pick::fromTexel(texel, pixelCoords)))
+				}),
+			_ => callback(None)
+		}
+	}
+
 	pub fn getDepthAtSurfacePixel_async<Closure: FnOnce(Option<f32>) + wgpu::WasmNotSend + 'static> (
 		&self, pixelCoords: glm::UVec2, callback: Closure
 	){
@@ -1179,8 +1794,21 @@ impl eframe::App for StaticImpls
 {
 	fn on_exit(&mut self) {instance::reset()}
 
+	fn save (&mut self, storage: &mut dyn eframe::Storage) {
+		let player = lock();
+		eframe::set_value(storage, Bindings::STORAGE_KEY, &player.state.bindings);
+		eframe::set_value(storage, view::ViewBookmarks::STORAGE_KEY, &player.state.viewBookmarks);
+	}
+
 	fn ui (&mut self, ui: &mut egui::Ui, _frame: &mut eframe::Frame)
 	{
+		// Capture the previous frame if recording and take requested screenshots (before locking, as both need to
+		// release the player while waiting for the GPU)
+		#[cfg(not(target_arch="wasm32"))] {
+			Player::advanceFrameRecording();
+			Player::processScreenshotRequest();
+		}
+
 		let mut lock = lock();
 		let player = &mut*lock;
 
@@ -1247,19 +1875,46 @@ impl eframe::App for StaticImpls
 				redrawScene |= player.dispatchEvents(&inputState.events, &complexEvents);
 			}
 
-			// If nobody else did, consume the global [ESC] quit shortcut
+			// If nobody else did, consume the global quit shortcut
 			if   (   response.contains_pointer() || menubarResponse.contains_pointer()
 			      || sidepanelResponse.contains_pointer())
-			   && ui.input_mut(|i| i.consume_shortcut(&player.quitShortcut))
+			   && ui.input_mut(|i| player.state.bindings.consumeKeyPress(Command::Quit, i))
 			{
 				player.exit(ui.ctx());
 			}
 
+			// Same for the zoom to fit shortcut
+			if    response.contains_pointer()
+			   && ui.input_mut(|i| player.state.bindings.consumeKeyPress(Command::ZoomToFit, i))
+			{
+				redrawScene |= player.zoomToFit();
+			}
+
 			// Update camera interactor
 			if let Some(mut ci) = player.cameraInteractors.takeActive() {
 				ci.update(player, Handle(player.cameraInteractors.active));
 				player.cameraInteractors.putActive(ci);
 			}
+
+			// Advance camera animation and bookmark transitions, keeping the scene redrawing continuously while they
+			// are active
+			let dt = player.lastFrameTime();
+			redrawScene |= player.state.cameraAnimation.update(dt, &mut*player.camera);
+			redrawScene |= player.state.viewBookmarks.update(dt, &mut*player.camera);
+			let animating =    player.cameraAnimation.isPlaying() || player.cameraAnimation.isRecording()
+			                || player.viewBookmarks.isTransitioning();
+			if animating != player.animationRedrawRequested {
+				if animating {
+					player.pushContinuousRedrawRequest();
+				}
+				else {
+					player.dropContinuousRedrawRequest();
+				}
+				player.animationRedrawRequested = animating;
+			}
+			if player.autoClippingPlanes {
+				player.adaptClippingPlanes();
+			}
 			if player.camera.update() {
 				redrawScene = true;
 			}
@@ -1267,6 +1922,7 @@ impl eframe::App for StaticImpls
 			// Schedule compositing of the scene view onto the eframe center panel.
 			player.pendingRedraw |= redrawScene;
 			ui.painter().add(egui_wgpu::Callback::new_paint_callback(rect, StaticImpls));
+			player.drawSelectionOverlay(ui.painter(), rect, pxlsPerPoint);
 		});
 	}
 }
@@ -0,0 +1,157 @@
+//////
+//
+// Imports
+//
+
+// Standard library
+use std::{collections::BTreeMap, num::NonZeroU32, sync::{Mutex, atomic::{AtomicU32, AtomicUsize, Ordering}}};
+
+// Local imports
+use crate::*;
+use super::Handle;
+
+
+
+//////
+//
+// Globals
+//
+
+/// Marks that no application is currently rendering.
+const NO_APPLICATION: usize = usize::MAX;
+
+/// The next unused object ID. Starts at *1* since an ID of *0* in the [ID attachment](hal::Framebuffer::ids) denotes
+/// that nothing was rendered.
+static NEXT_OBJECT_ID: AtomicU32 = AtomicU32::new(1);
+
+/// The index of the application whose [`render`](Application::render) method the [`Player`] is currently calling.
+static RENDERING_APPLICATION: AtomicUsize = AtomicUsize::new(NO_APPLICATION);
+
+/// Maps each live object ID to the index of the application that last rendered the object.
+static OWNERS: Mutex<BTreeMap<u32, usize>> = Mutex::new(BTreeMap::new());
+
+
+
+//////
+//
+// Structs
+//
+
+/// Uniquely identifies something that writes into the [object ID attachment](hal::Framebuffer::ids) of the main
+/// framebuffer, typically a renderer instance. Fragment shaders write the [raw value](ObjectId::get) of the ID into the
+/// red channel of the attachment, and the index of the element within the object (e.g. the sphere, the triangle etc.)
+/// into the green channel. IDs are allocated by creating an [`OwnedObjectId`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ObjectId (NonZeroU32);
+impl ObjectId
+{
+	/// Reconstruct an object ID from the raw value found in the ID attachment.
+	///
+	/// # Returns
+	///
+	/// `Some` object ID if the raw value is non-zero, `None` if it indicates that nothing was rendered.
+	pub fn fromRaw (raw: u32) -> Option<Self> {
+		NonZeroU32::new(raw).map(Self)
+	}
+
+	/// The raw value to write into the ID attachment.
+	#[inline(always)]
+	pub fn get (&self) -> u32 {
+		self.0.get()
+	}
+
+	/// The application that last rendered the object, if any.
+	pub fn owner (&self) -> Option<Handle> {
+		owners().get(&self.get()).copied().map(Handle)
+	}
+}
+
+/// An [object ID](ObjectId) allocated for exclusive use by its holder, typically a renderer instance. The ID stops
+/// being attributed to any application once this is dropped.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct OwnedObjectId (ObjectId);
+impl OwnedObjectId
+{
+	/// Allocate a new, globally unique object ID.
+	pub fn new () -> Self {
+		let raw = NEXT_OBJECT_ID.fetch_add(1, Ordering::Relaxed);
+		Self(ObjectId(NonZeroU32::new(raw).expect("object IDs exhausted")))
+	}
+
+	/// The allocated object ID.
+	#[inline(always)]
+	pub fn id (&self) -> ObjectId {
+		self.0
+	}
+
+	/// Record that the object is being rendered by the application the [`Player`] is currently asking to
+	/// [`render`](Application::render), so that [picks](Pick) on the object can be attributed to that application.
+	/// Objects should call this every time they issue draw calls.
+	pub fn markRendered (&self)
+	{
+		let application = RENDERING_APPLICATION.load(Ordering::Relaxed);
+		if application != NO_APPLICATION {
+			owners().insert(self.0.get(), application);
+		}
+	}
+}
+impl Default for OwnedObjectId {
+	fn default () -> Self { Self::new() }
+}
+impl Drop for OwnedObjectId {
+	fn drop (&mut self) {
+		owners().remove(&self.0.get());
+	}
+}
+impl std::ops::Deref for OwnedObjectId {
+	type Target = ObjectId;
+	fn deref (&self) -> &ObjectId { &self.0 }
+}
+
+/// Information about what was rendered at a certain pixel of the main viewport, as obtained by
+/// [`Player::pickAtSurfacePixel_async`].
+#[derive(Debug, Clone, Copy)]
+pub struct Pick
+{
+	/// The application that rendered the picked object, if known.
+	pub application: Option<Handle>,
+
+	/// The picked object, typically identifying a renderer instance.
+	pub object: ObjectId,
+
+	/// The index of the picked element within the object, e.g. the index of the sphere within the data a
+	/// [sphere renderer](renderer::Spheres) draws.
+	pub element: u32,
+
+	/// The pixel coordinates within the main viewport that were picked.
+	pub position: glm::UVec2
+}
+impl Pick
+{
+	/// Decode the texel found in the [ID attachment](hal::Framebuffer::ids) at the given pixel coordinates.
+	///
+	/// # Returns
+	///
+	/// `Some` pick if an object was rendered at the texel, `None` otherwise.
+	pub(crate) fn fromTexel (texel: [u32; 2], position: glm::UVec2) -> Option<Self> {
+		let object = ObjectId::fromRaw(texel[0])?;
+		Some(Self { application: object.owner(), object, element: texel[1], position })
+	}
+}
+
+
+
+//////
+//
+// Functions
+//
+
+/// Lock the owner map, ignoring poisoning since the map can't be left in an inconsistent state.
+fn owners () -> std::sync::MutexGuard<'static, BTreeMap<u32, usize>> {
+	OWNERS.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Report which application (if any) the [`Player`] is currently asking to [`render`](Application::render).
+pub(crate) fn setRenderingApplication (application: Option<usize>) {
+	RENDERING_APPLICATION.store(application.unwrap_or(NO_APPLICATION), Ordering::Relaxed);
+}
@@ -0,0 +1,258 @@
+//////
+//
+// Imports
+//
+
+// Standard library
+use std::path::{Path, PathBuf};
+
+// WGPU API
+use wgpu;
+
+// Local imports
+use crate::*;
+use super::{ImageFormat, instance, headless::readbackImageUnlocked};
+
+
+
+//////
+//
+// Structs
+//
+
+/// Configures a [high-resolution screenshot](Player::takeScreenshot) of the main viewport.
+#[derive(Debug, Clone)]
+pub struct ScreenshotOptions
+{
+	/// The resolution of the resulting image, in pixels. May exceed the maximum texture size of the GPU, in which case
+	/// the image gets rendered in several tiles.
+	pub resolution: glm::UVec2,
+
+	/// The number of rendered samples per pixel along each axis. The scene gets rendered at `supersampling` times the
+	/// resolution and box-filtered down to the final image.
+	pub supersampling: u32,
+
+	/// Whether to clear the background to fully transparent black instead of the clear color of the camera.
+	pub transparentBackground: bool,
+
+	/// An upper limit for the size of the tiles along each axis, in pixels, in addition to the maximum texture size of
+	/// the GPU. Keeps the memory consumed by the offscreen render targets in check.
+	pub maxTileSize: u32
+}
+impl Default for ScreenshotOptions {
+	fn default () -> Self { Self {
+		resolution: glm::vec2(3840, 2160), supersampling: 1, transparentBackground: false, maxTileSize: 4096
+	}}
+}
+
+
+
+//////
+//
+// Functions
+//
+
+/// Box-filter the given rendered tile down by the supersampling factor and write the result into the given output
+/// image at the given offset, discarding whatever lies outside of the output image.
+pub(crate) fn downsampleTile (
+	tile: &image::DynamicImage, supersampling: u32, output: &mut image::DynamicImage, offset: glm::UVec2
+)
+{
+	use egui::ecolor::{linear_f32_from_gamma_u8, gamma_u8_from_linear_f32};
+
+	// Obtain the tile in linear color space
+	let linear = match tile {
+		image::DynamicImage::ImageRgba32F(linear) => linear.clone(),
+		_ => {
+			let lut: [f32; 256] = std::array::from_fn(|i| linear_f32_from_gamma_u8(i as u8));
+			let srgb = tile.to_rgba8();
+			image::Rgba32FImage::from_fn(srgb.width(), srgb.height(), |x, y| {
+				let [r, g, b, a] = srgb.get_pixel(x, y).0;
+				image::Rgba([lut[r as usize], lut[g as usize], lut[b as usize], a as f32 / 255.])
+			})
+		}
+	};
+
+	// Average each block of samples into its output pixel
+	let dims = glm::vec2(
+		(linear.width()/supersampling).min(output.width()-offset.x),
+		(linear.height()/supersampling).min(output.height()-offset.y)
+	);
+	let weight = 1. / (supersampling*supersampling) as f32;
+	for y in 0..dims.y {
+		for x in 0..dims.x
+		{
+			let mut sum = [0f32; 4];
+			for sy in 0..supersampling {
+				for sx in 0..supersampling {
+					let sample = linear.get_pixel(x*supersampling + sx, y*supersampling + sy).0;
+					sum.iter_mut().zip(sample).for_each(|(s, c)| *s += c);
+				}
+			}
+			let [r, g, b, a] = sum.map(|s| s*weight);
+			match output {
+				image::DynamicImage::ImageRgba32F(output) =>
+					output.put_pixel(offset.x+x, offset.y+y, image::Rgba([r, g, b, a])),
+				image::DynamicImage::ImageRgba8(output) => output.put_pixel(offset.x+x, offset.y+y, image::Rgba([
+					gamma_u8_from_linear_f32(r), gamma_u8_from_linear_f32(g), gamma_u8_from_linear_f32(b),
+					(a.clamp(0., 1.)*255.).round() as u8
+				])),
+				_ => unreachable!("screenshot output is always RGBA8 or RGBA32F")
+			}
+		}
+	}
+}
+
+
+
+//////
+//
+// Classes
+//
+
+////
+// Player
+
+impl Player
+{
+	/// Render the scene as currently seen through the main camera of the global player instance into an image of
+	/// arbitrary resolution. The scene gets rendered by a dedicated offscreen camera, leaving the main camera and its
+	/// framebuffer untouched. Resolutions exceeding the [maximum tile size](ScreenshotOptions::maxTileSize) are
+	/// rendered as several tiles, each showing its part of the view frustum at full resolution, which are then stitched
+	/// together.
+	///
+	/// Since the player gets released while waiting for the GPU to finish each tile (so that pending asynchronous
+	/// callbacks can access it), this must not be called while holding the player lock. From inside callbacks, use
+	/// [`Self::requestScreenshot`] instead.
+	///
+	/// # Arguments
+	///
+	/// * `options` – The screenshot configuration.
+	///
+	/// # Returns
+	///
+	/// The screenshot as an 8-bit *sRGB* image if the main framebuffer uses an 8-bit color format, or as a linear
+	/// 32-bit floating point image otherwise.
+	pub fn takeScreenshot (options: &ScreenshotOptions) -> Result<image::DynamicImage>
+	{
+		// Validate options
+		if options.resolution.x < 1 || options.resolution.y < 1 {
+			return Err(anyhow!("invalid screenshot resolution: {}×{}", options.resolution.x, options.resolution.y));
+		}
+		if options.supersampling < 1 {
+			return Err(anyhow!("invalid supersampling factor: {}", options.supersampling));
+		}
+
+		// Determine tiling, making sure tiles span whole output pixels
+		let mut player = instance::lock();
+		let ss = options.supersampling;
+		let fullDims = options.resolution * ss;
+		let maxTileSize = player.context.device().limits().max_texture_dimension_2d.min(options.maxTileSize);
+		if maxTileSize < ss {
+			return Err(anyhow!("supersampling factor {ss} exceeds the maximum tile size of {maxTileSize}"));
+		}
+		let tileSize = (maxTileSize/ss) * ss;
+		let tileDims = glm::vec2(tileSize.min(fullDims.x), tileSize.min(fullDims.y));
+		let numTiles = glm::vec2(fullDims.x.div_ceil(tileDims.x), fullDims.y.div_ceil(tileDims.y));
+		tracing::info!(
+			"Taking {}×{} screenshot in {}×{} tiles of {}×{} pixels", options.resolution.x, options.resolution.y,
+			numTiles.x, numTiles.y, tileDims.x, tileDims.y
+		);
+
+		// Set up an offscreen camera mirroring the main camera, with render targets compatible to its framebuffer
+		let mut camera: Box<dyn view::Camera> = Box::new(view::MonoCamera::new(
+			&player.state.context, &player.state.renderSetup, tileDims,
+			player.camera.framebuffer().color0().descriptor.format,
+			player.state.renderSetup.defaultDepthStencilFormat().into(), Some("CGV__ScreenshotCamera")
+		));
+		camera.setIdAttachment(&player.state.context, player.state.pickingEnabled);
+		*camera.parameters_mut() = *player.camera.parameters();
+		camera.parameters_mut().intrinsics.aspect = options.resolution.x as f32 / options.resolution.y as f32;
+		camera.overrideClearColor(None, Some(if options.transparentBackground {
+			wgpu::Color::TRANSPARENT
+		} else {
+			player.camera.globalPasses().info[0].clearColor
+		}));
+
+		// Render and stitch the tiles
+		let mut output: Option<image::DynamicImage> = None;
+		for ty in 0..numTiles.y {
+			for tx in 0..numTiles.x
+			{
+				// Restrict the projection to the tile (the tile may extend beyond the image at the edges)
+				let origin = glm::vec2(tx, ty).component_mul(&tileDims);
+				let min = glm::vec2(
+					-1. + 2.*origin.x as f32 / fullDims.x as f32,
+					1. - 2.*(origin.y+tileDims.y) as f32 / fullDims.y as f32
+				);
+				let max = glm::vec2(
+					-1. + 2.*(origin.x+tileDims.x) as f32 / fullDims.x as f32,
+					1. - 2.*origin.y as f32 / fullDims.y as f32
+				);
+				camera.setProjectionWindow(Some((min, max)));
+
+				// Render with the offscreen camera standing in for the main camera, and read back while the main
+				// camera is back in place for any callbacks that run in the meantime
+				std::mem::swap(&mut player.camera, &mut camera);
+				player.renderImmediately();
+				std::mem::swap(&mut player.camera, &mut camera);
+				let tile;
+				(tile, player) = readbackImageUnlocked(player, Some(camera.framebuffer().color0()));
+				let tile = tile?;
+				let output = output.get_or_insert_with(|| match tile {
+					image::DynamicImage::ImageRgba32F(_) => image::DynamicImage::ImageRgba32F(
+						image::Rgba32FImage::new(options.resolution.x, options.resolution.y)
+					),
+					_ => image::DynamicImage::ImageRgba8(
+						image::RgbaImage::new(options.resolution.x, options.resolution.y)
+					)
+				});
+				downsampleTile(&tile, ss, output, origin / ss);
+			}
+		}
+		Ok(output.unwrap())
+	}
+
+	/// Take a [high-resolution screenshot](Self::takeScreenshot) and write it to the given file. The same restrictions
+	/// as for [`Self::takeScreenshot`] apply.
+	///
+	/// # Arguments
+	///
+	/// * `options` – The screenshot configuration.
+	/// * `path` – The file to write.
+	/// * `format` – The format to write the file in.
+	pub fn saveScreenshot (options: &ScreenshotOptions, path: impl AsRef<Path>, format: ImageFormat) -> Result<()>
+	{
+		let image = Self::takeScreenshot(options)?;
+		format.save(&image, &path)?;
+		tracing::info!("Wrote screenshot to {}", path.as_ref().display());
+		Ok(())
+	}
+
+	/// Request a [high-resolution screenshot](Self::takeScreenshot) to be written to the given file at the beginning
+	/// of the next frame. Unlike [`Self::saveScreenshot`], this can be called while holding the player lock, e.g. from
+	/// the GUI or other callbacks. Errors get logged. A request that is still pending gets replaced.
+	///
+	/// # Arguments
+	///
+	/// * `options` – The screenshot configuration.
+	/// * `path` – The file to write.
+	/// * `format` – The format to write the file in.
+	pub fn requestScreenshot (&mut self, options: ScreenshotOptions, path: impl Into<PathBuf>, format: ImageFormat)
+	{
+		self.state.pendingScreenshot = Some((options, path.into(), format));
+		self.state.pendingRedraw = true;
+	}
+
+	/// Take the [requested](Self::requestScreenshot) screenshot, if any. Must be called without holding the player
+	/// lock.
+	pub(crate) fn processScreenshotRequest ()
+	{
+		let Some((options, path, format)) = instance::lock().state.pendingScreenshot.take() else {
+			return;
+		};
+		if let Err(err) = Self::saveScreenshot(&options, &path, format) {
+			tracing::error!("Screenshot: {err}");
+		}
+	}
+}
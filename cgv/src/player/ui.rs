@@ -44,9 +44,9 @@ pub(crate) fn menuBar (player: &mut Player, ui: &mut egui::Ui) -> egui::Response
 				ui.menu_button("File", |ui| {
 					ui.style_mut().wrap_mode = Some(egui::TextWrapMode::Extend);
 					#[cfg(not(target_arch="wasm32"))]
-					if ui.add(
-						egui::Button::new("Quit").shortcut_text(ui.ctx().format_shortcut(&player.quitShortcut))
-					).clicked() {
+					if ui.add(egui::Button::new("Quit").shortcut_text(
+						player.bindings().get(player::Command::Quit).map(player::Chord::describe).unwrap_or_default()
+					)).clicked() {
 						player.exit(ui.ctx());
 					}
 					#[cfg(target_arch="wasm32")]
@@ -192,6 +192,20 @@ pub(crate) fn player (player: &mut Player, ui: &mut egui::Ui)
 					}
 					ui.add_space(-4.); ui.label("Depth");
 				}));
+				controlTable.add("Picking", |ui, _| {
+					let mut pickingEnabled = player.pickingEnabled();
+					if ui.add(gui::widget::toggle(&mut pickingEnabled)).clicked() {
+						player.setPickingEnabled(pickingEnabled);
+					}
+				});
+				controlTable.add("Pen input", |ui, _| {
+					let mut penInputEnabled = player.penInputEnabled();
+					if ui.add(gui::widget::toggle(&mut penInputEnabled)).on_hover_text(
+						"Treat touches reporting a pressure value as stylus contacts"
+					).clicked() {
+						player.setPenInputEnabled(penInputEnabled);
+					}
+				});
 				const GAMMA_RANGE: core::ops::RangeInclusive<f32> = 0.25..=7.5;
 				let mut gammaChanged = false;
 				controlTable.add("Gamma", |ui, _| ui.horizontal(|ui|
@@ -239,6 +253,130 @@ pub(crate) fn player (player: &mut Player, ui: &mut egui::Ui)
 			}
 		)
 	);
+
+	// Frame recording
+	#[cfg(not(target_arch="wasm32"))]
+	egui::CollapsingHeader::new("Recording").id_salt("CGV__player_rec")
+		.show(ui, |ui| frameRecording(player, ui));
+
+	// High-resolution screenshots
+	#[cfg(not(target_arch="wasm32"))]
+	egui::CollapsingHeader::new("Screenshot").id_salt("CGV__player_shot")
+		.show(ui, |ui| screenshot(player, ui));
+}
+
+/// Draw (and act upon) the controls for [recording frames](Player::startFrameRecording) of the main viewport.
+#[cfg(not(target_arch="wasm32"))]
+fn frameRecording (player: &mut Player, ui: &mut egui::Ui)
+{
+	use player::{FrameRecordingOptions, ImageFormat, StreamFormat};
+
+	// Keep the options for the next recording in the UI memory
+	let optionsId = egui::Id::new("CGV__player_rec_options");
+	let mut options: FrameRecordingOptions = ui.data_mut(|d| d.get_temp_mut_or_default::<FrameRecordingOptions>(
+		optionsId
+	).clone());
+	let recording = player.frameRecorder().is_some();
+
+	gui::layout::ControlTableLayouter::new(ui).layout(ui, "CGV__player_rec_layout", |recUi|
+	{
+		recUi.add("Directory", |ui, idealSize| {
+			let mut directory = options.directory.to_string_lossy().into_owned();
+			if ui.add_enabled(!recording, egui::TextEdit::singleline(&mut directory).desired_width(idealSize))
+				.changed()
+			{
+				options.directory = directory.into();
+			}
+		});
+		recUi.add("Framerate", |ui, _| ui.add_enabled(
+			!recording, egui::DragValue::new(&mut options.framerate).range(1..=240).suffix(" fps")
+		));
+		recUi.add("Fixed timestep", |ui, _| ui.add_enabled(!recording, egui::Checkbox::without_text(
+			&mut options.fixedTimestep
+		)).on_hover_text("Advance time by exactly one frame per rendered frame, so no frames get dropped"));
+		recUi.add("Images", |ui, idealSize| ui.add_enabled_ui(!recording, |ui|
+			egui::ComboBox::from_id_salt("CGV__player_rec_img")
+				.selected_text(options.images.map_or("None", |f| if f == ImageFormat::PNG {"PNG"} else {"EXR"}))
+				.width(idealSize)
+				.show_ui(ui, |ui| {
+					ui.selectable_value(&mut options.images, None, "None");
+					ui.selectable_value(&mut options.images, Some(ImageFormat::PNG), "PNG");
+					ui.selectable_value(&mut options.images, Some(ImageFormat::EXR), "EXR");
+				})
+		));
+		recUi.add("Stream", |ui, idealSize| ui.add_enabled_ui(!recording, |ui|
+			egui::ComboBox::from_id_salt("CGV__player_rec_stream")
+				.selected_text(match options.stream {
+					None => "None",
+					Some(StreamFormat::Y4M) => "Y4M",
+					Some(StreamFormat::RawRgba) => "Raw RGBA"
+				})
+				.width(idealSize)
+				.show_ui(ui, |ui| {
+					ui.selectable_value(&mut options.stream, None, "None");
+					ui.selectable_value(&mut options.stream, Some(StreamFormat::Y4M), "Y4M");
+					ui.selectable_value(&mut options.stream, Some(StreamFormat::RawRgba), "Raw RGBA");
+				})
+		));
+		recUi.add("Camera path", |ui, _| ui.add_enabled(!recording, egui::Checkbox::new(
+			&mut options.playCameraPath, "play"
+		)).on_hover_text("Play back the camera path from its start and stop recording when it ends"));
+		recUi.add("", |ui, _| ui.horizontal(|ui|
+		{
+			if ui.button(if recording {"⏹ Stop"} else {"⏺ Start"}).clicked() {
+				let result = if recording {
+					player.stopFrameRecording().map(|_| ())
+				}
+				else {
+					player.startFrameRecording(options.clone())
+				};
+				if let Err(err) = result {
+					tracing::error!("Frame recording: {err}");
+				}
+			}
+			if let Some(recorder) = player.frameRecorder() {
+				ui.label(format!("{} frames", recorder.numFrames()));
+			}
+		}));
+	});
+
+	ui.data_mut(|d| d.insert_temp(optionsId, options));
+}
+
+/// Draw (and act upon) the controls for taking [high-resolution screenshots](Player::takeScreenshot).
+#[cfg(not(target_arch="wasm32"))]
+fn screenshot (player: &mut Player, ui: &mut egui::Ui)
+{
+	use player::{ScreenshotOptions, ImageFormat};
+
+	// Keep the options and target file for the next screenshot in the UI memory
+	let optionsId = egui::Id::new("CGV__player_shot_options");
+	let (mut options, mut filename) = ui.data_mut(|d| d.get_temp_mut_or_insert_with(
+		optionsId, || (ScreenshotOptions::default(), String::from("screenshot.png"))
+	).clone());
+
+	gui::layout::ControlTableLayouter::new(ui).layout(ui, "CGV__player_shot_layout", |shotUi|
+	{
+		shotUi.add("Resolution", |ui, _| ui.horizontal(|ui| {
+			ui.add(egui::DragValue::new(&mut options.resolution.x).range(1..=65536).suffix(" px"));
+			ui.label("×");
+			ui.add(egui::DragValue::new(&mut options.resolution.y).range(1..=65536).suffix(" px"));
+		}));
+		shotUi.add("Supersampling", |ui, _| ui.add(
+			egui::DragValue::new(&mut options.supersampling).range(1..=8).prefix("×")
+		).on_hover_text("Number of samples per pixel along each axis"));
+		shotUi.add("Transparent", |ui, _| ui.add(egui::Checkbox::without_text(&mut options.transparentBackground)));
+		shotUi.add("File", |ui, idealSize| ui.add(
+			egui::TextEdit::singleline(&mut filename).desired_width(idealSize)
+		).on_hover_text("Written as OpenEXR if the extension is .exr, as PNG otherwise"));
+		shotUi.add("", |ui, _| if ui.button("📷 Take").clicked()
+		{
+			let format = if filename.to_lowercase().ends_with(".exr") {ImageFormat::EXR} else {ImageFormat::PNG};
+			player.requestScreenshot(options.clone(), filename.clone(), format);
+		});
+	});
+
+	ui.data_mut(|d| d.insert_temp(optionsId, (options, filename)));
 }
 
 /// Draw (and act upon) the side panel GUI for configuring and controlling the scene view, namely by manipulating.
@@ -277,6 +415,46 @@ pub(crate) fn view (player: &mut Player, ui: &mut egui::Ui)
 					&mut sel, 0, player.camera.name()
 				))
 		);
+
+		// selectionTool
+		let mut selectionTool = player.selectionTool();
+		cameraUi.add("Selection", |ui, idealSize|
+			egui::ComboBox::from_id_salt("CGV_view_sel")
+				.selected_text(match selectionTool {
+					None => "None",
+					Some(view::SelectionTool::Rectangle) => "Rectangle",
+					Some(view::SelectionTool::Lasso) => "Lasso"
+				})
+				.width(idealSize)
+				.show_ui(ui, |ui| {
+					ui.selectable_value(&mut selectionTool, None, "None");
+					ui.selectable_value(&mut selectionTool, Some(view::SelectionTool::Rectangle), "Rectangle");
+					ui.selectable_value(&mut selectionTool, Some(view::SelectionTool::Lasso), "Lasso");
+				})
+		);
+		if selectionTool != player.selectionTool() {
+			player.setSelectionTool(selectionTool);
+		}
+
+		// Scene bounds
+		let hasBounds = player.sceneBounds().is_some();
+		cameraUi.add("Scene", |ui, _| ui.horizontal(|ui| {
+			let shortcut = player.bindings().get(player::Command::ZoomToFit).map(player::Chord::describe);
+			if ui.add_enabled(hasBounds, egui::Button::new("Zoom to fit"))
+				.on_hover_text(shortcut.unwrap_or_default())
+				.on_disabled_hover_text("The application does not report its scene bounds")
+				.clicked()
+			{
+				player.zoomToFit();
+			}
+			let mut autoClip = player.autoClippingPlanes();
+			if ui.add_enabled(hasBounds, egui::Checkbox::new(&mut autoClip, "auto clipping"))
+				.on_hover_text("Keep the near and far planes tight around the scene for best depth precision")
+				.changed()
+			{
+				player.setAutoClippingPlanes(autoClip);
+			}
+		}));
 	});
 
 	// Settings from active camera and interactor
@@ -285,6 +463,331 @@ pub(crate) fn view (player: &mut Player, ui: &mut egui::Ui)
 		egui::CollapsingHeader::new("Interactor settings").id_salt("CGV_view_inter_s")
 			.show(ui, |ui| ci.ui(&mut*player.camera, ui));
 	}
+	let depthRangeLocked = player.autoClippingPlanes() && player.sceneBounds().is_some();
 	egui::CollapsingHeader::new("Active camera settings").id_salt("CGV_view_act_s")
-		.show(ui, |ui| CameraParameters::ui(&mut*player.camera, ui));
+		.show(ui, |ui| CameraParameters::ui(&mut*player.camera, ui, depthRangeLocked));
+
+	// View bookmarks
+	egui::CollapsingHeader::new("Bookmarks").id_salt("CGV_view_bkmk")
+		.show(ui, |ui| viewBookmarks(player, ui));
+
+	// Camera path animation
+	egui::CollapsingHeader::new("Camera path").id_salt("CGV_view_anim")
+		.show(ui, |ui| cameraPath(player, ui));
+
+	// Input bindings
+	egui::CollapsingHeader::new("Bindings").id_salt("CGV_view_bind")
+		.show(ui, |ui| bindings(player, ui));
+}
+
+/// Draw (and act upon) the manager of the [`view::ViewBookmarks`] of the player.
+fn viewBookmarks (player: &mut Player, ui: &mut egui::Ui)
+{
+	// Keep the pasted view text and the import/export file name in the UI memory
+	let pastedId = egui::Id::new("CGV_view_bkmk_paste");
+	let mut pasted = ui.data_mut(|d| d.get_temp_mut_or_default::<String>(pastedId).clone());
+	#[cfg(not(target_arch="wasm32"))]
+	let filenameId = egui::Id::new("CGV_view_bkmk_file");
+	#[cfg(not(target_arch="wasm32"))]
+	let mut filename = ui.data_mut(|d| d.get_persisted_mut_or_insert_with(
+		filenameId, || String::from("views.yaml")
+	).clone());
+
+	// List of bookmarks
+	let (mut recall, mut remove) = (None, None);
+	for (index, bookmark) in player.state.viewBookmarks.bookmarks_mut().iter_mut().enumerate()
+	{
+		ui.horizontal(|ui| {
+			if ui.button("👁").on_hover_text("Go to view").clicked() {
+				recall = Some(index);
+			}
+			if ui.button("📋").on_hover_text("Copy view to clipboard").clicked() {
+				ui.ctx().copy_text(bookmark.toText());
+			}
+			if ui.button("🗑").on_hover_text("Remove").clicked() {
+				remove = Some(index);
+			}
+			ui.add(egui::TextEdit::singleline(&mut bookmark.name).desired_width(ui.available_width()));
+		});
+	}
+	if let Some(index) = recall {
+		player.recallViewBookmark(index);
+	}
+	if let Some(index) = remove {
+		player.viewBookmarks_mut().bookmarks_mut().remove(index);
+	}
+
+	gui::layout::ControlTableLayouter::new(ui).layout(ui, "CGV__BookmarksUi", |bookmarksUi|
+	{
+		// Adding the current view
+		bookmarksUi.add("", |ui, _| if ui.button("Add current view").clicked() {
+			let name = format!("View {}", player.viewBookmarks().bookmarks().len()+1);
+			let bookmark = view::ViewBookmark::new(name, player.camera.parameters());
+			player.viewBookmarks_mut().add(bookmark);
+		});
+
+		// Adding a view shared as text
+		bookmarksUi.add("Paste", |ui, idealSize| ui.vertical(|ui|
+		{
+			ui.add(egui::TextEdit::multiline(&mut pasted).desired_rows(2).desired_width(idealSize)
+				.hint_text("view copied to the clipboard"));
+			if ui.add_enabled(!pasted.is_empty(), egui::Button::new("Add pasted view")).clicked() {
+				match view::ViewBookmark::fromText(&pasted) {
+					Ok(bookmark) => { player.viewBookmarks_mut().add(bookmark); pasted.clear(); },
+					Err(err) => tracing::error!("Failed to parse pasted view: {err}")
+				}
+			}
+		}));
+
+		// Import and export
+		#[cfg(not(target_arch="wasm32"))]
+		bookmarksUi.add("File", |ui, idealSize| ui.vertical(|ui|
+		{
+			ui.add(egui::TextEdit::singleline(&mut filename).desired_width(idealSize));
+			ui.horizontal(|ui| {
+				if ui.button("Export").clicked() && let Err(err) = player.viewBookmarks().save(&filename) {
+					tracing::error!("Failed to export view bookmarks to `{filename}`: {err}");
+				}
+				if ui.button("Import").clicked() {
+					match view::ViewBookmarks::load(&filename) {
+						Ok(bookmarks) => player.viewBookmarks_mut().append(bookmarks),
+						Err(err) => tracing::error!("Failed to import view bookmarks from `{filename}`: {err}")
+					}
+				}
+			});
+		}));
+	});
+
+	ui.data_mut(|d| {
+		d.insert_temp(pastedId, pasted);
+		#[cfg(not(target_arch="wasm32"))]
+		d.insert_persisted(filenameId, filename);
+	});
+}
+
+/// Draw (and act upon) the timeline controls of the [`view::CameraAnimation`] driving the active camera.
+fn cameraPath (player: &mut Player, ui: &mut egui::Ui)
+{
+	// Persist the recording interval and path file name in the UI memory
+	let intervalId = egui::Id::new("CGV_view_anim_interval");
+	let mut interval = ui.data_mut(|d| *d.get_persisted_mut_or(intervalId, 0.25f32));
+	#[cfg(not(target_arch="wasm32"))]
+	let filenameId = egui::Id::new("CGV_view_anim_file");
+	#[cfg(not(target_arch="wasm32"))]
+	let mut filename = ui.data_mut(|d| d.get_persisted_mut_or_insert_with(
+		filenameId, || String::from("camera_path.yaml")
+	).clone());
+
+	gui::layout::ControlTableLayouter::new(ui).layout(ui, "CGV__CameraPathUi", |pathUi|
+	{
+		// Keyframe management
+		pathUi.add("Keyframes", |ui, _| ui.horizontal(|ui|
+		{
+			ui.label(format!("{}", player.cameraAnimation().path().keyframes().len()));
+			if ui.button("Add").on_hover_text("Append the current view 1s after the last keyframe").clicked() {
+				let parameters = *player.camera.parameters();
+				player.cameraAnimation_mut().appendKeyframe(&parameters, 1.);
+			}
+			if ui.button("Clear").clicked() {
+				player.cameraAnimation_mut().setPath(view::CameraPath::new());
+			}
+		}));
+
+		// Timeline scrubbing
+		let (start, end) = (player.cameraAnimation().path().startTime(), player.cameraAnimation().path().endTime());
+		let mut time = player.cameraAnimation().time();
+		pathUi.add("Time", |ui, _|
+			if ui.add_enabled(end > start, egui::Slider::new(&mut time, start..=end).suffix(" s")).changed() {
+				player.seekCameraAnimation(time);
+			}
+		);
+
+		// Playback
+		pathUi.add("Playback", |ui, _| ui.horizontal(|ui|
+		{
+			let playing = player.cameraAnimation().isPlaying();
+			if ui.button("⏮").on_hover_text("Rewind").clicked() {
+				player.seekCameraAnimation(start);
+			}
+			if ui.button(if playing {"⏸"} else {"▶"}).on_hover_text(if playing {"Pause"} else {"Play"}).clicked() {
+				if playing {
+					player.cameraAnimation_mut().pause();
+				}
+				else {
+					player.cameraAnimation_mut().play();
+				}
+			}
+			ui.checkbox(&mut player.state.cameraAnimation.looping, "loop");
+			ui.add(egui::DragValue::new(&mut player.state.cameraAnimation.speed)
+				.range(0.0625..=16.).speed(0.03125).prefix("×"));
+		}));
+
+		// Recording
+		pathUi.add("Recording", |ui, _| ui.horizontal(|ui|
+		{
+			let recording = player.cameraAnimation().isRecording();
+			if ui.selectable_label(recording, "⏺ Record")
+				.on_hover_text("Append keyframes following the camera while navigating").clicked()
+			{
+				let parameters = *player.camera.parameters();
+				if recording {
+					player.cameraAnimation_mut().stopRecording(&parameters);
+				}
+				else {
+					player.cameraAnimation_mut().startRecording(&parameters, interval);
+				}
+			}
+			ui.add(egui::DragValue::new(&mut interval).range(0.03125..=4.).speed(0.0078125).suffix(" s"))
+				.on_hover_text("Time between recorded keyframes");
+		}));
+
+		// Saving and loading
+		#[cfg(not(target_arch="wasm32"))]
+		pathUi.add("File", |ui, idealSize| ui.vertical(|ui|
+		{
+			ui.add(egui::TextEdit::singleline(&mut filename).desired_width(idealSize));
+			ui.horizontal(|ui| {
+				if ui.button("Save").clicked() && let Err(err) = player.cameraAnimation().path().save(&filename) {
+					tracing::error!("Failed to save camera path to `{filename}`: {err}");
+				}
+				if ui.button("Load").clicked() {
+					match view::CameraPath::load(&filename) {
+						Ok(path) => player.cameraAnimation_mut().setPath(path),
+						Err(err) => tracing::error!("Failed to load camera path from `{filename}`: {err}")
+					}
+				}
+			});
+		}));
+	});
+
+	ui.data_mut(|d| {
+		d.insert_persisted(intervalId, interval);
+		#[cfg(not(target_arch="wasm32"))]
+		d.insert_persisted(filenameId, filename);
+	});
+}
+
+/// Draw (and act upon) the editor for the [`player::Bindings`] of commands to input chords.
+fn bindings (player: &mut Player, ui: &mut egui::Ui)
+{
+	use player::{Command, Chord, Trigger};
+
+	/// Local helper for editing the modifiers of a chord.
+	fn modifierToggles (ui: &mut egui::Ui, modifiers: &mut egui::Modifiers) -> bool {
+		let ctrlName = if cfg!(target_os="macos") {"Cmd"} else {"Ctrl"};
+		  ui.checkbox(&mut modifiers.shift, "Shift").changed()
+		| ui.checkbox(&mut modifiers.command, ctrlName).changed()
+		| ui.checkbox(&mut modifiers.alt, "Alt").changed()
+	}
+
+	// Capture the next key press if the user is currently re-binding a key command. Any key can be captured (even
+	// Escape, which quits by default), so cancelling is done via a button instead.
+	if let Some(command) = player.capturingBinding {
+		let captured = ui.input_mut(|i| {
+			let mut captured = None;
+			i.events.retain(|event| match event {
+				&egui::Event::Key { key, physical_key, pressed: true, modifiers, .. } if captured.is_none() => {
+					captured = Some((key, physical_key, modifiers));
+					false
+				},
+				_ => true
+			});
+			captured
+		});
+		if let Some((key, physicalKey, modifiers)) = captured {
+			// Keep binding by physical location for commands that do so by default
+			let trigger = match (command.defaultChord().trigger, physicalKey) {
+				(Trigger::PhysicalKey(_), Some(physicalKey)) => Trigger::PhysicalKey(physicalKey),
+				_ => Trigger::Key(key)
+			};
+			player.bindings_mut().set(command, Some(Chord::new(trigger, modifiers)));
+			player.capturingBinding = None;
+		}
+	}
+
+	gui::layout::ControlTableLayouter::new(ui).layout(ui, "CGV__BindingsUi", |bindingsUi|
+	{
+		/// The width of the button next to each binding editor.
+		const SIDE_BUTTON_WIDTH: f32 = 56.;
+
+		for command in Command::ALL
+		{
+			let bound = player.bindings().get(command).copied();
+			let capturing = player.capturingBinding == Some(command);
+			let mut update = None;
+			bindingsUi.add(command.title(), |ui, idealSize|
+			{
+				let height = ui.spacing().interact_size.y;
+				let editorWidth = (idealSize - ui.spacing().item_spacing.x - SIDE_BUTTON_WIDTH).max(0.);
+				let default = command.defaultChord();
+				match bound.unwrap_or(default).trigger
+				{
+					Trigger::Key(_) | Trigger::PhysicalKey(_) => {
+						let text = if capturing { "press key…".to_owned() } else {
+							bound.map_or_else(|| "unbound".to_owned(), |chord| chord.describe())
+						};
+						if ui.add_sized(egui::vec2(editorWidth, height), egui::Button::selectable(capturing, text))
+							.on_hover_text("Click, then press the new key").clicked()
+						{
+							player.capturingBinding = if capturing { None } else { Some(command) };
+						}
+					},
+
+					// Unbound commands of the other kinds can be bound again by restoring their default
+					_ if bound.is_none() => if ui.add_sized(
+						egui::vec2(editorWidth, height), egui::Button::new("unbound")
+					).on_hover_text("Click to restore the default binding").clicked() {
+						update = Some(Some(default));
+					},
+
+					Trigger::Pointer(mut button) => {
+						use egui::PointerButton;
+						let mut chord = bound.unwrap();
+						let mut changed = false;
+						egui::ComboBox::from_id_salt(("CGV_view_bind_btn", command))
+							.selected_text(format!("{button:?}"))
+							.show_ui(ui, |ui| for candidate in [
+								PointerButton::Primary, PointerButton::Secondary, PointerButton::Middle,
+								PointerButton::Extra1, PointerButton::Extra2
+							] {
+								changed |= ui.selectable_value(
+									&mut button, candidate, format!("{candidate:?}")
+								).changed();
+							});
+						chord.trigger = Trigger::Pointer(button);
+						if changed | modifierToggles(ui, &mut chord.modifiers) {
+							update = Some(Some(chord));
+						}
+					},
+
+					Trigger::ModifiersOnly => {
+						let mut chord = bound.unwrap();
+						if modifierToggles(ui, &mut chord.modifiers) {
+							update = Some(Some(chord));
+						}
+					}
+				}
+
+				// Cancel capturing, or unbind the command
+				let buttonSize = egui::vec2(SIDE_BUTTON_WIDTH, height);
+				if capturing {
+					if ui.add(egui::Button::new("Cancel").min_size(buttonSize)).clicked() {
+						player.capturingBinding = None;
+					}
+				}
+				else if ui.add_enabled(bound.is_some(), egui::Button::new("Unbind").min_size(buttonSize))
+					.on_hover_text("Remove the binding, leaving the command without any input").clicked()
+				{
+					update = Some(None);
+				}
+			});
+			if let Some(chord) = update {
+				player.bindings_mut().set(command, chord);
+			}
+		}
+		bindingsUi.add("", |ui, _| if ui.button("Reset to defaults").clicked() {
+			player.bindings_mut().resetToDefaults();
+			player.capturingBinding = None;
+		});
+	});
 }
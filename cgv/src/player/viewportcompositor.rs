@@ -26,10 +26,7 @@ pub(crate) struct ViewportCompositor
 	pub(crate) invGamma_all: f32,
 	pub(crate) invGamma: glm::Vec3,
 	pub(crate) gammaUniform: hal::UniformGroup<glm::Vec3>,
-	texBindGroupName: Option<String>,
-	sampler: wgpu::Sampler,
-	texBindGroupLayout: wgpu::BindGroupLayout,
-	texBindGroup: wgpu::BindGroup,
+	texBindGroup: hal::TextureBindGroup,
 	pipeline: wgpu::RenderPipeline
 }
 impl ViewportCompositor
@@ -56,59 +53,24 @@ impl ViewportCompositor
 			anyhow!(msg)
 		})?;
 
-		// ToDo: introduce a sampler library and put this there
-		let sampler = context.device().create_sampler(&wgpu::SamplerDescriptor {
-			address_mode_u: wgpu::AddressMode::ClampToEdge,
-			address_mode_v: wgpu::AddressMode::ClampToEdge,
-			address_mode_w: wgpu::AddressMode::ClampToEdge,
-			mag_filter: wgpu::FilterMode::Nearest,
-			min_filter: wgpu::FilterMode::Nearest,
-			mipmap_filter: wgpu::MipmapFilterMode::Nearest,
-			..Default::default()
-		});
-
-		let texBindGroupLayout = context.device().create_bind_group_layout(
-			&wgpu::BindGroupLayoutDescriptor {
-				label: util::concatIfSome(&name, "_texBindGroupLayout").as_deref(),
-				entries: &[
-					wgpu::BindGroupLayoutEntry {
-						binding: 0,
-						visibility: wgpu::ShaderStages::FRAGMENT,
-						ty: wgpu::BindingType::Texture {
-							multisampled: false,
-							view_dimension: wgpu::TextureViewDimension::D2,
-							sample_type: wgpu::TextureSampleType::Float { filterable: true },
-						},
-						count: None,
-					},
-					wgpu::BindGroupLayoutEntry {
-						binding: 1,
-						visibility: wgpu::ShaderStages::FRAGMENT,
-						ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::NonFiltering),
-						count: None,
-					},
-				]
-			}
-		);
-		let texBindGroupName = util::concatIfSome(&name, "_texBindGroup");
-		let texBindGroup = context.device().create_bind_group(&wgpu::BindGroupDescriptor {
-			label: texBindGroupName.as_deref(),
-			layout: &texBindGroupLayout,
-			entries: &[
-				wgpu::BindGroupEntry {
-					binding: 0,
-					resource: wgpu::BindingResource::TextureView(&source.view()),
-				},
-				wgpu::BindGroupEntry {
-					binding: 1,
-					resource: wgpu::BindingResource::Sampler(&sampler),
+		let texBindGroup = hal::TextureBindGroup::create(
+			context, wgpu::ShaderStages::FRAGMENT, &[hal::SampledTexture::withSampler(
+				source, wgpu::SamplerDescriptor {
+					address_mode_u: wgpu::AddressMode::ClampToEdge,
+					address_mode_v: wgpu::AddressMode::ClampToEdge,
+					address_mode_w: wgpu::AddressMode::ClampToEdge,
+					mag_filter: wgpu::FilterMode::Nearest,
+					min_filter: wgpu::FilterMode::Nearest,
+					mipmap_filter: wgpu::MipmapFilterMode::Nearest,
+					..Default::default()
 				}
-			]
-		});
+			)],
+			util::concatIfSome(&name, "_tex").as_deref()
+		)?;
 
 		let pipelineLayout = context.device().create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
 			label: util::concatIfSome(&name, "_pipelineLayout").as_deref(),
-			bind_group_layouts: &[Some(&texBindGroupLayout), Some(&gammaUniform.bindGroupLayout)],
+			bind_group_layouts: &[Some(&texBindGroup.bindGroupLayout), Some(&gammaUniform.bindGroupLayout)],
 			immediate_size: 0
 		});
 
@@ -142,30 +104,16 @@ impl ViewportCompositor
 			cache: None
 		});
 
-		Ok(Self { invGamma_all, invGamma, gammaUniform, texBindGroupName, sampler, texBindGroupLayout, texBindGroup, pipeline })
+		Ok(Self { invGamma_all, invGamma, gammaUniform, texBindGroup, pipeline })
 	}
 
-	pub fn updateSource (&mut self, context: &Context, source: &hal::Texture)
-	{
-		self.texBindGroup = context.device().create_bind_group(&wgpu::BindGroupDescriptor {
-			label: self.texBindGroupName.as_deref(),
-			layout: &self.texBindGroupLayout,
-			entries: &[
-				wgpu::BindGroupEntry {
-					binding: 0,
-					resource: wgpu::BindingResource::TextureView(&source.view()),
-				},
-				wgpu::BindGroupEntry {
-					binding: 1,
-					resource: wgpu::BindingResource::Sampler(&self.sampler),
-				}
-			]
-		});
+	pub fn updateSource (&mut self, context: &Context, source: &hal::Texture) {
+		self.texBindGroup.rebind(context, &[source]);
 	}
 
 	pub fn composit (&self, renderPass: &mut wgpu::RenderPass) {
 		renderPass.set_pipeline(&self.pipeline);
-		renderPass.set_bind_group(0, &self.texBindGroup, &[]);
+		renderPass.set_bind_group(0, &self.texBindGroup.bindGroup, &[]);
 		renderPass.set_bind_group(1, &self.gammaUniform.bindGroup, &[]);
 		renderPass.draw(0..4, 0..1);
 	}
@@ -0,0 +1,253 @@
+//////
+//
+// Imports
+//
+
+// Standard library
+use std::path::{Path, PathBuf};
+
+// Local imports
+use crate::*;
+use player::{HeadlessOptions, Shot};
+
+
+
+//////
+//
+// Globals
+//
+
+/// Name of the environment variable that, when set, makes [`RegressionTest`] overwrite its reference images with the
+/// current output instead of comparing against them.
+pub const UPDATE_REFERENCES_VAR: &str = "CGV_UPDATE_REFERENCES";
+
+
+
+//////
+//
+// Structs
+//
+
+/// How much a rendered image may deviate from its reference before a [`RegressionTest`] fails.
+#[derive(Debug, Clone, Copy)]
+pub struct Tolerance
+{
+	/// The largest absolute difference of any 8-bit color channel for which a pixel is still considered matching.
+	pub perChannel: u8,
+
+	/// The fraction of pixels (in `[0, 1]`) that may fail to match, e.g. to accommodate rasterization differences along
+	/// edges between adapters.
+	pub maxFailingFraction: f32
+}
+impl Default for Tolerance {
+	fn default () -> Self {
+		Self { perChannel: 2, maxFailingFraction: 0. }
+	}
+}
+
+/// The result of [comparing](compare) an image against its reference.
+pub struct Comparison
+{
+	/// The number of pixels that exceeded the [per-channel tolerance](Tolerance::perChannel).
+	pub numFailingPixels: usize,
+
+	/// The total number of pixels compared.
+	pub numPixels: usize,
+
+	/// The largest absolute difference of any color channel encountered.
+	pub maxDifference: u8,
+
+	/// Visualization of the differences – failing pixels are shown in red with an intensity proportional to their
+	/// difference, matching pixels as a darkened grayscale version of the reference.
+	pub diff: image::RgbaImage
+}
+impl Comparison
+{
+	/// Check whether the comparison is within the given tolerance.
+	pub fn passes (&self, tolerance: &Tolerance) -> bool {
+		self.numFailingPixels as f64 <= tolerance.maxFailingFraction as f64 * self.numPixels as f64
+	}
+}
+
+/// A set of images to be rendered by the [headless](Player::renderHeadless) player and checked against references
+/// stored as `<name>_<index>.png` in a given directory. When an image does not match, the rendered image and a
+/// [difference visualization](Comparison::diff) get written to the [output directory](Self::outputDirectory) as
+/// `<name>_<index>.actual.png` and `<name>_<index>.diff.png`. Missing references get recorded from the current output
+/// into the output directory as well (failing the test so the new reference can be inspected before copying it over),
+/// and setting the environment variable [`UPDATE_REFERENCES_VAR`] re-records all references in place.
+pub struct RegressionTest
+{
+	name: String,
+	referenceDirectory: PathBuf,
+	outputDirectory: PathBuf,
+	options: HeadlessOptions,
+	tolerance: Tolerance,
+	shots: Vec<Shot>
+}
+impl RegressionTest
+{
+	/// Create a test with the given name, storing its references in the given directory. By default, a small image is
+	/// rendered using the software fallback adapter of *WGPU*, and output is written to `regression` inside the *Cargo*
+	/// target directory (`$CARGO_TARGET_DIR`, or `target` relative to the working directory if unset).
+	pub fn new (name: impl Into<String>, referenceDirectory: impl Into<PathBuf>) -> Self { Self {
+		name: name.into(),
+		referenceDirectory: referenceDirectory.into(),
+		outputDirectory: PathBuf::from(std::env::var_os("CARGO_TARGET_DIR").unwrap_or("target".into()))
+			.join("regression"),
+		options: HeadlessOptions {
+			resolution: glm::vec2(256, 256), forceFallbackAdapter: true, ..Default::default()
+		},
+		tolerance: Default::default(),
+		shots: Vec::new()
+	}}
+
+	/// Use the given headless rendering options.
+	pub fn options (mut self, options: HeadlessOptions) -> Self {
+		self.options = options;
+		self
+	}
+
+	/// Write mismatching images, difference visualizations and newly recorded references to the given directory.
+	pub fn outputDirectory (mut self, outputDirectory: impl Into<PathBuf>) -> Self {
+		self.outputDirectory = outputDirectory.into();
+		self
+	}
+
+	/// Use the given tolerance when comparing against the references.
+	pub fn tolerance (mut self, tolerance: Tolerance) -> Self {
+		self.tolerance = tolerance;
+		self
+	}
+
+	/// Add a shot to render and check.
+	pub fn shot (mut self, shot: Shot) -> Self {
+		self.shots.push(shot);
+		self
+	}
+
+	/// Render all shots of the application created by the given factory and check them against their references. If no
+	/// suitable adapter exists on the machine, the test gets skipped with a warning.
+	///
+	/// # Returns
+	///
+	/// `Ok` if all images matched their references, or an error listing every mismatch otherwise.
+	pub fn run (&self, applicationFactory: Box<dyn ApplicationFactory>) -> Result<()>
+	{
+		let update = std::env::var_os(UPDATE_REFERENCES_VAR).is_some();
+		let mut failures = Vec::new();
+		let result = Player::renderHeadless(applicationFactory, &self.options, &self.shots, |index, image| {
+			let path = self.referenceDirectory.join(format!("{}_{index}.png", self.name));
+			let check = checkAgainstReference(
+				&image.to_rgba8(), &path, &self.outputDirectory, &self.tolerance, update
+			);
+			if let Err(failure) = check {
+				failures.push(failure.to_string());
+			}
+			Ok(())
+		});
+		if let Err(error) = result {
+			if error.downcast_ref::<wgpu::RequestAdapterError>().is_some() {
+				tracing::warn!("skipping regression test '{}' – no suitable adapter: {error}", self.name);
+				return Ok(());
+			}
+			return Err(error);
+		}
+		if failures.is_empty() { Ok(()) } else {
+			Err(anyhow!("regression test '{}' failed:\n{}", self.name, failures.join("\n")))
+		}
+	}
+}
+
+
+
+//////
+//
+// Functions
+//
+
+/// Compare the given image against its reference.
+///
+/// # Returns
+///
+/// The [comparison result](Comparison), or an error if the images differ in size.
+pub fn compare (actual: &image::RgbaImage, reference: &image::RgbaImage, tolerance: &Tolerance) -> Result<Comparison>
+{
+	if actual.dimensions() != reference.dimensions() {
+		return Err(anyhow!(
+			"image size {:?} does not match reference size {:?}", actual.dimensions(), reference.dimensions()
+		));
+	}
+	let mut numFailingPixels = 0;
+	let mut maxDifference = 0;
+	let diff = image::RgbaImage::from_fn(actual.width(), actual.height(), |x, y| {
+		let (a, r) = (actual.get_pixel(x, y).0, reference.get_pixel(x, y).0);
+		let difference = (0..4).map(|c| a[c].abs_diff(r[c])).max().unwrap();
+		maxDifference = maxDifference.max(difference);
+		if difference > tolerance.perChannel {
+			numFailingPixels += 1;
+			image::Rgba([128 + difference/2, 0, 0, 255])
+		}
+		else {
+			let luma = ((r[0] as u32 + r[1] as u32 + r[2] as u32) / 12) as u8;
+			image::Rgba([luma, luma, luma, 255])
+		}
+	});
+	Ok(Comparison { numFailingPixels, numPixels: (actual.width()*actual.height()) as usize, maxDifference, diff })
+}
+
+/// Check the given image against the reference stored at the given path, writing the image and a difference
+/// visualization to the given output directory on failure. See [`RegressionTest`] for how missing references are
+/// handled.
+///
+/// # Arguments
+///
+/// * `actual` – The image to check.
+/// * `referencePath` – Where the reference image is stored.
+/// * `outputDirectory` – Where to write the image, its difference visualization or a newly recorded reference.
+/// * `tolerance` – How much the image may deviate from the reference.
+/// * `update` – If `true`, record the image as the new reference instead of checking it.
+pub fn checkAgainstReference (
+	actual: &image::RgbaImage, referencePath: impl AsRef<Path>, outputDirectory: impl AsRef<Path>,
+	tolerance: &Tolerance, update: bool
+) -> Result<()>
+{
+	// Re-record reference if requested
+	let referencePath = referencePath.as_ref();
+	if update {
+		if let Some(parent) = referencePath.parent() {
+			std::fs::create_dir_all(parent)?;
+		}
+		return Ok(actual.save_with_format(referencePath, image::ImageFormat::Png)?);
+	}
+
+	// Record missing reference into the output directory
+	let outputDirectory = outputDirectory.as_ref();
+	std::fs::create_dir_all(outputDirectory)?;
+	let outputPath = outputDirectory.join(referencePath.file_name().ok_or_else(
+		|| anyhow!("invalid reference path: {}", referencePath.display())
+	)?);
+	if !referencePath.exists() {
+		actual.save_with_format(&outputPath, image::ImageFormat::Png)?;
+		return Err(anyhow!(
+			"no reference image {}, recorded current output as {}", referencePath.display(), outputPath.display()
+		));
+	}
+
+	// Compare
+	let reference = image::open(referencePath)?.to_rgba8();
+	let failure = match compare(actual, &reference, tolerance) {
+		Ok(comparison) if comparison.passes(tolerance) => return Ok(()),
+		Ok(comparison) => {
+			let diffPath = outputPath.with_extension("diff.png");
+			comparison.diff.save_with_format(&diffPath, image::ImageFormat::Png)?;
+			format!(
+				"{} of {} pixels differ (by up to {}), see {}", comparison.numFailingPixels, comparison.numPixels,
+				comparison.maxDifference, diffPath.display()
+			)
+		},
+		Err(error) => error.to_string()
+	};
+	let actualPath = outputPath.with_extension("actual.png");
+	actual.save_with_format(&actualPath, image::ImageFormat::Png)?;
+	Err(anyhow!("{}: {failure}; output written to {}", referencePath.display(), actualPath.display()))
+}
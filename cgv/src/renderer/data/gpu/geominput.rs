@@ -0,0 +1,312 @@
+//////
+//
+// Imports
+//
+
+// Standard library
+use std::{fmt::Write, hash::{Hash, Hasher}};
+
+// Local imports
+use crate::{*, renderer::{*, data::{*, gpu::*}}};
+
+
+
+//////
+//
+// Classes
+//
+
+/// A *Slang* module implementing the `cgv::geom::IGeometryInput` interface from the *CGV-rs* core shader library for a
+/// specific [`BufferLayout`], together with the [`PipelineBufferLayout`] that feeds the vertex input stream it
+/// declares.
+///
+/// The generated module exports its implementation as `GeometryInput`, so renderer shaders can consume arbitrary
+/// layouts by declaring the specialization point `extern struct GeometryInput: cgv::geom::IGeometryInput;` and using
+/// it as vertex shader input. Attributes are accessed through the interface regardless of how they are actually laid
+/// out in the buffers – co-located scalars are read from the *w*-component of their host, missing components are
+/// padded and attributes not present in the layout report the default value provided by the renderer.
+pub struct GeometryInput
+{
+	/// The generated *Slang* source code.
+	sourceCode: String,
+
+	/// The virtual filepath under which to compile the module.
+	modulePath: String,
+
+	/// The pipeline buffer layout with shader locations matching the generated vertex input stream.
+	pipelineLayout: PipelineBufferLayout,
+
+	/// The attributes that the generated module will report as present.
+	includedAttribs: GeometryAttributeFlags
+}
+impl GeometryInput
+{
+	/// The path of the core shader library module defining the `IGeometryInput` interface.
+	pub const INTERFACE_MODULE: &str = "cgv/geom/input.slang";
+
+	/// The name of the interface implementation exported by every generated module.
+	pub const EXPORTED_TYPE: &str = "GeometryInput";
+
+	/// Generate the `IGeometryInput` implementation for the given buffer layout.
+	///
+	/// # Arguments
+	///
+	/// * `dataLayout` – The buffer layout of the render data.
+	/// * `filter` – The attributes to make available to the shader. Attributes present in the layout but not in the
+	///              filter will be reported as missing, i.e. accessing them will yield the provided default value.
+	/// * `stepMode` – Whether the attributes advance per vertex or per instance.
+	///
+	/// # Returns
+	///
+	/// The generated module source along with the pipeline buffer layout to use with it, or an error if the layout
+	/// co-locates an attribute with some other attribute that the layout does not contain.
+	pub fn generate (
+		dataLayout: &BufferLayout, filter: GeometryAttributeFlags, stepMode: wgpu::VertexStepMode
+	) -> Result<Self>
+	{
+		// Determine the attributes to expose and the slots we need to declare in the input stream to access them
+		let mut includedAttribs = GAF::empty();
+		let mut declared = [false; GA::NUM_SLOTS as usize];
+		for slot in 0..GA::NUM_SLOTS
+		{
+			let attrib = GA::from(slot);
+			let Some(loc) = dataLayout.attribute(attrib) else { continue };
+			if !filter.contains(attrib.into()) {
+				continue;
+			}
+			includedAttribs |= attrib.into();
+			match Self::hostOf(dataLayout, &loc)? {
+				Some(host) => declared[host.slot()] = true,
+				None => if loc.offset() == 0 { declared[attrib.slot()] = true }
+			}
+		}
+
+		// Assign shader locations in attribute order, positions always come first
+		let mut includeAttribs = Vec::with_capacity(GA::NUM_SLOTS as usize);
+		let mut shaderLoc = 1;
+		for slot in 0..GA::NUM_SLOTS {
+			if declared[slot as usize] {
+				includeAttribs.push((GA::from(slot), shaderLoc));
+				shaderLoc += 1;
+			}
+		}
+		let pipelineLayout = PipelineBufferLayout::create(dataLayout, 0, stepMode, &includeAttribs);
+
+		// Emit the input stream declaration
+		let mut src = String::with_capacity(4096);
+		writeln!(
+			src, "// Auto-generated by CGV-rs from a `renderer::data::gpu::BufferLayout` – do not edit!\n\
+			      import \"{}\";\n\n\
+			      /// Vertex input stream matching the buffer layout of the render data.\n\
+			      public struct {}Impl: cgv::geom::IGeometryInput\n{{",
+			Self::INTERFACE_MODULE, Self::EXPORTED_TYPE
+		).unwrap();
+		let posFormat = Self::formatOf(dataLayout, &dataLayout.positions);
+		writeln!(src, "\t[vk::location(0)] public var positions: {};", slangTypeFor(posFormat)).unwrap();
+		for &(attrib, loc) in &includeAttribs {
+			let format = Self::formatOf(dataLayout, &dataLayout.attribute(attrib).unwrap());
+			writeln!(
+				src, "\t[vk::location({loc})] public var {}: {};", fieldName(attrib), slangTypeFor(format)
+			).unwrap();
+		}
+
+		// Emit the accessors
+		writeln!(
+			src, "\n\tpublic func position () -> float4 {{ return float4({}, 1); }}",
+			componentsExpr("positions", posFormat, 0, 3)
+		).unwrap();
+		for slot in 0..GA::NUM_SLOTS
+		{
+			let attrib = GA::from(slot);
+			let (has, accessor, ty) = accessorNames(attrib);
+			let value = if includedAttribs.contains(attrib.into())
+			{
+				let loc = dataLayout.attribute(attrib).unwrap();
+				let (field, format) = match Self::hostOf(dataLayout, &loc)? {
+					Some(host) => (
+						fieldName(host), Self::formatOf(dataLayout, &dataLayout.attribute(host).unwrap())
+					),
+					None if Self::hostedByPositions(dataLayout, &loc) => ("positions", posFormat),
+					None => (fieldName(attrib), Self::formatOf(dataLayout, &loc))
+				};
+				componentsExpr(field, format, loc.offset() as usize, attrib.components() as usize)
+			}
+			else {
+				"defaultValue".into()
+			};
+			writeln!(
+				src, "\tpublic func {has} () -> bool {{ return {}; }}\n\
+				      \tpublic func {accessor} (defaultValue: {ty}) -> {ty} {{ return {value}; }}",
+				includedAttribs.contains(attrib.into())
+			).unwrap();
+		}
+		writeln!(
+			src, "}}\n\n\
+			      /// Bind to the specialization point of the renderer.\n\
+			      export struct {0}: cgv::geom::IGeometryInput = {0}Impl;",
+			Self::EXPORTED_TYPE
+		).unwrap();
+
+		// Derive a module path that is unique to the generated code, so identical layouts can share compiled modules
+		let mut hasher = std::hash::DefaultHasher::new();
+		src.hash(&mut hasher);
+		let modulePath = format!("cgv/geom/input/generated_{:016x}.slang", hasher.finish());
+
+		// Done!
+		Ok(Self { sourceCode: src, modulePath, pipelineLayout, includedAttribs })
+	}
+
+	/// Reference the generated *Slang* source code.
+	#[inline(always)]
+	pub fn sourceCode (&self) -> &str {
+		&self.sourceCode
+	}
+
+	/// The virtual filepath under which the generated module should be compiled. It is derived from the generated code,
+	/// so two `GeometryInput`s share the same path if and only if they generated the same code.
+	#[inline(always)]
+	pub fn modulePath (&self) -> &str {
+		&self.modulePath
+	}
+
+	/// Reference the pipeline buffer layout that feeds the vertex input stream declared by the generated module.
+	#[inline(always)]
+	pub fn pipelineLayout (&self) -> &PipelineBufferLayout {
+		&self.pipelineLayout
+	}
+
+	/// The attributes that the generated module reports as present.
+	#[inline(always)]
+	pub fn includedAttribs (&self) -> GeometryAttributeFlags {
+		self.includedAttribs
+	}
+
+	/// Compile the generated module under the given context. The context must have access to the core shader library
+	/// (e.g. via [`cgv::obtainShaderCompileEnvironment`](crate::obtainShaderCompileEnvironment)).
+	#[cfg(feature="slang_runtime")]
+	pub fn compile<'ctx, Context> (&self, context: &'ctx Context)
+		-> Result<Context::ModuleType<'ctx>, shader::compile::LoadModuleError>
+	where
+		Context: shader::compile::Context + 'ctx
+	{
+		context.compileFromNamedSource(&self.modulePath, &self.sourceCode)
+	}
+
+	/// Check whether the attribute at the given location is co-located with the positions.
+	#[inline(always)]
+	fn hostedByPositions (dataLayout: &BufferLayout, loc: &BufferAttributeSlot) -> bool {
+		loc.offset() > 0 && dataLayout.positions.inSameBufferSlot(loc)
+	}
+
+	/// Find the attribute owning the slot that the attribute at the given location is co-located in, if any. Returns
+	/// `None` for attributes that have their own slot or are hosted by the positions, and an error if the owning
+	/// attribute is not part of the layout.
+	fn hostOf (dataLayout: &BufferLayout, loc: &BufferAttributeSlot) -> Result<Option<GeometryAttribute>>
+	{
+		if loc.offset() == 0 || Self::hostedByPositions(dataLayout, loc) {
+			return Ok(None);
+		}
+		(0..GA::NUM_SLOTS).map(GA::from).find(|&other| dataLayout.attribute(other).is_some_and(
+			|otherLoc| otherLoc.offset() == 0 && otherLoc.inSameBufferSlot(loc)
+		)).map(Some).ok_or_else(|| anyhow!(
+			"attribute at slot {} of buffer {} is co-located with some other attribute that is not in the layout",
+			loc.slot(), loc.buffer()
+		))
+	}
+
+	/// Look up the vertex format of the given attribute location.
+	#[inline(always)]
+	fn formatOf (dataLayout: &BufferLayout, loc: &BufferAttributeSlot) -> wgpu::VertexFormat {
+		dataLayout.buffers[loc.buffer()].attributes[loc.slot()].format
+	}
+}
+
+
+
+//////
+//
+// Functions
+//
+
+/// The name of the input stream field holding the given attribute.
+fn fieldName (attrib: GeometryAttribute) -> &'static str {
+	match attrib {
+		GA::Normals => "normals",
+		GA::Tangents => "tangents",
+		GA::Radii => "radii",
+		GA::RadiusDerivs => "radiusDerivs",
+		GA::Orientations => "orientations",
+		GA::Scalings => "scalings",
+		GA::Colors => "colors"
+	}
+}
+
+/// The names of the `IGeometryInput` presence query and accessor for the given attribute, plus the accessor type.
+fn accessorNames (attrib: GeometryAttribute) -> (&'static str, &'static str, &'static str) {
+	match attrib {
+		GA::Normals => ("hasNormals", "normal", "float3"),
+		GA::Tangents => ("hasTangents", "tangent", "float3"),
+		GA::Radii => ("hasRadii", "radius", "float"),
+		GA::RadiusDerivs => ("hasRadiusDerivs", "radiusDeriv", "float"),
+		GA::Orientations => ("hasOrientations", "orientation", "float4"),
+		GA::Scalings => ("hasScalings", "scaling", "float3"),
+		GA::Colors => ("hasColors", "color", "float4")
+	}
+}
+
+/// Determine the scalar *Slang* type and number of components that the given vertex format is presented as in a
+/// shader.
+fn slangComponents (format: wgpu::VertexFormat) -> (&'static str, usize)
+{
+	use wgpu::VertexFormat as VF;
+	match format {
+		VF::Uint8 | VF::Uint16 | VF::Uint32 => ("uint", 1),
+		VF::Uint8x2 | VF::Uint16x2 | VF::Uint32x2 => ("uint", 2),
+		VF::Uint32x3 => ("uint", 3),
+		VF::Uint8x4 | VF::Uint16x4 | VF::Uint32x4 => ("uint", 4),
+		VF::Sint8 | VF::Sint16 | VF::Sint32 => ("int", 1),
+		VF::Sint8x2 | VF::Sint16x2 | VF::Sint32x2 => ("int", 2),
+		VF::Sint32x3 => ("int", 3),
+		VF::Sint8x4 | VF::Sint16x4 | VF::Sint32x4 => ("int", 4),
+		VF::Float64 => ("double", 1),
+		VF::Float64x2 => ("double", 2),
+		VF::Float64x3 => ("double", 3),
+		VF::Float64x4 => ("double", 4),
+		VF::Unorm8 | VF::Snorm8 | VF::Unorm16 | VF::Snorm16 | VF::Float16 | VF::Float32 => ("float", 1),
+		VF::Unorm8x2 | VF::Snorm8x2 | VF::Unorm16x2 | VF::Snorm16x2 | VF::Float16x2 | VF::Float32x2 => ("float", 2),
+		VF::Float32x3 => ("float", 3),
+		// All remaining formats are normalized or floating point 4-vectors
+		_ => ("float", 4)
+	}
+}
+
+/// Determine the *Slang* type of an input stream field of the given vertex format.
+fn slangTypeFor (format: wgpu::VertexFormat) -> String {
+	match slangComponents(format) {
+		(scalar, 1) => scalar.to_owned(),
+		(scalar, n) => format!("{scalar}{n}")
+	}
+}
+
+/// Construct a *Slang* expression extracting `count` float components starting at component `offset` from the given
+/// input stream field. Components not provided by the field are padded with `0`, except for a missing 4th component
+/// which is padded with `1` (yielding opaque colors and identity quaternions).
+fn componentsExpr (field: &str, format: wgpu::VertexFormat, offset: usize, count: usize) -> String
+{
+	const SWIZZLE: &str = "xyzw";
+	let floatType = |n: usize| if n > 1 { format!("float{n}") } else { "float".to_owned() };
+	let (_, available) = slangComponents(format);
+	let provided = available.saturating_sub(offset).min(count);
+	let mut expr = if available == 1 {
+		format!("this.{field}")
+	} else {
+		format!("this.{field}.{}", &SWIZZLE[offset..offset+provided])
+	};
+	if provided < count {
+		expr = format!("{}({expr})", floatType(provided));
+		for component in provided..count {
+			expr += if component == 3 { ", 1" } else { ", 0" };
+		}
+	}
+	format!("{}({expr})", floatType(count))
+}
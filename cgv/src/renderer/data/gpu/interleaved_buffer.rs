@@ -178,7 +178,8 @@ impl InterleavedBuffer
 			"buffer size calculation consistency check failed"
 		);
 		let buffer = context.device().create_buffer(&wgpu::BufferDescriptor {
-			label, size, usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::INDIRECT | wgpu::BufferUsages::STORAGE,
+			label, size, usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::INDIRECT | wgpu::BufferUsages::STORAGE
+				| wgpu::BufferUsages::COPY_SRC,
 			mapped_at_creation: true,
 		});
 
@@ -8,6 +8,10 @@
 mod interleaved_buffer;
 pub use interleaved_buffer::{InterleavedBuffer, InterleavedBufferOptions}; // re-export
 
+/// Module implementing the generation of `IGeometryInput` *Slang* modules from [buffer layouts](BufferLayout).
+mod geominput;
+pub use geominput::GeometryInput; // re-export
+
 
 
 //////
@@ -31,11 +35,8 @@ use crate::{self as cgv, *, renderer::{*, data::*}};
 /// Where to store scalar attributes in [GPU-side render data](Data). Right now, only storing them
 /// [separately](ScalarAttributeStorage::Separate) or [in the *w*-component](ScalarAttributeStorage::InWComponent) of a
 /// vector-valued attribute is supported to keep the burden of checking dozens of possible  configurations low on
-/// [`Renderer`]s.
-///
-/// **TODO: Replace by a different mechanism once [`BufferLayout`] gains the functionality to auto-generate a Slang
-/// "geometry input" implementation for use by renderers that can/want to make use of
-/// [runtime shader compilation](cgv_shader::compile).**
+/// [`Renderer`]s. Renderers that make use of [runtime shader compilation](cgv_shader::compile) can avoid this burden
+/// altogether by consuming a [`GeometryInput`] generated from the [`BufferLayout`].
 #[derive(Clone,Copy,PartialEq,Eq,Debug)]
 pub enum ScalarAttributeStorage
 {
@@ -391,9 +392,9 @@ impl DerefMut for GeometryAttributeOccupancy {
 	}
 }
 
-/// **TODO: Add functionality to auto-generate implementations of the projected `IGeometryInput` interface from the
-/// *CGV-rs* core *Slang* shader library for use by renderers that make use of
-/// [runtime shader compilation](cgv_shader::compile).**
+/// Describes where the geometry attributes of some [GPU-side render data](Data) are located in its buffers.
+/// Implementations of the `IGeometryInput` interface from the *CGV-rs* core *Slang* shader library can be generated for
+/// any layout via [`BufferLayout::geometryInput`].
 #[derive(Clone)]
 pub struct BufferLayout
 {
@@ -471,6 +472,15 @@ impl BufferLayout
 		}
 	}
 
+	/// Generate the `IGeometryInput` *Slang* module giving shaders access to the attributes described by this layout.
+	/// See [`GeometryInput::generate`] for details.
+	#[inline(always)]
+	pub fn geometryInput (&self, filter: GeometryAttributeFlags, stepMode: wgpu::VertexStepMode)
+		-> Result<GeometryInput>
+	{
+		GeometryInput::generate(self, filter, stepMode)
+	}
+
 	/// Infer whether this layout is interleaved or not.
 	pub fn isInterleaved (&self) -> bool {
 		self.buffers.len() < 2 && {
@@ -864,3 +874,52 @@ pub fn hostDataGpuSize<D: renderer::HostData+?Sized> (
 		+ if hostData.hasColors()       { VECTOR_SIZE } else { 0 }
 	)  *  hostData.num() as wgpu::BufferAddress
 }
+
+/// Read back the values of the given attribute of some [GPU-side render data](Data), e.g. for verifying the results of
+/// a compute kernel that updated them. Works like [`hal::readbackBufferAsync`], except that the values are extracted
+/// from the buffer layout of the data and passed to the callback as a tightly packed `Vec`.
+///
+/// # Arguments
+///
+/// * `context` – The graphics context.
+/// * `data` – The render data to read from. Its buffers must have been created with [`wgpu::BufferUsages::COPY_SRC`].
+/// * `attribute` – Where in the [layout](Data::layout) of the data the attribute is located, e.g.
+///                 [`BufferLayout::positions`] or the slot returned by [`BufferLayout::attribute`]. Attributes that
+///                 are [co-located](ScalarAttributeStorage::isColocated) with another one must be read as their base
+///                 primitive type (e.g. `f32` for radii stored in the *w*-component of the positions).
+/// * `callback` – The closure receiving the values, one per element of the data.
+pub fn readbackAttributeAsync<T: bytemuck::Pod, Closure: FnOnce(Vec<T>) + wgpu::WasmNotSend + 'static> (
+	context: &Context, data: &dyn Data, attribute: BufferAttributeSlot, callback: Closure
+){
+	// Locate the attribute values in the buffer
+	let bufferLayout = &data.layout().buffers[attribute.buffer()];
+	let vertexAttrib = bufferLayout.attributes[attribute.slot()];
+	let offset = (vertexAttrib.offset as usize) + attribute.offset() as usize * size_of::<T>();
+	assert!(
+		offset + size_of::<T>() <= (vertexAttrib.offset + vertexAttrib.format.size()) as usize,
+		"type of read back values is larger than the attribute"
+	);
+	let stride = bufferLayout.array_stride as usize;
+	let num = data.num() as usize;
+
+	// Read back all elements and pick out the attribute values
+	let geometry = data.geometry()[attribute.buffer()];
+	let start = geometry.offset();
+	hal::readbackBufferAsync(context, geometry.buffer(), start..start+(stride*num) as wgpu::BufferAddress,
+		move |bytes: &[u8]| callback((0..num).map(
+			|idx| bytemuck::pod_read_unaligned(&bytes[idx*stride+offset..idx*stride+offset+size_of::<T>()])
+		).collect())
+	);
+}
+
+/// Read back the values of the given attribute of some [GPU-side render data](Data), blocking until the data is
+/// available. See [`readbackAttributeAsync`] for details on the arguments.
+#[cfg(not(target_arch="wasm32"))]
+pub fn readbackAttribute<T: bytemuck::Pod> (context: &Context, data: &dyn Data, attribute: BufferAttributeSlot)
+	-> Result<Vec<T>>
+{
+	let (sender, receiver) = std::sync::mpsc::channel();
+	readbackAttributeAsync(context, data, attribute, move |values: Vec<T>| { sender.send(values).ok(); });
+	context.device().poll(wgpu::PollType::wait_indefinitely())?;
+	receiver.recv().map_err(|_| anyhow!("readback buffer could not be mapped"))
+}
@@ -59,6 +59,12 @@ pub trait Data:
 	/// topologies.
 	fn topology (&self) -> wgpu::PrimitiveTopology;
 
+	/// Compute the axis-aligned bounding box of the positions, or `None` if there are no elements. Iterates over all
+	/// positions, so callers that need the bounds repeatedly should cache the result.
+	fn bounds (&self) -> Option<util::math::Aabb> {
+		if self.num() > 0 { util::math::Aabb::fromPoints(self.positions()) } else { None }
+	}
+
 	/// Check if the attribute indicated at runtime is available.
 	fn hasAttrib (&self, attrib: GeometryAttribute) -> bool
 	{
@@ -91,7 +91,8 @@ impl LayoutVariant {
 		fn createBuffer<A: Sized> (dev: &wgpu::Device, numInstances: u32, label: Option<&str>) -> wgpu::Buffer {
 			dev.create_buffer(&wgpu::BufferDescriptor {
 				label, size: (numInstances as usize * size_of::<A>()) as wgpu::BufferAddress,
-				usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::INDIRECT | wgpu::BufferUsages::STORAGE,
+				usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::INDIRECT | wgpu::BufferUsages::STORAGE
+					| wgpu::BufferUsages::COPY_SRC,
 				mapped_at_creation: true,
 			})
 		}
@@ -126,12 +127,23 @@ pub struct Style
 	/// The default radius of the rendered spheres, used when the radius attribute is not sourced from user data.
 	pub radiusScale: f32,
 
-	/// Explicit padding for *bytemuck*.
-	pub pad: [u32; 2]
+	/// The [object ID](cgv::player::ObjectId) written into the ID attachment of the framebuffer for
+	/// [picking](cgv::Player::setPickingEnabled). Managed by the renderer, which sets this to its
+	/// [own ID](cgv::renderer::Spheres::objectId) upon creation.
+	pub objectId: u32,
+
+	/// The number of elements covered by the current [selection](cgv::renderer::Spheres::setSelection), or *0* if
+	/// there is none. Managed by the renderer.
+	pub selectionSize: u32,
+
+	/// The color selected spheres get highlighted with, with the alpha component determining how strongly the
+	/// highlight color replaces the color of the sphere.
+	pub highlightColor: Rgba
 }
 impl Default for Style {
 	fn default () -> Self { Self {
-		radiusScale: 1., defaultRadius: 1., defaultColor: Rgba::from_rgb(2./5., 2./5., 2./5.), pad: [0; 2]
+		radiusScale: 1., defaultRadius: 1., defaultColor: Rgba::from_rgb(2./5., 2./5., 2./5.), objectId: 0,
+		selectionSize: 0, highlightColor: Rgba::from_rgba_premultiplied(1., 3./5., 0., 3./4.)
 	}}
 }
 pub type StyleUniformGroup = hal::UniformGroup<Style>;
@@ -16,11 +16,17 @@ pub use data::GpuData; // re-export
 //
 
 // Standard library
-use std::sync::{LazyLock, Arc};
+use std::sync::Arc;
+#[cfg(not(feature="slang_runtime"))]
+use std::sync::LazyLock;
 
 // Egui library
 use egui::ecolor::Rgba;
 
+// DashMap concurrent associative container library
+#[cfg(feature="slang_runtime")]
+use dashmap::DashMap;
+
 // Local imports
 use crate::{*, renderer::{data::*, *}};
 use data::*;
@@ -36,12 +42,36 @@ use data::*;
 pub struct DataReceiver {
 	data: Arc<dyn renderer::GpuData>,
 	includedAttribs: GeometryAttributeFlags,
+	#[cfg(not(feature="slang_runtime"))]
 	layout: GpuPipelineBufferLayout,
+	#[cfg(not(feature="slang_runtime"))]
 	vsEntryPoint: String,
-	fsEntryPoint: String
+	#[cfg(not(feature="slang_runtime"))]
+	fsEntryPoint: String,
+	#[cfg(feature="slang_runtime")]
+	geometryInput: gpu::GeometryInput,
+	#[cfg(feature="slang_runtime")]
+	shaderPackage: shader::Package
 }
 impl DataReceiver
 {
+	/// Receive the provided GPU data.
+	///
+	#[doc=include_str!("_doc/_spheres_layoutRemarks.md")]
+	///
+	/// # Arguments
+	///
+	/// * `data` – The `GpuData` to render.
+	///
+	/// # Returns
+	///
+	/// A `DataReceiver` for feeding into a `renderer::Spheres`, or an error if the layout of the data is not supported
+	/// or the shader for it could not be built.
+	#[cfg(feature="slang_runtime")]
+	#[inline(always)]
+	pub fn new (data: Arc<dyn renderer::GpuData>) -> Result<Self> {
+		Self::withAttributes(data, GAF::all())
+	}
 	/// Receive the provided GPU data.
 	///
 	#[doc=include_str!("_doc/_spheres_layoutRemarks.md")]
@@ -53,6 +83,7 @@ impl DataReceiver
 	/// # Returns
 	///
 	/// A `DataReceiver` for feeding into a `renderer::Spheres`.
+	#[cfg(not(feature="slang_runtime"))]
 	#[inline(always)]
 	pub fn new (data: Arc<dyn renderer::GpuData>) -> Self {
 		Self::withAttributes(data, GAF::all())
@@ -70,7 +101,34 @@ impl DataReceiver
 	///
 	/// # Returns
 	///
+	/// A `DataReceiver` for feeding into a `renderer::Spheres`, or an error if the layout of the data is not supported
+	/// or the shader for it could not be built.
+	#[cfg(feature="slang_runtime")]
+	pub fn withAttributes (data: Arc<dyn renderer::GpuData>, filter: GeometryAttributeFlags) -> Result<Self>
+	{
+		// Any layout is supported, we link the generated geometry input into the shader right away so that the
+		// renderer itself can't fail later on
+		let geometryInput = data.layout().geometryInput(
+			filter & (GAF::RADII|GAF::COLORS), wgpu::VertexStepMode::Instance
+		)?;
+		let shaderPackage = Spheres::buildShaderPackage(&geometryInput)?;
+		Ok(Self { includedAttribs: geometryInput.includedAttribs(), data, geometryInput, shaderPackage })
+	}
+	/// Receive the provided GPU data, using only the specified attributes, the rest will be fixed as indicated by the
+	/// current [style](Spheres::setStyleUniforms).
+	///
+	#[doc=include_str!("_doc/_spheres_layoutRemarks.md")]
+	///
+	/// # Arguments
+	///
+	/// * `data` – The `GpuData` to render.
+	/// * `filter` – The attributes to use from the data. Only specifying [`GAF::RADII`] and [`GAF::COLORS`] will have
+	///              an effect; other attributes are always ignored by `renderer::Spheres`.
+	///
+	/// # Returns
+	///
 	/// A `DataReceiver` for feeding into a `renderer::Spheres`.
+	#[cfg(not(feature="slang_runtime"))]
 	pub fn withAttributes (data: Arc<dyn renderer::GpuData>, filter: GeometryAttributeFlags) -> Self
 	{
 		// Infer the right shader entry point and vertex shader locations from the available attributes
@@ -102,6 +160,13 @@ impl DataReceiver
 		// Done!
 		Self { data, includedAttribs, layout, vsEntryPoint, fsEntryPoint }
 	}
+
+	/// Reference the pipeline buffer layout to render the data with.
+	#[inline(always)]
+	fn pipelineLayout (&self) -> &GpuPipelineBufferLayout {
+		#[cfg(feature="slang_runtime")] { self.geometryInput.pipelineLayout() }
+		#[cfg(not(feature="slang_runtime"))] { &self.layout }
+	}
 }
 impl GpuDataReceiver for DataReceiver {
 	fn gpuData (&self) -> &dyn renderer::GpuData {
@@ -111,15 +176,30 @@ impl GpuDataReceiver for DataReceiver {
 	/// Custom implementation to also take ignored/included attributes into account
 	#[inline]
 	fn isCompatible (&self, otherReceiver: &Self) -> bool {
-		self.layout == otherReceiver.layout && self.includedAttribs.bits() == otherReceiver.includedAttribs.bits()
+		#[cfg(feature="slang_runtime")]
+		if self.geometryInput.modulePath() != otherReceiver.geometryInput.modulePath() {
+			return false;
+		}
+		   self.pipelineLayout() == otherReceiver.pipelineLayout()
+		&& self.includedAttribs.bits() == otherReceiver.includedAttribs.bits()
 	}
 }
+#[cfg(not(feature="slang_runtime"))]
 impl From<Arc<dyn renderer::GpuData+'static>> for DataReceiver {
 	#[inline(always)]
 	fn from (data: Arc<dyn renderer::GpuData>) -> Self {
 		Self::new(data)
 	}
 }
+#[cfg(feature="slang_runtime")]
+impl TryFrom<Arc<dyn renderer::GpuData+'static>> for DataReceiver {
+	type Error = anyhow::Error;
+
+	#[inline(always)]
+	fn try_from (data: Arc<dyn renderer::GpuData>) -> Result<Self> {
+		Self::new(data)
+	}
+}
 impl Deref for DataReceiver {
 	type Target = dyn renderer::GpuData;
 
@@ -131,12 +211,24 @@ impl Deref for DataReceiver {
 
 ///
 pub struct Spheres {
+	#[cfg(not(feature="slang_runtime"))]
 	shader: wgpu::ShaderModule,
+	#[cfg(feature="slang_runtime")]
+	shaders: DashMap<String, wgpu::ShaderModule>,
 	pipelineLayout: wgpu::PipelineLayout,
-	styleUniforms: StyleUniformGroup
+	styleUniforms: StyleUniformGroup,
+	selectionBindGroupLayout: wgpu::BindGroupLayout,
+	selectionBuffer: wgpu::Buffer,
+	selectionBindGroup: wgpu::BindGroup,
+	objectId: player::OwnedObjectId
 }
 impl Spheres
 {
+	/// The entry points of the runtime-linked shader.
+	#[cfg(feature="slang_runtime")]
+	const RUNTIME_ENTRY_POINTS: (&str, &str) = ("vertexMain", "fragmentMain");
+
+	#[cfg(not(feature="slang_runtime"))]
 	fn shaderPackage<'outer> () -> &'outer shader::Package
 	{
 		static SHADER_PACKAGE: LazyLock<shader::Package> = LazyLock::new(||
@@ -147,27 +239,149 @@ impl Spheres
 		&SHADER_PACKAGE
 	}
 
+	/// Create a storage buffer able to hold the given number of selection words, along with a bind group for it.
+	fn createSelectionBinding (context: &Context, layout: &wgpu::BindGroupLayout, numWords: usize)
+		-> (wgpu::Buffer, wgpu::BindGroup)
+	{
+		let buffer = context.device().create_buffer(&wgpu::BufferDescriptor {
+			label: Some("CGV__renderer_Spheres_selectionBuffer"), size: (numWords.max(1)*size_of::<u32>()) as u64,
+			usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST, mapped_at_creation: false
+		});
+		let bindGroup = context.device().create_bind_group(&wgpu::BindGroupDescriptor {
+			label: Some("CGV__renderer_Spheres_selectionBindGroup"), layout,
+			entries: &[wgpu::BindGroupEntry { binding: 0, resource: buffer.as_entire_binding() }]
+		});
+		(buffer, bindGroup)
+	}
+
 	pub fn new (context: &Context, renderSetup: &RenderSetup) -> Self
 	{
 		// Create constant (not state-dependent) GPU objects
-		let styleUniforms = StyleUniformGroup::createAndUpload(
+		let objectId = player::OwnedObjectId::new();
+		let mut styleUniforms = StyleUniformGroup::create(
 			context, wgpu::ShaderStages::VERTEX_FRAGMENT,
 			Some("CGV__renderer_Spheres_styleUniforms").as_deref()
 		);
+		styleUniforms.update(context, |style| style.objectId = objectId.get());
+		let selectionBindGroupLayout = context.device().create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+			label: Some("CGV__renderer_Spheres_selectionBindGroupLayout"),
+			entries: &[wgpu::BindGroupLayoutEntry {
+				binding: 0, visibility: wgpu::ShaderStages::FRAGMENT, count: None,
+				ty: wgpu::BindingType::Buffer {
+					ty: wgpu::BufferBindingType::Storage { read_only: true }, has_dynamic_offset: false,
+					min_binding_size: None
+				}
+			}]
+		});
+		let (selectionBuffer, selectionBindGroup) = Self::createSelectionBinding(
+			context, &selectionBindGroupLayout, 1
+		);
 		let pipelineLayout =
 			context.device().create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
 				label: Some("CGV__renderer_Spheres_renderPipelineLayout"),
 				bind_group_layouts: &[
-					Some(&renderSetup.bindGroupLayouts().viewing), Some(&styleUniforms.bindGroupLayout)
+					Some(&renderSetup.bindGroupLayouts().viewing), Some(&styleUniforms.bindGroupLayout),
+					Some(&selectionBindGroupLayout)
 				],
 				immediate_size: 0
 			});
+		#[cfg(not(feature="slang_runtime"))]
 		let shader = Self::shaderPackage().createShaderModuleFromBestInstance(
 			context.device(), None, Some("CGV__renderer_Spheres_shaderModule")
 		).expect("shader module could not be compiled by WGPU");
 
 		// Done!
-		Self { shader, pipelineLayout, styleUniforms }
+		#[cfg(not(feature="slang_runtime"))] {
+			Self {
+				shader, pipelineLayout, styleUniforms, selectionBindGroupLayout, selectionBuffer, selectionBindGroup,
+				objectId
+			}
+		}
+		#[cfg(feature="slang_runtime")] {
+			Self {
+				shaders: DashMap::with_capacity(4), pipelineLayout, styleUniforms, selectionBindGroupLayout,
+				selectionBuffer, selectionBindGroup, objectId
+			}
+		}
+	}
+
+	/// Build the shader package for the given geometry input, compiling and linking it unless the persistent shader
+	/// cache already holds it.
+	#[cfg(feature="slang_runtime")]
+	fn buildShaderPackage (geometryInput: &gpu::GeometryInput) -> Result<shader::Package>
+	{
+		// Imports we only need here
+		use shader::compile::{self, Context as _, ContextBuilder as _, EnvironmentEnabled as _, Module as _};
+
+		// Set up a compilation context and determine the cache key for the shader we need
+		const MAIN_MODULE_PATH: &str = "cgv/renderer/runtime/spheres.slang";
+		let mainModuleSource = util::sourceFile!("/shader/renderer/runtime/spheres.slang");
+		let mut slangCtx = shader::slang::ContextBuilder::default().build()?;
+		let environment = obtainShaderCompileEnvironment();
+		let cacheKey = shader::cache::KeyBuilder::new()
+			.context(&slangCtx).environment(&environment).sourceType(shader::WgpuSourceType::mostSuitable())
+			.source(MAIN_MODULE_PATH, mainModuleSource)
+			.source(geometryInput.modulePath(), geometryInput.sourceCode())
+			.build();
+
+		// Obtain the package from the persistent cache, only compiling it if that fails
+		obtainShaderCache().getOrTryInsertWith(&cacheKey, || -> Result<_>
+		{
+			// Provide access to the core shader library
+			slangCtx.replaceEnvironment(Some(environment))?;
+
+			// Compile and link the runtime shader with the generated geometry input
+			let mainModule = slangCtx.compileFromNamedSource(MAIN_MODULE_PATH, mainModuleSource)?;
+			let inputModule = geometryInput.compile(&slangCtx)?;
+			let mut components = vec![
+				compile::ComponentRef::Module(&mainModule), compile::ComponentRef::Module(&inputModule)
+			];
+			components.extend(mainModule.entryPoints().iter().map(compile::ComponentRef::EntryPoint));
+			let linked = slangCtx.linkComposite(&slangCtx.createComposite(&components)?)?;
+			let package = shader::Package::fromLinkedComposite(
+				shader::WgpuSourceType::mostSuitable(), &slangCtx, &linked, Some(geometryInput.modulePath().into()),
+				None
+			)?;
+			Ok((package, mainModule.fileDependencies()))
+		})
+	}
+
+	/// Obtain the shader module for the given data, creating it from the shader package the data was received with if
+	/// we haven't encountered its geometry input before.
+	#[cfg(feature="slang_runtime")]
+	fn refShader (&self, context: &Context, data: &DataReceiver) -> wgpu::ShaderModule
+	{
+		let modulePath = data.geometryInput.modulePath();
+		if let Some(shader) = self.shaders.get(modulePath) {
+			return shader.clone();
+		}
+		let shader = data.shaderPackage.createShaderModuleFromBestInstance(
+			context.device(), None, Some("CGV__renderer_Spheres_shaderModule")
+		).expect("shader module could not be compiled by WGPU");
+		self.shaders.insert(modulePath.to_owned(), shader.clone());
+		shader
+	}
+
+	/// The object ID the renderer writes into the ID attachment of the framebuffer, for identifying its spheres in
+	/// [picks](player::Pick). The [element index](player::Pick::element) of a pick is the index of the sphere within
+	/// the rendered data.
+	#[inline(always)]
+	pub fn objectId (&self) -> player::ObjectId {
+		self.objectId.id()
+	}
+
+	/// Set the selection of spheres to highlight in the [highlight color](Style::highlightColor), with element *i* of
+	/// the selection referring to the *i*-th sphere in the rendered data. Pass `None` to remove any highlighting.
+	pub fn setSelection (&mut self, context: &Context, selection: Option<&view::Selection>)
+	{
+		let words = selection.map(view::Selection::words).filter(|words| !words.is_empty()).unwrap_or(&[0]);
+		if self.selectionBuffer.size() < size_of_val(words) as u64 {
+			(self.selectionBuffer, self.selectionBindGroup) = Self::createSelectionBinding(
+				context, &self.selectionBindGroupLayout, words.len()
+			);
+		}
+		context.queue().write_buffer(&self.selectionBuffer, 0, bytemuck::cast_slice(words));
+		self.styleUniforms.update(context, |style| style.selectionSize = selection.map_or(0, view::Selection::num));
 	}
 
 	#[inline(always)]
@@ -196,11 +410,21 @@ impl Renderer for Spheres
 		&self, context: &Context, renderState: &RenderState, data: &Self::GpuDataReceiver
 	) -> Self::GpuState
 	{
+		// Obtain shader
+		#[cfg(not(feature="slang_runtime"))]
+		let (shader, vsEntryPoint, fsEntryPoint) = (
+			&self.shader, data.vsEntryPoint.as_str(), data.fsEntryPoint.as_str()
+		);
+		#[cfg(feature="slang_runtime")]
+		let (shader, (vsEntryPoint, fsEntryPoint)) = (
+			&self.refShader(context, data), Self::RUNTIME_ENTRY_POINTS
+		);
+
 		// Construct vertex state
 		let vertexState = wgpu::VertexState {
-			module: &self.shader,
-			entry_point: Some(&data.vsEntryPoint),
-			buffers: &data.layout.bufferLayouts(),
+			module: shader,
+			entry_point: Some(vsEntryPoint),
+			buffers: data.pipelineLayout().bufferLayouts(),
 			compilation_options: wgpu::PipelineCompilationOptions::default(),
 		};
 
@@ -210,11 +434,11 @@ impl Renderer for Spheres
 			layout: Some(&self.pipelineLayout),
 			vertex: vertexState,
 			fragment: Some(wgpu::FragmentState {
-				module: &self.shader,
-				entry_point: Some(&data.fsEntryPoint),
-				targets: &[Some(renderstate::changeColorTargetState_blending(
+				module: shader,
+				entry_point: Some(fsEntryPoint),
+				targets: &renderState.colorTargetStates(renderstate::changeColorTargetState_blending(
 					renderState.colorTargetState(), renderstate::BlendingOperation::AlphaPreMultiplied
-				))],
+				), /* writeIds: */true),
 				compilation_options: wgpu::PipelineCompilationOptions::default(),
 			}),
 			primitive: wgpu::PrimitiveState {
@@ -239,10 +463,12 @@ impl Renderer for Spheres
 		renderPass.set_pipeline(gpuState); // <- in our case it's literally just the pipeline
 		renderPass.set_bind_group(0, &renderState.viewingUniforms.bindGroup, &[]);
 		renderPass.set_bind_group(1, &self.styleUniforms.bindGroup, &[]);
+		renderPass.set_bind_group(2, &self.selectionBindGroup, &[]);
 		let buffers = data.data.geometry();
-		for (slot, buffer) in data.layout.bufferIndices().iter().enumerate() {
+		for (slot, buffer) in data.pipelineLayout().bufferIndices().iter().enumerate() {
 			renderPass.set_vertex_buffer(slot as u32, buffers[*buffer]);
 		}
 		renderPass.draw(0..4, 0..data.num());
+		self.objectId.markRendered();
 	}
 }
@@ -137,6 +137,7 @@ pub struct RenderState
 
 	pub(crate) framebuffer: hal::Framebuffer,
 	colorTargetState: wgpu::ColorTargetState,
+	idTargetState: Option<wgpu::ColorTargetState>,
 	depthStencilState: wgpu::DepthStencilState
 }
 impl RenderState
@@ -154,6 +155,7 @@ impl RenderState
 		// Done!
 		Self {
 			colorTargetState: defaultColorTargetState(&framebuffer.color0()),
+			idTargetState: framebuffer.ids().map(defaultColorTargetState),
 			depthStencilState: defaultDepthStencilState(&framebuffer.depthStencil().unwrap()),
 			viewingUniforms, framebuffer
 		}
@@ -171,6 +173,37 @@ impl RenderState
 		&self.colorTargetState
 	}
 
+	/// Reference the color target state for the [object ID attachment](hal::Framebuffer::ids) of the current managed
+	/// [render pass](GlobalPassInfo), if the main framebuffer has one.
+	pub fn idTargetState (&self) -> Option<&wgpu::ColorTargetState> {
+		self.idTargetState.as_ref()
+	}
+
+	/// Compile the list of color target states a pipeline rendering to the main framebuffer of the current managed
+	/// [render pass](GlobalPassInfo) must declare. Whenever [picking](Player::setPickingEnabled) is enabled, the main
+	/// framebuffer has an [object ID attachment](hal::Framebuffer::ids) in addition to the main color attachment, and
+	/// every pipeline used inside the pass needs to declare a target for it.
+	///
+	/// # Arguments
+	///
+	/// * `main` – The color target state to use for the main color attachment, usually derived from
+	///            [`Self::colorTargetState`].
+	/// * `writeIds` – Whether the pipeline writes object IDs, i.e. whether its fragment shader has an output at
+	///                location *1*. If not, the ID target gets declared with an empty write mask.
+	pub fn colorTargetStates (&self, main: wgpu::ColorTargetState, writeIds: bool)
+		-> Vec<Option<wgpu::ColorTargetState>>
+	{
+		let mut targets = Vec::with_capacity(2);
+		targets.push(Some(main));
+		if let Some(idTargetState) = &self.idTargetState {
+			targets.push(Some(wgpu::ColorTargetState {
+				write_mask: if writeIds { idTargetState.write_mask } else { wgpu::ColorWrites::empty() },
+				..idTargetState.clone()
+			}));
+		}
+		targets
+	}
+
 	/// Query the depth/stencil format used in the current managed [render pass](GlobalPassInfo) the render state
 	/// belongs to.
 	pub fn depthStencilFormat (&self) -> wgpu::TextureFormat {
@@ -191,9 +224,17 @@ impl RenderState
 
 	pub fn setFramebuffer (&mut self, newFramebuffer: hal::Framebuffer) {
 		self.depthStencilState = defaultDepthStencilState(newFramebuffer.depthStencil().unwrap());
+		self.idTargetState = newFramebuffer.ids().map(defaultColorTargetState);
 		self.framebuffer = newFramebuffer;
 	}
 
+	/// Add or remove the [object ID attachment](hal::Framebuffer::ids) of the main framebuffer. Pipelines rendering to
+	/// it need to be recreated afterwards, see [`Self::colorTargetStates`].
+	pub fn setIdAttachment (&mut self, context: &Context, enabled: bool) {
+		self.framebuffer.setIdAttachment(context, enabled);
+		self.idTargetState = self.framebuffer.ids().map(defaultColorTargetState);
+	}
+
 	pub fn resizeFramebuffer (&mut self, context: &Context, dims: glm::UVec2) {
 		self.framebuffer.resize(context, dims)
 	}
@@ -212,6 +253,21 @@ impl RenderState
 		})
 	}
 
+	/// Get the attachment for the [object ID attachment](hal::Framebuffer::ids) of the main framebuffer, if it has one.
+	/// IDs are always cleared to *0*, denoting that nothing was rendered.
+	pub fn getIdAttachment (&self) -> Option<wgpu::RenderPassColorAttachment<'_>>
+	{
+		self.framebuffer.ids().map(|ids| wgpu::RenderPassColorAttachment {
+			view: &ids.view(),
+			resolve_target: None,
+			ops: wgpu::Operations {
+				load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+				store: wgpu::StoreOp::Store,
+			},
+			depth_slice: None
+		})
+	}
+
 	pub fn getMainDepthStencilAttachment (&self, clear: Option<f32>)
 		-> Option<wgpu::RenderPassDepthStencilAttachment<'_>>
 	{
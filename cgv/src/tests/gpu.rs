@@ -0,0 +1,68 @@
+//////
+//
+// Imports
+//
+
+// Standard library
+/* nothing here yet */
+
+// Local imports
+use crate::*;
+
+
+
+//////
+//
+// Tests
+//
+
+#[test]
+fn test_compute_numWorkgroups ()
+{
+	use gpu::compute::numWorkgroups;
+	assert_eq!(numWorkgroups(glm::vec3(64, 1, 1), glm::vec3(64, 1, 1)), glm::vec3(1, 1, 1));
+	assert_eq!(numWorkgroups(glm::vec3(65, 1, 1), glm::vec3(64, 1, 1)), glm::vec3(2, 1, 1));
+	assert_eq!(numWorkgroups(glm::vec3(1920, 1080, 1), glm::vec3(8, 8, 1)), glm::vec3(240, 135, 1));
+	assert_eq!(numWorkgroups(glm::vec3(0, 7, 9), glm::vec3(4, 4, 4)), glm::vec3(0, 2, 3));
+}
+
+#[cfg(not(target_arch="wasm32"))]
+#[test]
+fn test_compute_Kernel_dispatch ()
+{
+	use gpu::compute::{Binding, Resource};
+
+	// Set up a context on the software fallback adapter, if there is one
+	let Some(context) = tests::fallbackContext() else {
+		return;
+	};
+	let package = shader::Package::fromSingleInstance(
+		shader::WgpuSourceType::WGSL, shader::ProgramInstance::generic(b"\
+override scale: u32 = 1u;
+@group(0) @binding(0) var<storage, read_write> values: array<u32>;
+@compute @workgroup_size(64)
+fn fill (@builtin(global_invocation_id) id: vec3u) {
+	if id.x < arrayLength(&values) {
+		values[id.x] = id.x * scale;
+	}
+}".to_vec()), Some("test_compute".into())
+	);
+	let buffer = context.device().create_buffer(&wgpu::BufferDescriptor {
+		label: None, size: 100*4, usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+		mapped_at_creation: false
+	});
+	let kernel = || gpu::Kernel::new(&package, "fill", glm::vec3(64, 1, 1), &[Binding::Storage { readOnly: false }]);
+
+	// Invocations beyond the requested count must be masked by the kernel itself
+	kernel().constant("scale", 3.).dispatchAdhoc(&context, &[Resource::Buffer(&buffer)], glm::vec3(100, 1, 1));
+	assert_eq!(
+		hal::readbackBuffer::<u32>(&context, &buffer, 0..100*4).unwrap(), (0..100).map(|i| i*3).collect::<Vec<_>>()
+	);
+
+	// Kernels with the same contents share their pipelines, while different constants need their own
+	kernel().constant("scale", 3.).dispatchAdhoc(&context, &[Resource::Buffer(&buffer)], glm::vec3(100, 1, 1));
+	assert_eq!(context.computePipelineCache.len(), 1);
+	kernel().dispatchAdhoc(&context, &[Resource::Buffer(&buffer)], glm::vec3(100, 1, 1));
+	assert_eq!(context.computePipelineCache.len(), 2);
+	assert_eq!(hal::readbackBuffer::<u32>(&context, &buffer, 0..8).unwrap(), vec![0, 1]);
+}
@@ -0,0 +1,148 @@
+//////
+//
+// Imports
+//
+
+// Standard library
+/* nothing here yet */
+
+// Local imports
+use crate::{*, hal::{VertexLayout, descriptorBindingType}};
+
+
+
+//////
+//
+// Structs
+//
+
+/// A per-vertex struct exercising format inference, explicit locations and skipped padding.
+#[repr(C)]
+#[derive(Clone, Copy, VertexLayout)]
+pub struct Vertex {
+	pub pos: glm::Vec4,
+	#[cgv_vertexAttr(normalized)] pub color: [u8; 4],
+	#[cgv_vertexAttr(location=3)] pub texcoord: glm::Vec2,
+	#[cgv_vertexAttr(skip)]       pub pad: u32
+}
+
+/// A per-instance struct exercising matrix columns and format overrides.
+#[repr(C)]
+#[derive(Clone, Copy, VertexLayout)]
+#[cgv_vertexLayout(instance)]
+pub struct Instance {
+	#[cgv_vertexAttr(location=4)]        pub transform: glm::Mat4,
+	                                     pub id: u32,
+	#[cgv_vertexAttr(format=Float16x2)]  pub halfs: [u16; 2]
+}
+
+
+
+//////
+//
+// Tests
+//
+
+#[test]
+fn test_derive_vertexLayout ()
+{
+	// Per-vertex layout
+	let layout = Vertex::layoutDesc();
+	assert_eq!(layout.array_stride, 32);
+	assert_eq!(layout.step_mode, wgpu::VertexStepMode::Vertex);
+	assert_eq!(layout.attributes, &[
+		wgpu::VertexAttribute { format: wgpu::VertexFormat::Float32x4, offset: 0, shader_location: 0 },
+		wgpu::VertexAttribute { format: wgpu::VertexFormat::Unorm8x4, offset: 16, shader_location: 1 },
+		wgpu::VertexAttribute { format: wgpu::VertexFormat::Float32x2, offset: 20, shader_location: 3 },
+	]);
+
+	// Per-instance layout
+	let layout = Instance::layoutDesc();
+	assert_eq!(layout.step_mode, wgpu::VertexStepMode::Instance);
+	assert_eq!(layout.attributes.len(), 6);
+	for (col, attrib) in layout.attributes[0..4].iter().enumerate() {
+		assert_eq!(attrib.format, wgpu::VertexFormat::Float32x4);
+		assert_eq!(attrib.offset, 16*col as u64);
+		assert_eq!(attrib.shader_location, 4+col as u32);
+	}
+	assert_eq!(layout.attributes[4].shader_location, 8);
+	assert_eq!(layout.attributes[5].format, wgpu::VertexFormat::Float16x2);
+	assert_eq!(layout.attributes[5].offset, 68);
+}
+
+#[test]
+fn test_descriptorBindingType ()
+{
+	// Helper producing the descriptor of a 2D texture with the given format and sample count
+	let desc = |format, sample_count| wgpu::TextureDescriptor {
+		label: None, size: wgpu::Extent3d { width: 4, height: 4, depth_or_array_layers: 1 }, mip_level_count: 1,
+		sample_count, dimension: wgpu::TextureDimension::D2, format, usage: wgpu::TextureUsages::TEXTURE_BINDING,
+		view_formats: &[]
+	};
+	let texture = |multisampled, sample_type| Some(wgpu::BindingType::Texture {
+		multisampled, view_dimension: wgpu::TextureViewDimension::D2, sample_type
+	});
+	let features = wgpu::Features::empty();
+
+	// Float formats are filterable unless multisampled or 32-bit without the corresponding feature
+	assert_eq!(
+		descriptorBindingType(&desc(wgpu::TextureFormat::Rgba8Unorm, 1), features),
+		texture(false, wgpu::TextureSampleType::Float { filterable: true })
+	);
+	assert_eq!(
+		descriptorBindingType(&desc(wgpu::TextureFormat::Rgba8Unorm, 4), features),
+		texture(true, wgpu::TextureSampleType::Float { filterable: false })
+	);
+	assert_eq!(
+		descriptorBindingType(&desc(wgpu::TextureFormat::R32Float, 1), features),
+		texture(false, wgpu::TextureSampleType::Float { filterable: false })
+	);
+
+	// Depth formats, including the depth aspect of combined depth/stencil formats
+	assert_eq!(
+		descriptorBindingType(&desc(wgpu::TextureFormat::Depth32Float, 1), features),
+		texture(false, wgpu::TextureSampleType::Depth)
+	);
+	assert_eq!(
+		descriptorBindingType(&desc(wgpu::TextureFormat::Depth24PlusStencil8, 1), features),
+		texture(false, wgpu::TextureSampleType::Depth)
+	);
+
+	// Integer formats
+	assert_eq!(
+		descriptorBindingType(&desc(wgpu::TextureFormat::R32Uint, 1), features),
+		texture(false, wgpu::TextureSampleType::Uint)
+	);
+	assert_eq!(
+		descriptorBindingType(&desc(wgpu::TextureFormat::Rg16Sint, 1), features),
+		texture(false, wgpu::TextureSampleType::Sint)
+	);
+
+	// Layered textures get array views
+	let mut layered = desc(wgpu::TextureFormat::Rgba8Unorm, 1);
+	layered.size.depth_or_array_layers = 6;
+	assert!(matches!(
+		descriptorBindingType(&layered, features),
+		Some(wgpu::BindingType::Texture { view_dimension: wgpu::TextureViewDimension::D2Array, .. })
+	));
+
+	// Formats that cannot be sampled as a whole are reported instead of causing a panic
+	assert_eq!(descriptorBindingType(&desc(wgpu::TextureFormat::NV12, 1), features), None);
+}
+
+#[cfg(not(target_arch="wasm32"))]
+#[test]
+fn test_readbackBuffer_emptyRange ()
+{
+	// Set up a context on the software fallback adapter, if there is one
+	let Some(context) = tests::fallbackContext() else {
+		return;
+	};
+	let buffer = context.device().create_buffer(&wgpu::BufferDescriptor {
+		label: None, size: 16, usage: wgpu::BufferUsages::COPY_SRC, mapped_at_creation: false
+	});
+
+	// Empty ranges yield no values instead of panicking, non-empty ones still work
+	assert!(hal::readbackBuffer::<u32>(&context, &buffer, 8..8).unwrap().is_empty());
+	assert_eq!(hal::readbackBuffer::<u32>(&context, &buffer, 0..16).unwrap(), vec![0; 4]);
+}
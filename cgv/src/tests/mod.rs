@@ -4,9 +4,25 @@
 // Module definitions
 //
 
+/// Tests for the `gpu` module.
+mod gpu;
+
+/// Tests for the `hal` module.
+mod hal;
+
+/// Tests for the `player` module.
+mod player;
+
+/// Tests for the `regression` module.
+#[cfg(not(target_arch="wasm32"))]
+mod regression;
+
 /// Tests for the `renderer` module.
 mod renderer;
 
+/// Tests for the `view` module.
+mod view;
+
 
 
 //////
@@ -21,6 +37,28 @@ mod renderer;
 use crate::*;
 
 
+////
+// Helpers
+
+/// Set up a graphics context on the software fallback adapter for tests that need to do actual GPU work.
+///
+/// # Returns
+///
+/// The context, or `None` if the system does not provide a suitable adapter, in which case the test should be skipped.
+#[cfg(not(target_arch="wasm32"))]
+pub(crate) fn fallbackContext () -> Option<Context>
+{
+	let options = player::HeadlessOptions { forceFallbackAdapter: true, ..Default::default() };
+	match player::createHal(&options) {
+		Ok((adapter, device, queue)) => Some(Context::fromHal(adapter, device, queue)),
+		Err(error) => {
+			tracing::warn!("no suitable adapter for GPU tests: {error}");
+			None
+		}
+	}
+}
+
+
 ////
 // Tests
 
@@ -0,0 +1,195 @@
+//////
+//
+// Imports
+//
+
+// Standard library
+/* nothing here yet */
+
+// Local imports
+use crate::{*, player::*};
+
+
+
+//////
+//
+// Tests
+//
+
+#[test]
+fn test_ActionInfo_fromInputEvent ()
+{
+	let bindings = Bindings::default();
+
+	// Drags map according to the pressed buttons and modifiers, keeping the modality
+	let drag = |buttons: [bool; 5], modifiers, source| InputEvent::Dragged(DragInfo {
+		buttons, modifiers, direction: glm::vec2(4., -2.), position: glm::vec2(16., 8.), source
+	});
+	let actions = ActionInfo::fromInputEvent(&drag(
+		[true, false, false, false, false], egui::Modifiers::NONE, InputSource::Touch
+	), &bindings);
+	assert_eq!(actions.len(), 1);
+	assert_eq!(actions[0].action, Action::Rotate(glm::vec2(4., -2.)));
+	assert_eq!(actions[0].source, InputSource::Touch);
+	let actions = ActionInfo::fromInputEvent(&drag(
+		[true, true, true, false, false], egui::Modifiers::SHIFT, InputSource::Mouse
+	), &bindings);
+	assert_eq!(actions[0].action, Action::DragRoll(-2.));
+	assert!(actions[0].action.rollAngle(0.25) < 0.);
+	assert!(actions[0].action.rollAngle(0.25) > actions[0].action.rollAngle(0.5));
+	assert_eq!(actions[1].action, Action::Pan(glm::vec2(4., -2.)));
+	assert_eq!(actions[2].action, Action::Dolly(-2.));
+
+	// Mouse wheel and pinch both zoom, in the same direction
+	let wheel = |modifiers| InputEvent::MouseWheel(MouseWheelInfo { amount: glm::vec2(0., 32.), modifiers });
+	assert!(matches!(
+		ActionInfo::fromInputEvent(&wheel(egui::Modifiers::NONE), &bindings)[0].action,
+		Action::Zoom(factor) if factor > 1.
+	));
+	assert!(matches!(
+		ActionInfo::fromInputEvent(&wheel(egui::Modifiers::ALT), &bindings)[0].action,
+		Action::FieldOfView(angle) if angle > 0.
+	));
+	let pinch = InputEvent::Pinch(PinchInfo {
+		zoom: 1.25, modifiers: egui::Modifiers::NONE, source: InputSource::Touch
+	});
+	assert_eq!(ActionInfo::fromInputEvent(&pinch, &bindings)[0].action, Action::Zoom(1.25));
+
+	// Two-finger gestures
+	let multiTouch = InputEvent::MultiTouch(MultiTouchInfo {
+		numTouches: 2, modifiers: egui::Modifiers::NONE, center: glm::vec2(10., 10.), translation: glm::vec2(1., 0.)
+	});
+	assert_eq!(ActionInfo::fromInputEvent(&multiTouch, &bindings)[0].action, Action::Pan(glm::vec2(1., 0.)));
+	let rotate = InputEvent::Rotate(RotateInfo { angle: 0.1, modifiers: egui::Modifiers::NONE });
+	assert_eq!(ActionInfo::fromInputEvent(&rotate, &bindings)[0].action, Action::Roll(0.1));
+	assert_eq!(Action::Roll(0.1).rollAngle(0.25), 0.1);
+
+	// Double clicks focus, raw touches imply no action
+	let doubleClick = InputEvent::DoubleClick(ClickInfo {
+		button: egui::PointerButton::Primary, modifiers: egui::Modifiers::NONE, position: glm::vec2(3, 7),
+		source: InputSource::Touch
+	});
+	assert_eq!(ActionInfo::fromInputEvent(&doubleClick, &bindings)[0].action, Action::Focus(glm::vec2(3, 7)));
+	let touch = InputEvent::Touch(TouchInfo {
+		id: 0, phase: egui::TouchPhase::Start, modifiers: egui::Modifiers::NONE, position: glm::vec2(3., 7.)
+	});
+	assert!(ActionInfo::fromInputEvent(&touch, &bindings).is_empty());
+}
+
+#[test]
+fn test_Bindings ()
+{
+	// Movement keys match by physical location regardless of the logical key the layout produces, e.g. AZERTY
+	let mut bindings = Bindings::default();
+	assert!(bindings.matchesKey(Command::MoveForward, egui::Key::Z, Some(egui::Key::W), Some(&egui::Modifiers::NONE)));
+	assert!(!bindings.matchesKey(Command::MoveForward, egui::Key::W, Some(egui::Key::Z), None));
+	assert!(bindings.matchesKey(Command::MoveForward, egui::Key::W, Some(egui::Key::W), Some(&egui::Modifiers::SHIFT)));
+	assert!(bindings.modifiersHeld(Command::MoveSlowly, &egui::Modifiers::SHIFT));
+	assert!(!bindings.modifiersHeld(Command::MoveSlowly, &egui::Modifiers::NONE));
+	let num7 = egui::Key::Num7;
+	assert!(bindings.matchesKey(Command::ViewTop, num7, Some(num7), Some(&egui::Modifiers::NONE)));
+	assert!(!bindings.matchesKey(Command::ViewTop, egui::Key::Num1, Some(egui::Key::Num1), None));
+
+	// The most specific drag chord wins
+	let drags = [Command::Rotate, Command::Roll, Command::Pan, Command::Dolly];
+	let primary = egui::PointerButton::Primary;
+	assert_eq!(bindings.dragCommand(&drags, primary, &egui::Modifiers::NONE), Some(Command::Rotate));
+	assert_eq!(bindings.dragCommand(&drags, primary, &egui::Modifiers::SHIFT), Some(Command::Roll));
+	assert_eq!(bindings.dragCommand(&drags, egui::PointerButton::Extra1, &egui::Modifiers::NONE), None);
+
+	// Re-bound and unbound commands
+	bindings.set(Command::Pan, Some(Chord::new(Trigger::Pointer(primary), egui::Modifiers::ALT)));
+	bindings.set(Command::Dolly, None);
+	assert_eq!(bindings.dragCommand(&drags, primary, &egui::Modifiers::ALT), Some(Command::Pan));
+	assert_eq!(bindings.dragCommand(&drags, egui::PointerButton::Middle, &egui::Modifiers::NONE), None);
+	bindings.resetToDefaults();
+	assert_eq!(bindings, Bindings::default());
+}
+
+#[test]
+fn test_Bindings_persistence ()
+{
+	// Explicit unbinds survive a round-trip
+	let mut bindings = Bindings::default();
+	bindings.set(Command::Pan, None);
+	bindings.set(Command::Quit, Some(Chord::new(Trigger::Key(egui::Key::Q), egui::Modifiers::CTRL)));
+	let stored = serde_yaml_ng::to_string(&bindings).unwrap();
+	assert_eq!(serde_yaml_ng::from_str::<Bindings>(&stored).unwrap(), bindings);
+
+	// Commands missing from a stored map (e.g. added after storing) get their default chord
+	let mut stored: serde_yaml_ng::Value = serde_yaml_ng::to_value(&bindings).unwrap();
+	stored["map"].as_mapping_mut().unwrap().remove("ZoomToFit").unwrap();
+	stored.as_mapping_mut().unwrap().remove("unbound").unwrap();
+	let restored: Bindings = serde_yaml_ng::from_value(stored).unwrap();
+	assert_eq!(restored.get(Command::ZoomToFit), Some(&Command::ZoomToFit.defaultChord()));
+	assert_eq!(restored.get(Command::Quit), bindings.get(Command::Quit));
+}
+
+#[test]
+fn test_ObjectId_picking ()
+{
+	// Raw values round-trip, with *0* denoting that nothing was rendered
+	let owned = OwnedObjectId::new();
+	assert_eq!(ObjectId::fromRaw(owned.get()), Some(owned.id()));
+	assert_eq!(ObjectId::fromRaw(0), None);
+	assert_ne!(OwnedObjectId::new().id(), owned.id());
+
+	// Texels decode into picks, attributed to the application that last rendered the object
+	let position = glm::vec2(3, 5);
+	assert!(Pick::fromTexel([0, 7], position).is_none());
+	let pick = Pick::fromTexel([owned.get(), 7], position).unwrap();
+	assert_eq!((pick.object, pick.element, pick.position), (owned.id(), 7, position));
+	assert!(pick.application.is_none());
+	setRenderingApplication(Some(3));
+	owned.markRendered();
+	setRenderingApplication(None);
+	let owner = owned.owner();
+	assert!(owner.is_some());
+	assert_eq!(Pick::fromTexel([owned.get(), 0], position).unwrap().application, owner);
+
+	// Dropping the ID stops attributing it
+	let id = owned.id();
+	drop(owned);
+	assert!(id.owner().is_none());
+	assert!(Pick::fromTexel([id.get(), 0], position).unwrap().application.is_none());
+}
+
+#[cfg(not(target_arch="wasm32"))]
+#[test]
+fn test_writeY4mFrame ()
+{
+	// A black and a white pixel end up at the limits of the limited luma range with neutral chroma, in planar layout
+	let srgb = image::RgbaImage::from_raw(2, 1, vec![0, 0, 0, 255, 255, 255, 255, 255]).unwrap();
+	let mut frame = Vec::new();
+	writeY4mFrame(&mut frame, &srgb).unwrap();
+	assert_eq!(frame, [b"FRAME\n".as_slice(), &[16, 235], &[128, 128], &[128, 128]].concat());
+}
+
+#[cfg(not(target_arch="wasm32"))]
+#[test]
+fn test_downsampleTile ()
+{
+	// Each 2×2 block of the tile gets averaged, with the second block clipped by the right edge of the output
+	let tile = image::DynamicImage::ImageRgba32F(image::Rgba32FImage::from_fn(4, 2, |x, y| {
+		let value = (x + 4*y) as f32;
+		image::Rgba([value, 2.*value, 0., 1.])
+	}));
+	let mut output = image::DynamicImage::ImageRgba32F(image::Rgba32FImage::new(3, 2));
+	downsampleTile(&tile, 2, &mut output, glm::vec2(2, 1));
+	let output = output.into_rgba32f();
+	assert_eq!(output.get_pixel(2, 1).0, [2.5, 5., 0., 1.]);
+	assert!(output.enumerate_pixels().all(|(x, y, pixel)| (x, y) == (2, 1) || pixel.0 == [0.; 4]));
+
+	// Unclipped blocks all arrive, and 8-bit tiles are averaged in linear space
+	let mut output = image::DynamicImage::ImageRgba32F(image::Rgba32FImage::new(2, 1));
+	downsampleTile(&tile, 2, &mut output, glm::vec2(0, 0));
+	assert_eq!(output.into_rgba32f().pixels().map(|p| p.0).collect::<Vec<_>>(), [[2.5, 5., 0., 1.], [4.5, 9., 0., 1.]]);
+	let tile = image::DynamicImage::ImageRgba8(image::RgbaImage::from_fn(2, 2, |x, _| {
+		if x == 0 { image::Rgba([0, 0, 0, 255]) } else { image::Rgba([255, 255, 255, 255]) }
+	}));
+	let mut output = image::DynamicImage::ImageRgba8(image::RgbaImage::new(1, 1));
+	downsampleTile(&tile, 2, &mut output, glm::vec2(0, 0));
+	let expected = egui::ecolor::gamma_u8_from_linear_f32(0.5);
+	assert_eq!(output.to_rgba8().get_pixel(0, 0).0, [expected, expected, expected, 255]);
+	assert!(expected > 128);
+}
@@ -0,0 +1,38 @@
+//////
+//
+// Imports
+//
+
+// Standard library
+/* nothing here yet */
+
+// Local imports
+use crate::regression::*;
+
+
+
+//////
+//
+// Tests
+//
+
+#[test]
+fn test_compare ()
+{
+	let reference = image::RgbaImage::from_pixel(4, 4, image::Rgba([100, 150, 200, 255]));
+	let mut actual = reference.clone();
+	actual.put_pixel(1, 2, image::Rgba([103, 150, 200, 255]));
+	actual.put_pixel(3, 0, image::Rgba([100, 150, 190, 255]));
+
+	// One pixel exceeds the per-channel tolerance and gets highlighted in the diff
+	let strict = Tolerance { perChannel: 4, maxFailingFraction: 0. };
+	let comparison = compare(&actual, &reference, &strict).unwrap();
+	assert_eq!((comparison.numFailingPixels, comparison.numPixels, comparison.maxDifference), (1, 16, 10));
+	assert_eq!(comparison.diff.get_pixel(3, 0).0, [133, 0, 0, 255]);
+	assert_eq!(comparison.diff.get_pixel(1, 2).0[1], comparison.diff.get_pixel(1, 2).0[0]);
+	assert!(!comparison.passes(&strict));
+	assert!(comparison.passes(&Tolerance { maxFailingFraction: 1./16., ..strict }));
+
+	// Size mismatches are errors
+	assert!(compare(&image::RgbaImage::new(4, 3), &reference, &strict).is_err());
+}
@@ -0,0 +1,84 @@
+
+//////
+//
+// Imports
+//
+
+// Standard library
+/* nothing here yet */
+
+// Local imports
+use crate::{*, renderer::data::{*, gpu::*}};
+
+
+
+//////
+//
+// Functions
+//
+
+/// Create a single-buffer layout with positions (and co-located radii) in the first slot and normals (and co-located
+/// radius derivatives) in the second.
+fn posRadNormalRadDerivLayout () -> BufferLayout {
+	BufferLayout {
+		buffers: vec![VertexBufferLayoutDesc {
+			array_stride: 2*size_of::<glm::Vec4>() as wgpu::BufferAddress,
+			attributes: Vec::from(wgpu::vertex_attr_array![0=>Float32x4, 1=>Float32x4])
+		}],
+		positions: BufferAttributeSlot::new(0, 0, 0),
+		attribs: GeometryAttributeOccupancy::default()
+			.withAttribute(GA::Radii, BufferAttributeSlot::new(0, 0, 3))
+			.withAttribute(GA::Normals, BufferAttributeSlot::new(0, 1, 0))
+			.withAttribute(GA::RadiusDerivs, BufferAttributeSlot::new(0, 1, 3))
+	}
+}
+
+
+
+//////
+//
+// Tests
+//
+
+#[test]
+fn test_GeometryInput_generate ()
+{
+	// Expose everything
+	let layout = posRadNormalRadDerivLayout();
+	let input = layout.geometryInput(GAF::all(), wgpu::VertexStepMode::Instance).unwrap();
+	let src = input.sourceCode();
+	assert_eq!(input.includedAttribs().bits(), (GAF::NORMALS|GAF::RADII|GAF::RADIUS_DERIVS).bits());
+	assert!(src.contains("[vk::location(0)] public var positions: float4;"));
+	assert!(src.contains("[vk::location(1)] public var normals: float4;"));
+	assert!(src.contains("return float4(float3(this.positions.xyz), 1);"));
+	assert!(src.contains("func radius (defaultValue: float) -> float { return float(this.positions.w); }"));
+	assert!(src.contains("func radiusDeriv (defaultValue: float) -> float { return float(this.normals.w); }"));
+	assert!(src.contains("func hasColors () -> bool { return false; }"));
+	assert!(src.contains("func color (defaultValue: float4) -> float4 { return defaultValue; }"));
+	assert!(src.contains("export struct GeometryInput: cgv::geom::IGeometryInput = GeometryInputImpl;"));
+	assert_eq!(input.pipelineLayout().bufferLayouts()[0].attributes.len(), 2);
+
+	// Filter out the normals – their slot must still be declared to access the co-located radius derivatives
+	let filtered = layout.geometryInput(GAF::RADIUS_DERIVS, wgpu::VertexStepMode::Instance).unwrap();
+	let src = filtered.sourceCode();
+	assert!(src.contains("[vk::location(1)] public var normals: float4;"));
+	assert!(src.contains("func hasNormals () -> bool { return false; }"));
+	assert!(src.contains("func radius (defaultValue: float) -> float { return defaultValue; }"));
+	assert!(src.contains("func radiusDeriv (defaultValue: float) -> float { return float(this.normals.w); }"));
+
+	// Identical layouts should map to the same module, different ones should not
+	let again = posRadNormalRadDerivLayout().geometryInput(GAF::all(), wgpu::VertexStepMode::Instance).unwrap();
+	assert_eq!(input.modulePath(), again.modulePath());
+	assert_ne!(input.modulePath(), filtered.modulePath());
+}
+
+#[test]
+fn test_GeometryInput_generate_rejectsMissingHost ()
+{
+	// Radius derivatives co-located with normals that are not part of the layout
+	let mut layout = posRadNormalRadDerivLayout();
+	layout.attribs = GeometryAttributeOccupancy::default()
+		.withAttribute(GA::Radii, BufferAttributeSlot::new(0, 0, 3))
+		.withAttribute(GA::RadiusDerivs, BufferAttributeSlot::new(0, 1, 3));
+	assert!(layout.geometryInput(GAF::all(), wgpu::VertexStepMode::Instance).is_err());
+}
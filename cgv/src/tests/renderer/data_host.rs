@@ -289,6 +289,11 @@ fn test_HostData_blanketImpls ()
 	assert_eq!(iter.next(), None);
 	assert_eq!(testDataVec.pos(0), glm::vec3(0., 0., 0.));
 	assert_eq!(testDataVec.pos(1), glm::vec3(1., 2., 3.));
+	// - bounds
+	let bounds = util::math::Aabb::new(glm::vec3(0., 0., 0.), glm::vec3(1., 2., 3.));
+	assert_eq!(testData.bounds(), Some(bounds));
+	assert_eq!(testDataVec.bounds(), Some(bounds));
+	assert_eq!(Vec::<PosTanColor>::new().bounds(), None);
 	// - tangents
 	iter = testData.tangents();
 	assert_eq!(testData.len(), iter.len());
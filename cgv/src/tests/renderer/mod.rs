@@ -10,6 +10,13 @@ mod data;
 /// Tests for the `data::host` module.
 mod data_host;
 
+/// Tests for the `data::gpu` module.
+mod data_gpu;
+
+/// Image-based regression tests for the `spheres` module.
+#[cfg(not(target_arch="wasm32"))]
+mod spheres;
+
 
 
 //////
@@ -0,0 +1,124 @@
+//////
+//
+// Imports
+//
+
+// Standard library
+use std::sync::Arc;
+
+// Local imports
+use crate::{self as cgv, *, renderer::data::InterleavedElem, regression::*};
+
+
+
+//////
+//
+// Structs
+//
+
+/// A sphere with an individual radius and color.
+#[repr(C)]
+#[derive(Clone, InterleavedElem)]
+pub struct Sphere {
+	#[cgv_renderAttr(pos)]    pub pos: glm::Vec3,
+	#[cgv_renderAttr(radius)] pub radius: f32,
+	#[cgv_renderAttr(color)]  pub color: cgv::RGBA
+}
+
+/// Minimal application rendering a fixed set of spheres.
+struct SpheresScene {
+	spheres: renderer::Managed<renderer::Spheres>
+}
+impl Application for SpheresScene
+{
+	fn title (&self) -> &str { "Spheres regression scene" }
+
+	fn preInit (&mut self, _: &mut Player) -> Result<()> { Ok(()) }
+
+	fn recreatePipelines (&mut self, context: &Context, _: &RenderSetup, globalPasses: &GlobalPasses) {
+		self.spheres.rebuildForGlobalPasses(context, *globalPasses);
+	}
+
+	fn postInit (&mut self, player: &mut Player) -> Result<()> {
+		let camera = player.camera.parameters_mut();
+		camera.intrinsics.f = 2.;
+		camera.extrinsics.eye = glm::vec3(0., 0., 2.);
+		Ok(())
+	}
+
+	fn input (&mut self, _: &InputEvent, _: &mut Player, _: player::Handle) -> EventOutcome {
+		EventOutcome::NotHandled
+	}
+
+	fn resize (&mut self, _: &Context, _: glm::UVec2) {}
+
+	fn update (&mut self, _: &mut Player, _: player::Handle) -> bool { false }
+
+	fn prepareFrame (&mut self, _: &Context, _: &RenderState, _: &GlobalPassInfo)
+		-> Option<Vec<wgpu::CommandBuffer>> { None }
+
+	fn render (
+		&mut self, context: &Context, renderState: &RenderState, renderPass: &mut wgpu::RenderPass,
+		globalPass: &GlobalPassInfo
+	) -> Option<Vec<wgpu::CommandBuffer>> {
+		self.spheres.renderForGlobalPass(context, renderState, renderPass, globalPass.index);
+		None
+	}
+
+	fn ui (&mut self, _: &mut egui::Ui, _: &mut Player) {}
+}
+
+
+
+//////
+//
+// Functions
+//
+
+/// Create the [`SpheresScene`]: three overlapping spheres of different radii and colors.
+fn createSpheresScene (context: &Context, renderSetup: &RenderSetup, _: run::Environment)
+	-> Result<Box<dyn Application>>
+{
+	let spheres = vec![
+		Sphere {
+			pos: glm::vec3(-0.4, -0.2, 0.), radius: 0.35, color: cgv::RGBA::from_rgba_unmultiplied(1., 0.2, 0.2, 1.)
+		},
+		Sphere {
+			pos: glm::vec3(0.35, -0.1, -0.3), radius: 0.5, color: cgv::RGBA::from_rgba_unmultiplied(0.2, 1., 0.2, 1.)
+		},
+		Sphere {
+			pos: glm::vec3(0., 0.4, 0.2), radius: 0.25, color: cgv::RGBA::from_rgba_unmultiplied(0.2, 0.2, 1., 1.)
+		}
+	];
+	let data: Arc<dyn renderer::GpuData> = renderer::data::InterleavedBuffer::fromHost(
+		context, &spheres, Default::default(), Some("SpheresRegression_data")
+	);
+	let mut renderer = renderer::Managed::new(renderer::Spheres::new(context, renderSetup));
+	renderer.setData(data.try_into()?); // <- fallible only with runtime shader compilation
+	Ok(Box::new(SpheresScene { spheres: renderer }))
+}
+
+
+
+//////
+//
+// Tests
+//
+
+/// Renders the scene from the front and from the side. References live in `src/tests/renderer/references` – see
+/// [`RegressionTest`] on how to (re-)record them.
+#[test]
+#[ignore = "no reference images recorded yet – record them with CGV_UPDATE_REFERENCES=1"]
+fn test_Spheres_regression ()
+{
+	let mut side = view::CameraParameters::defaultWithAspect(1.);
+	side.intrinsics.f = 2.;
+	side.extrinsics.eye = glm::vec3(2., 0., 0.);
+	side.extrinsics.dir = glm::vec3(-1., 0., 0.);
+	RegressionTest::new("spheres", concat!(env!("CARGO_MANIFEST_DIR"), "/src/tests/renderer/references"))
+		.tolerance(Tolerance { perChannel: 4, maxFailingFraction: 0.001 })
+		.shot(player::Shot::Frames(1))
+		.shot(player::Shot::Camera(side))
+		.run(Box::new(createSpheresScene))
+		.unwrap();
+}
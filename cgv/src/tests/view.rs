@@ -0,0 +1,301 @@
+//////
+//
+// Imports
+//
+
+// Standard library
+/* nothing here yet */
+
+// Local imports
+use crate::{self as cgv, *, renderer::data::*, view::{
+	Selection, SelectionMode, SelectionRegion, FoV, Keyframe, CameraPath, CameraParameters, ViewBookmark, ViewBookmarks,
+	projectionWindowTransform, TrackballInteractor
+}, player::Command, util::math::Aabb};
+
+
+
+//////
+//
+// Structs
+//
+
+/// A position-only `InterleavedElem` for testing selection against.
+#[derive(Clone, InterleavedElem)]
+struct Point {
+	#[cgv_renderAttr(pos)] pos: glm::Vec3
+}
+
+
+
+//////
+//
+// Tests
+//
+
+#[test]
+fn test_Selection_bitOperations ()
+{
+	// Setting and querying
+	let mut selection = Selection::new(40);
+	assert_eq!(selection.words().len(), 2);
+	assert!(selection.isEmpty());
+	selection.set(3, true); selection.set(35, true); selection.set(39, true);
+	assert!(selection.contains(3) && selection.contains(35) && !selection.contains(4) && !selection.contains(40));
+	assert_eq!(selection.count(), 3);
+	assert_eq!(selection.iter().collect::<Vec<_>>(), vec![3, 35, 39]);
+	selection.set(35, false);
+	assert_eq!(selection.iter().collect::<Vec<_>>(), vec![3, 39]);
+
+	// Combining
+	let mut other = Selection::new(40);
+	other.set(3, true); other.set(7, true);
+	let mut added = selection.clone();
+	added.combine(&other, SelectionMode::Add);
+	assert_eq!(added.iter().collect::<Vec<_>>(), vec![3, 7, 39]);
+	let mut subtracted = selection.clone();
+	subtracted.combine(&other, SelectionMode::Subtract);
+	assert_eq!(subtracted.iter().collect::<Vec<_>>(), vec![39]);
+	let mut replaced = selection;
+	replaced.combine(&other, SelectionMode::Replace);
+	assert_eq!(replaced, other);
+}
+
+#[test]
+fn test_SelectionRegion_selectHost ()
+{
+	// With an identity transformation, the upper-left quarter of the viewport covers x∈[-1,0] and y∈[0,1]
+	let dims = glm::vec2(100, 100);
+	let points = vec![
+		Point { pos: glm::vec3(-0.5, 0.5, 0.5) },  // inside
+		Point { pos: glm::vec3(0.5, 0.5, 0.5) },   // right of the region
+		Point { pos: glm::vec3(-0.5, -0.5, 0.5) }, // below the region
+		Point { pos: glm::vec3(-0.5, 0.5, 2.) },   // beyond the far plane
+		Point { pos: glm::vec3(-0.1, 0.9, 0.) }    // inside, on the near plane
+	];
+
+	// Rectangle
+	let rectangle = SelectionRegion::rectangle(
+		glm::vec2(50., 50.), glm::vec2(0., 0.), dims, glm::Mat4::identity(), SelectionMode::Replace
+	);
+	assert_eq!(rectangle.bounds(), (glm::vec2(0., 0.), glm::vec2(50., 50.)));
+	assert_eq!(rectangle.selectHost(&points).iter().collect::<Vec<_>>(), vec![0, 4]);
+
+	// Lasso – a triangle only containing the first point
+	let lasso = SelectionRegion::lasso(
+		vec![glm::vec2(0., 0.), glm::vec2(40., 20.), glm::vec2(0., 50.)], dims, glm::Mat4::identity(),
+		SelectionMode::Add
+	);
+	assert_eq!(lasso.selectHost(&points).iter().collect::<Vec<_>>(), vec![0]);
+
+	// Degenerate lassos select nothing
+	let degenerate = SelectionRegion::lasso(
+		vec![glm::vec2(0., 0.), glm::vec2(50., 50.)], dims, glm::Mat4::identity(), SelectionMode::Replace
+	);
+	assert!(degenerate.selectHost(&points).isEmpty());
+}
+
+#[test]
+fn test_CameraPath_sample ()
+{
+	// Keyframes equally spaced along a straight flight, with the field of view widening
+	let keyframe = |time: f32| Keyframe {
+		time, eye: glm::vec3(time, 0., 0.), dir: glm::vec3(0., 0., -1.), up: glm::vec3(0., 1., 0.),
+		fovY: FoV::Perspective(1. + 0.5*time), f: 2.
+	};
+	let mut path = CameraPath::new();
+	assert!(path.sample(0.).is_none());
+	for time in [2., 0., 3., 1.] {
+		path.insert(keyframe(time));
+	}
+	assert_eq!(path.keyframes().iter().map(|k| k.time).collect::<Vec<_>>(), [0., 1., 2., 3.]);
+	assert_eq!(path.duration(), 3.);
+
+	// Keyframes are interpolated exactly, times outside the path get clamped
+	assert_eq!(path.sample(1.), Some(keyframe(1.)));
+	assert_eq!(path.sample(-1.), Some(keyframe(0.)));
+	assert_eq!(path.sample(5.), Some(keyframe(3.)));
+
+	// Uniform linear motion is reproduced in between keyframes
+	let sample = path.sample(1.5).unwrap();
+	assert!((sample.eye - glm::vec3(1.5, 0., 0.)).norm() < 1e-5);
+	assert!(matches!(sample.fovY, FoV::Perspective(fov) if (fov - 1.75).abs() < 1e-5));
+	assert!((sample.dir.norm() - 1.).abs() < 1e-5 && sample.up.dot(&sample.dir).abs() < 1e-5);
+
+	// Replacing keyframes and serialization round trip
+	path.insert(Keyframe { f: 4., ..keyframe(2.) });
+	assert_eq!(path.keyframes().len(), 4);
+	assert_eq!(CameraPath::deserialize(path.serialize()).unwrap(), path);
+}
+
+#[test]
+fn test_ViewBookmark_text ()
+{
+	// Text round trip reproduces the exact view
+	let mut parameters = CameraParameters::defaultWithAspect(1.5);
+	parameters.extrinsics.eye = glm::vec3(1., 2., 3.);
+	parameters.intrinsics.fovY = FoV::Orthographic(2.5);
+	let bookmark = ViewBookmark::new("Figure 3", &parameters);
+	let parsed = ViewBookmark::fromText(&bookmark.toText()).unwrap();
+	assert_eq!(parsed.name, "Figure 3");
+	assert!(parsed.parameters.intrinsics == parameters.intrinsics);
+	assert!(parsed.parameters.extrinsics == parameters.extrinsics);
+	assert!(ViewBookmark::fromText("not a view").is_err());
+
+	// Bookmark collections survive serialization and can be merged
+	let mut bookmarks = ViewBookmarks::new();
+	bookmarks.add(bookmark.clone());
+	bookmarks.add(ViewBookmark::new("Overview", &CameraParameters::defaultWithAspect(1.)));
+	let mut merged = ViewBookmarks::deserialize(bookmarks.serialize()).unwrap();
+	merged.append(ViewBookmarks::deserialize(bookmarks.serialize()).unwrap());
+	assert_eq!(merged.bookmarks().iter().map(|b| b.name.as_str()).collect::<Vec<_>>(), [
+		"Figure 3", "Overview", "Figure 3", "Overview"
+	]);
+	assert!(!merged.isTransitioning());
+}
+
+#[test]
+fn test_projectionWindowTransform ()
+{
+	// The corners and center of the window end up at the corners and center of normalized device space, regardless of
+	// the homogeneous coordinate, with depth left alone
+	let transform = projectionWindowTransform(&glm::vec2(0., -1.), &glm::vec2(1., 0.));
+	let ndc = |x: f32, y: f32, w: f32| {
+		let p = transform * glm::vec4(x*w, y*w, 0.25*w, w);
+		glm::vec3(p.x/p.w, p.y/p.w, p.z/p.w)
+	};
+	for w in [1., 3.] {
+		assert!((ndc(0., -1., w) - glm::vec3(-1., -1., 0.25)).norm() < 1e-6);
+		assert!((ndc(1., 0., w) - glm::vec3(1., 1., 0.25)).norm() < 1e-6);
+		assert!((ndc(0.5, -0.5, w) - glm::vec3(0., 0., 0.25)).norm() < 1e-6);
+	}
+
+	// The full window is the identity
+	assert_eq!(projectionWindowTransform(&glm::vec2(-1., -1.), &glm::vec2(1., 1.)), glm::Mat4::identity());
+}
+
+#[test]
+fn test_TrackballInteractor_projectOntoBall ()
+{
+	// The center maps to the pole facing the viewer, and the sphere and the hyperbolic sheet meet continuously
+	let dims = glm::vec2(300., 200.);
+	let center = glm::vec2(150., 100.);
+	assert!((TrackballInteractor::projectOntoBall(&center, &dims) - glm::vec3(0., 0., 1.)).norm() < 1e-6);
+	let seam = 100. * 0.5f32.sqrt();
+	for dir in [glm::vec2(1., 0.), glm::vec2(0., 1.), glm::vec2(-0.6, 0.8)] {
+		let inside = TrackballInteractor::projectOntoBall(&(center + (seam-0.01)*dir), &dims);
+		let outside = TrackballInteractor::projectOntoBall(&(center + (seam+0.01)*dir), &dims);
+		assert!((inside - outside).norm() < 1e-3);
+		assert!((inside.norm() - 1.).abs() < 1e-5 && (outside.norm() - 1.).abs() < 1e-5);
+	}
+
+	// Screen y points down, ball y points up
+	assert!(TrackballInteractor::projectOntoBall(&glm::vec2(150., 20.), &dims).y > 0.);
+}
+
+#[test]
+fn test_TrackballInteractor_rotateScene ()
+{
+	// Rotating keeps the focus point, and re-orthonormalizes a slightly skewed up direction
+	let mut params = CameraParameters::defaultWithAspect(1.);
+	params.extrinsics.eye = glm::vec3(1., 2., 3.);
+	params.extrinsics.dir = glm::vec3(0., 0., -1.);
+	params.extrinsics.up = glm::vec3(0., 1., 0.05);
+	params.intrinsics.f = 4.;
+	let focus = |p: &CameraParameters| p.extrinsics.eye + p.intrinsics.f*p.extrinsics.dir;
+	let before = focus(&params);
+	for _ in 0..16 {
+		TrackballInteractor::rotateScene(&mut params, &glm::vec3(1., 1., 0.).normalize(), 0.3);
+	}
+	let e = &params.extrinsics;
+	assert!((focus(&params) - before).norm() < 1e-4);
+	assert!((e.dir.norm() - 1.).abs() < 1e-5 && (e.up.norm() - 1.).abs() < 1e-5 && e.up.dot(&e.dir).abs() < 1e-5);
+
+	// Rotating the scene rotates the camera the opposite way
+	let mut params = CameraParameters::defaultWithAspect(1.);
+	params.extrinsics.dir = glm::vec3(0., 0., -1.);
+	params.extrinsics.up = glm::vec3(0., 1., 0.);
+	TrackballInteractor::rotateScene(&mut params, &glm::vec3(0., 1., 0.), std::f32::consts::FRAC_PI_2);
+	assert!((params.extrinsics.dir - glm::vec3(1., 0., 0.)).norm() < 1e-5);
+}
+
+#[test]
+fn test_TrackballInteractor_snap ()
+{
+	// Snapping keeps the focus point, and repeating the same key alternates between opposite sides
+	let mut trackball = TrackballInteractor::new();
+	let mut params = CameraParameters::defaultWithAspect(1.);
+	params.extrinsics.eye = glm::vec3(1., 2., 3.);
+	let focus = |p: &CameraParameters| p.extrinsics.eye + p.intrinsics.f*p.extrinsics.dir;
+	let dirs = (0..3).map(|_| {
+		let target = trackball.snap(Command::ViewFront, &params);
+		assert!((focus(&target) - focus(&params)).norm() < 1e-5);
+		target.extrinsics.dir
+	}).collect::<Vec<_>>();
+	assert_eq!(dirs, [glm::vec3(0., 0., -1.), glm::vec3(0., 0., 1.), glm::vec3(0., 0., -1.)]);
+
+	// Another key starts over on its front side
+	let target = trackball.snap(Command::ViewTop, &params);
+	assert_eq!((target.extrinsics.dir, target.extrinsics.up), (glm::vec3(0., -1., 0.), glm::vec3(0., 0., -1.)));
+	let target = trackball.snap(Command::ViewSide, &params);
+	assert_eq!(target.extrinsics.dir, glm::vec3(-1., 0., 0.));
+}
+
+#[test]
+fn test_Intrinsics_adaptDepthRange ()
+{
+	// The planes enclose the box tightly when looking at it from outside
+	let bounds = Aabb::new(glm::vec3(-1., -1., -1.), glm::vec3(1., 1., 1.));
+	let mut params = CameraParameters::defaultWithAspect(1.);
+	params.extrinsics.eye = glm::vec3(0., 0., 5.);
+	params.extrinsics.dir = glm::vec3(0., 0., -1.);
+	params.intrinsics.adaptDepthRange(&params.extrinsics, &bounds);
+	let i = params.intrinsics;
+	assert!(i.zNear < 4. && i.zNear > 3.9 && i.zFar > 6. && i.zFar < 6.1);
+
+	// With the eye inside the box, the near plane stays in front of the eye at a sane ratio to the far plane
+	params.extrinsics.eye = glm::vec3(0., 0., 0.5);
+	params.intrinsics.adaptDepthRange(&params.extrinsics, &bounds);
+	let i = params.intrinsics;
+	assert!(i.zNear > 0. && i.zNear < 0.01 && i.zFar > 1.5 && i.zFar/i.zNear <= 4096.*1.001);
+
+	// A box behind the eye leaves the planes untouched
+	let before = params.intrinsics;
+	params.extrinsics.eye = glm::vec3(0., 0., -5.);
+	params.intrinsics.adaptDepthRange(&params.extrinsics, &bounds);
+	assert!(params.intrinsics == before);
+}
+
+#[test]
+fn test_CameraParameters_fitToBounds ()
+{
+	// The whole box ends up in view, centered along the unchanged viewing direction, for either projection and
+	// regardless of whether the viewport is wide or tall
+	let bounds = Aabb::new(glm::vec3(1., 2., 3.), glm::vec3(3., 4., 5.));
+	let radius = 0.5 * 12f32.sqrt();
+	for aspect in [2., 0.5] {
+		for fovY in [FoV::Perspective(1.), FoV::Orthographic(1.)]
+		{
+			let mut params = CameraParameters::defaultWithAspect(aspect);
+			params.intrinsics.fovY = fovY;
+			let dir = params.extrinsics.dir;
+			params.fitToBounds(&bounds);
+			let (i, e) = (&params.intrinsics, &params.extrinsics);
+			assert!((e.eye + i.f*e.dir - bounds.center()).norm() < 1e-5);
+			assert!((e.dir - dir).norm() < 1e-6);
+			assert!(i.zNear < i.f - radius && i.zFar > i.f + radius);
+			match i.fovY
+			{
+				// The bounding sphere touches the narrower of the two frustum sides
+				FoV::Perspective(fovY) => {
+					let halfFovX = f32::atan(f32::tan(0.5*fovY) * aspect);
+					assert!((i.f*f32::sin(f32::min(0.5*fovY, halfFovX)) - radius).abs() < 1e-4);
+				},
+				// The bounding sphere fits into the view volume, whose width is the height scaled by the aspect
+				FoV::Orthographic(height) => {
+					assert!(height >= 2.*radius - 1e-5 && height*aspect >= 2.*radius - 1e-5);
+					assert!((f32::min(height, height*aspect) - 2.*radius).abs() < 1e-4);
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,415 @@
+//////
+//
+// Imports
+//
+
+// Standard library
+use std::{ops::{Add, Sub, Mul}, path::Path, fs};
+
+// Serde library
+use serde;
+
+// Local imports
+use crate::*;
+use view::*;
+
+
+
+//////
+//
+// Structs
+//
+
+/// A snapshot of the animatable [camera parameters](CameraParameters) at a point in time along a [`CameraPath`].
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Keyframe
+{
+	/// The point in time of the keyframe, in seconds.
+	pub time: f32,
+
+	/// The eye point.
+	pub eye: glm::Vec3,
+
+	/// The viewing direction.
+	pub dir: glm::Vec3,
+
+	/// The up direction.
+	pub up: glm::Vec3,
+
+	/// The vertical field of view.
+	pub fovY: FoV,
+
+	/// The focus distance.
+	pub f: f32
+}
+impl Keyframe
+{
+	/// Capture the animatable state of the given camera parameters at the given point in time.
+	pub fn fromParameters (parameters: &CameraParameters, time: f32) -> Self { Self {
+		time, eye: parameters.extrinsics.eye, dir: parameters.extrinsics.dir, up: parameters.extrinsics.up,
+		fovY: parameters.intrinsics.fovY, f: parameters.intrinsics.f
+	}}
+
+	/// Apply the keyframe to the given camera parameters. Properties that are not animated, like the aspect ratio or
+	/// the clipping planes, are left untouched.
+	pub fn apply (&self, parameters: &mut CameraParameters) {
+		parameters.extrinsics.eye = self.eye;
+		parameters.extrinsics.dir = self.dir;
+		parameters.extrinsics.up = self.up;
+		parameters.intrinsics.fovY = self.fovY;
+		parameters.intrinsics.f = self.f;
+	}
+}
+
+/// A camera path defined by [keyframes](Keyframe) which get interpolated by a *C*1-continuous Catmull-Rom spline,
+/// taking the (possibly non-uniform) spacing of the keyframes in time into account. Generalizes the simple transitions
+/// between two camera states performed by the built-in [camera interactors](CameraInteractor) when changing focus.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CameraPath {
+	keyframes: Vec<Keyframe>
+}
+impl CameraPath
+{
+	/// Create an empty camera path.
+	#[inline(always)]
+	pub fn new () -> Self {
+		Self::default()
+	}
+
+	/// Reference the keyframes of the path, in chronological order.
+	#[inline(always)]
+	pub fn keyframes (&self) -> &[Keyframe] {
+		&self.keyframes
+	}
+
+	/// Check if the path contains no keyframes.
+	#[inline(always)]
+	pub fn isEmpty (&self) -> bool {
+		self.keyframes.is_empty()
+	}
+
+	/// Insert the given keyframe at the position in the path its time dictates. An existing keyframe at the exact same
+	/// point in time gets replaced.
+	///
+	/// # Returns
+	///
+	/// The index of the keyframe within the path.
+	pub fn insert (&mut self, keyframe: Keyframe) -> usize
+	{
+		let index = self.keyframes.partition_point(|k| k.time < keyframe.time);
+		if self.keyframes.get(index).is_some_and(|k| k.time == keyframe.time) {
+			self.keyframes[index] = keyframe;
+		}
+		else {
+			self.keyframes.insert(index, keyframe);
+		}
+		index
+	}
+
+	/// Remove the keyframe at the given index from the path.
+	///
+	/// # Panics
+	///
+	/// If `index` is out of bounds.
+	#[inline(always)]
+	pub fn remove (&mut self, index: usize) -> Keyframe {
+		self.keyframes.remove(index)
+	}
+
+	/// Remove all keyframes from the path.
+	#[inline(always)]
+	pub fn clear (&mut self) {
+		self.keyframes.clear();
+	}
+
+	/// The point in time of the first keyframe, or *0* if the path is empty.
+	#[inline]
+	pub fn startTime (&self) -> f32 {
+		self.keyframes.first().map_or(0., |k| k.time)
+	}
+
+	/// The point in time of the last keyframe, or *0* if the path is empty.
+	#[inline]
+	pub fn endTime (&self) -> f32 {
+		self.keyframes.last().map_or(0., |k| k.time)
+	}
+
+	/// The timespan covered by the path, in seconds.
+	#[inline]
+	pub fn duration (&self) -> f32 {
+		self.endTime() - self.startTime()
+	}
+
+	/// Interpolate the camera state at the given point in time. Times outside of the path are clamped to its start or
+	/// end. Directions get re-orthonormalized after interpolation. The field of view is only interpolated between
+	/// keyframes of the same projection type, otherwise it switches over halfway between them.
+	///
+	/// # Returns
+	///
+	/// The interpolated keyframe, or `None` if the path is empty.
+	pub fn sample (&self, time: f32) -> Option<Keyframe>
+	{
+		let keys = &self.keyframes;
+		let last = keys.len().checked_sub(1)?;
+		let time = time.clamp(keys[0].time, keys[last].time);
+		if last == 0 {
+			return Some(Keyframe { time, ..keys[0] });
+		}
+
+		// Gather the keyframes influencing the segment, duplicating the endpoints of the path where necessary
+		let i = keys.partition_point(|k| k.time <= time).saturating_sub(1).min(last-1);
+		let k = [keys[i.saturating_sub(1)], keys[i], keys[i+1], keys[(i+2).min(last)]];
+		let t = k.map(|k| k.time);
+
+		// Interpolate
+		let dir = catmullRom(k.map(|k| k.dir), t, time).normalize();
+		let up = catmullRom(k.map(|k| k.up), t, time);
+		let fovY = if k[1].fovY.isPerspective() == k[2].fovY.isPerspective() {
+			let valueLike = |fov: FoV, reference: FoV| fovValue(
+				if fov.isPerspective() == reference.isPerspective() { fov } else { reference }
+			);
+			let values = [valueLike(k[0].fovY, k[1].fovY), fovValue(k[1].fovY), fovValue(k[2].fovY),
+			              valueLike(k[3].fovY, k[2].fovY)];
+			let value = catmullRom(values, t, time);
+			if k[1].fovY.isPerspective() { FoV::Perspective(value) } else { FoV::Orthographic(value) }
+		}
+		else if time < 0.5*(t[1]+t[2]) { k[1].fovY } else { k[2].fovY };
+		Some(Keyframe {
+			time, eye: catmullRom(k.map(|k| k.eye), t, time), dir,
+			up: glm::cross(&glm::cross(&dir, &up), &dir).normalize(), fovY, f: catmullRom(k.map(|k| k.f), t, time)
+		})
+	}
+
+	/// Serialize the path into a human-readable *YAML* representation.
+	pub fn serialize (&self) -> Vec<u8> {
+		let mut bytes = Vec::new();
+		serde_yaml_ng::to_writer(&mut bytes, self).expect(
+			"INTERNAL LOGIC ERROR: failed to serialize instance of view::CameraPath"
+		);
+		bytes
+	}
+
+	/// Deserialize a path from the given bytes as obtained from [`serialize`](Self::serialize).
+	pub fn deserialize (bytes: impl AsRef<[u8]>) -> Result<Self> {
+		Ok(serde_yaml_ng::from_slice(bytes.as_ref())?)
+	}
+
+	/// Write the path to the given file.
+	pub fn save (&self, filename: impl AsRef<Path>) -> Result<()> {
+		Ok(fs::write(filename, self.serialize())?)
+	}
+
+	/// Read a path from the given file.
+	pub fn load (filename: impl AsRef<Path>) -> Result<Self> {
+		Self::deserialize(fs::read(filename)?)
+	}
+}
+
+/// Timeline playback and interactive recording of a [`CameraPath`]. The [`Player`] owns an instance that drives its
+/// active camera and is controllable from the *View* side panel.
+#[derive(Debug, Clone)]
+pub struct CameraAnimation
+{
+	path: CameraPath,
+	time: f32,
+	playing: bool,
+	recordInterval: Option<f32>,
+	sinceLastRecorded: f32,
+
+	/// Factor by which playback is faster than real time.
+	pub speed: f32,
+
+	/// Whether playback should start over at the beginning of the path upon reaching its end.
+	pub looping: bool
+}
+impl CameraAnimation
+{
+	/// Create an animation with an empty path.
+	pub fn new () -> Self { Self {
+		path: CameraPath::new(), time: 0., playing: false, recordInterval: None, sinceLastRecorded: 0., speed: 1.,
+		looping: false
+	}}
+
+	/// Reference the animated path.
+	#[inline(always)]
+	pub fn path (&self) -> &CameraPath {
+		&self.path
+	}
+
+	/// Mutably reference the animated path.
+	#[inline(always)]
+	pub fn path_mut (&mut self) -> &mut CameraPath {
+		&mut self.path
+	}
+
+	/// Replace the animated path, stopping any ongoing playback or recording and rewinding to the start of the path.
+	pub fn setPath (&mut self, path: CameraPath) {
+		self.stop();
+		self.time = path.startTime();
+		self.path = path;
+	}
+
+	/// The current position on the timeline, in seconds.
+	#[inline(always)]
+	pub fn time (&self) -> f32 {
+		self.time
+	}
+
+	/// Check if the path is currently being played back.
+	#[inline(always)]
+	pub fn isPlaying (&self) -> bool {
+		self.playing
+	}
+
+	/// Check if camera movements are currently being recorded.
+	#[inline(always)]
+	pub fn isRecording (&self) -> bool {
+		self.recordInterval.is_some()
+	}
+
+	/// Start playback from the current position on the timeline, rewinding first if the end was already reached. Stops
+	/// any ongoing recording.
+	pub fn play (&mut self)
+	{
+		self.recordInterval = None;
+		if self.path.isEmpty() {
+			return;
+		}
+		if self.time >= self.path.endTime() {
+			self.time = self.path.startTime();
+		}
+		self.playing = true;
+	}
+
+	/// Pause playback at the current position on the timeline.
+	#[inline(always)]
+	pub fn pause (&mut self) {
+		self.playing = false;
+	}
+
+	/// Stop both playback and recording.
+	#[inline(always)]
+	pub fn stop (&mut self) {
+		self.playing = false;
+		self.recordInterval = None;
+	}
+
+	/// Move to the given position on the timeline and apply the camera state there to the given camera.
+	pub fn seek (&mut self, time: f32, camera: &mut dyn Camera)
+	{
+		self.time = time.clamp(self.path.startTime(), self.path.endTime());
+		if let Some(keyframe) = self.path.sample(self.time) {
+			keyframe.apply(camera.parameters_mut());
+		}
+	}
+
+	/// Append a keyframe capturing the given camera parameters to the end of the path.
+	///
+	/// # Arguments
+	///
+	/// * `parameters` – The camera parameters to capture.
+	/// * `spacing` – The time, in seconds, between the current end of the path and the new keyframe. Ignored for the
+	///               first keyframe, which always gets placed at time *0*.
+	pub fn appendKeyframe (&mut self, parameters: &CameraParameters, spacing: f32)
+	{
+		let time = if self.path.isEmpty() { 0. } else { self.path.endTime() + spacing };
+		self.path.insert(Keyframe::fromParameters(parameters, time));
+		self.time = time;
+	}
+
+	/// Start recording the movements of the camera with the given parameters, appending a keyframe to the path in
+	/// regular intervals. Keyframes are spaced in real time, so playing back the path reproduces the recorded flight.
+	/// Pauses any ongoing playback.
+	///
+	/// # Arguments
+	///
+	/// * `parameters` – The current camera parameters, captured into the first recorded keyframe.
+	/// * `interval` – The time, in seconds, between recorded keyframes.
+	pub fn startRecording (&mut self, parameters: &CameraParameters, interval: f32)
+	{
+		self.playing = false;
+		self.appendKeyframe(parameters, interval);
+		self.recordInterval = Some(interval);
+		self.sinceLastRecorded = 0.;
+	}
+
+	/// Stop recording, capturing the given final camera parameters into a last keyframe.
+	pub fn stopRecording (&mut self, parameters: &CameraParameters)
+	{
+		if self.recordInterval.take().is_some() && self.sinceLastRecorded > 0. {
+			self.appendKeyframe(parameters, self.sinceLastRecorded);
+		}
+	}
+
+	/// Advance the animation by the given amount of time, applying the result to the given camera during playback or
+	/// capturing its movements during recording.
+	///
+	/// # Returns
+	///
+	/// `true` if the camera was changed and the scene needs to be redrawn, `false` otherwise.
+	pub fn update (&mut self, dt: f32, camera: &mut dyn Camera) -> bool
+	{
+		if let Some(interval) = self.recordInterval {
+			self.sinceLastRecorded += dt;
+			if self.sinceLastRecorded >= interval {
+				self.appendKeyframe(camera.parameters(), self.sinceLastRecorded);
+				self.sinceLastRecorded = 0.;
+			}
+			false
+		}
+		else if self.playing
+		{
+			let (start, end) = (self.path.startTime(), self.path.endTime());
+			self.time += dt*self.speed;
+			if self.time >= end {
+				if self.looping && end > start {
+					self.time = start + (self.time-start) % (end-start);
+				}
+				else {
+					self.time = end;
+					self.playing = false;
+				}
+			}
+			match self.path.sample(self.time) {
+				Some(keyframe) => { keyframe.apply(camera.parameters_mut()); true },
+				None => { self.playing = false; false }
+			}
+		}
+		else {
+			false
+		}
+	}
+}
+impl Default for CameraAnimation {
+	fn default () -> Self {
+		Self::new()
+	}
+}
+
+
+
+//////
+//
+// Functions
+//
+
+/// Evaluate the Catmull-Rom spline segment between `p[1]` and `p[2]` at the given time, using tangents that account
+/// for the non-uniform spacing `t` of the control points in time. The segment gets evaluated in its Bézier form.
+fn catmullRom<V> (p: [V; 4], t: [f32; 4], time: f32) -> V
+	where V: Copy + Add<Output=V> + Sub<Output=V> + Mul<f32, Output=V>
+{
+	let h = t[2] - t[1];
+	let tangent = |a: usize, b: usize| (p[b]-p[a]) * (1./f32::max(t[b]-t[a], f32::EPSILON));
+	let (m1, m2) = (tangent(0, 2), tangent(1, 3));
+	util::math::evalBezier(
+		[p[1], p[1] + m1*(h/3.), p[2] - m2*(h/3.), p[2]], (time-t[1]) / f32::max(h, f32::EPSILON)
+	)
+}
+
+/// Extract the value of the given field of view regardless of its projection type.
+#[inline(always)]
+fn fovValue (fov: FoV) -> f32 {
+	match fov {
+		FoV::Perspective(value) | FoV::Orthographic(value) => value
+	}
+}
@@ -0,0 +1,167 @@
+//////
+//
+// Imports
+//
+
+// Standard library
+use std::{path::Path, fs};
+
+// Serde library
+use serde;
+
+// Local imports
+use crate::*;
+use view::*;
+
+
+
+//////
+//
+// Structs
+//
+
+/// A named snapshot of [camera parameters](CameraParameters) that can be recalled later or shared as text.
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+pub struct ViewBookmark
+{
+	/// The name of the bookmark as shown in the GUI.
+	pub name: String,
+
+	/// The stored camera parameters.
+	pub parameters: CameraParameters
+}
+impl ViewBookmark
+{
+	/// Create a bookmark of the given camera parameters.
+	#[inline(always)]
+	pub fn new (name: impl Into<String>, parameters: &CameraParameters) -> Self {
+		Self { name: name.into(), parameters: *parameters }
+	}
+
+	/// Produce a human-readable *YAML* representation of the bookmark, suitable for e.g. the clipboard.
+	pub fn toText (&self) -> String {
+		serde_yaml_ng::to_string(self).expect(
+			"INTERNAL LOGIC ERROR: failed to serialize instance of view::ViewBookmark"
+		)
+	}
+
+	/// Parse a bookmark from the given text as obtained from [`toText`](Self::toText).
+	pub fn fromText (text: &str) -> Result<Self> {
+		Ok(serde_yaml_ng::from_str(text)?)
+	}
+}
+
+/// A collection of [view bookmarks](ViewBookmark) that can animate a camera onto any of them. The [`Player`] owns an
+/// instance that drives its active camera, persists it across sessions and makes it manageable from the *View* side
+/// panel.
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+pub struct ViewBookmarks
+{
+	bookmarks: Vec<ViewBookmark>,
+
+	#[serde(skip)]
+	transition: Option<FocusChange>
+}
+impl ViewBookmarks
+{
+	/// The key the bookmarks are persisted under in the *eframe* storage.
+	pub(crate) const STORAGE_KEY: &'static str = "CGV__view_bookmarks";
+
+	/// The time, in seconds, that the transition onto a recalled bookmark takes.
+	const TRANSITION_TIMESPAN: f32 = 0.75;
+
+	/// Create an empty bookmark collection.
+	#[inline(always)]
+	pub fn new () -> Self {
+		Self::default()
+	}
+
+	/// Reference the list of bookmarks.
+	#[inline(always)]
+	pub fn bookmarks (&self) -> &[ViewBookmark] {
+		&self.bookmarks
+	}
+
+	/// Mutably reference the list of bookmarks, e.g. for renaming, reordering or removing them.
+	#[inline(always)]
+	pub fn bookmarks_mut (&mut self) -> &mut Vec<ViewBookmark> {
+		&mut self.bookmarks
+	}
+
+	/// Append the given bookmark to the collection.
+	#[inline(always)]
+	pub fn add (&mut self, bookmark: ViewBookmark) {
+		self.bookmarks.push(bookmark);
+	}
+
+	/// Append all bookmarks from the given collection.
+	pub fn append (&mut self, other: ViewBookmarks) {
+		self.bookmarks.extend(other.bookmarks);
+	}
+
+	/// Start animating the given camera onto the bookmark with the given index. The transition gets carried out by
+	/// subsequent calls to [`update`](Self::update). The aspect ratio of the camera is left untouched, while the
+	/// clipping planes are changed right away.
+	pub fn recall (&mut self, index: usize, camera: &mut dyn Camera)
+	{
+		let target = self.bookmarks[index].parameters;
+		let params = camera.parameters_mut();
+		params.intrinsics.zNear = target.intrinsics.zNear;
+		params.intrinsics.zFar = target.intrinsics.zFar;
+		let mut transition = FocusChange::new(params, Self::TRANSITION_TIMESPAN);
+		transition.setNewParameters(params, &target);
+		self.transition = Some(transition);
+	}
+
+	/// Check if a transition onto a recalled bookmark is ongoing.
+	#[inline(always)]
+	pub fn isTransitioning (&self) -> bool {
+		self.transition.is_some()
+	}
+
+	/// Abort an ongoing transition onto a recalled bookmark, leaving the camera where it currently is.
+	#[inline(always)]
+	pub fn cancelTransition (&mut self) {
+		self.transition = None;
+	}
+
+	/// Advance an ongoing transition onto a recalled bookmark by the given time, updating the given camera.
+	///
+	/// # Returns
+	///
+	/// `true` if the camera was changed, `false` otherwise.
+	pub fn update (&mut self, dt: f32, camera: &mut dyn Camera) -> bool
+	{
+		let Some(transition) = &mut self.transition else {
+			return false;
+		};
+		if transition.update(dt, camera.parameters_mut()) {
+			self.transition = None;
+		}
+		true
+	}
+
+	/// Serialize the bookmarks into a human-readable *YAML* representation.
+	pub fn serialize (&self) -> Vec<u8> {
+		let mut bytes = Vec::new();
+		serde_yaml_ng::to_writer(&mut bytes, self).expect(
+			"INTERNAL LOGIC ERROR: failed to serialize instance of view::ViewBookmarks"
+		);
+		bytes
+	}
+
+	/// Deserialize bookmarks from the given bytes as obtained from [`serialize`](Self::serialize).
+	pub fn deserialize (bytes: impl AsRef<[u8]>) -> Result<Self> {
+		Ok(serde_yaml_ng::from_slice(bytes.as_ref())?)
+	}
+
+	/// Export the bookmarks to the given file.
+	pub fn save (&self, filename: impl AsRef<Path>) -> Result<()> {
+		Ok(fs::write(filename, self.serialize())?)
+	}
+
+	/// Import bookmarks from the given file.
+	pub fn load (filename: impl AsRef<Path>) -> Result<Self> {
+		Self::deserialize(fs::read(filename)?)
+	}
+}
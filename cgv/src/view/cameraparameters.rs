@@ -10,6 +10,9 @@
 // Egui library
 use egui;
 
+// Serde library
+use serde;
+
 // CGV-rs components
 use cgv_util::math;
 
@@ -27,6 +30,9 @@ use crate::{*, view::*};
 /// and perspective projections.
 const FOV_ORTHO_THRESHOLD: f32 = 5.;
 
+/// The hover text of the clipping plane controls while they're managed automatically.
+const DEPTH_RANGE_LOCKED_HINT: &str = "Managed by automatic clipping planes";
+
 
 
 //////
@@ -34,7 +40,7 @@ const FOV_ORTHO_THRESHOLD: f32 = 5.;
 // Classes
 //
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, serde::Serialize, serde::Deserialize)]
 pub struct Intrinsics
 {
 	pub fovY: FoV,
@@ -64,13 +70,40 @@ impl Intrinsics
 		(0.5*diameter)/f32::tan(0.5*fov)
 	}
 
+	/// Place the near and far clipping planes as tightly around the given bounding box as possible, to make the most
+	/// of the available depth buffer precision when viewing it with the given extrinsics. The planes are left
+	/// untouched if the box lies completely behind the eye.
+	pub fn adaptDepthRange (&mut self, extrinsics: &Extrinsics, bounds: &math::Aabb)
+	{
+		/// The smallest ratio between near and far plane distance we allow, for when the eye is inside the bounds.
+		const MIN_NEAR_FAR_RATIO: f32 = 1./4096.;
+
+		// Find the depth range the box covers
+		let (minDepth, maxDepth) = bounds.corners().iter().fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), c| {
+			let depth = (c - extrinsics.eye).dot(&extrinsics.dir);
+			(lo.min(depth), hi.max(depth))
+		});
+		if maxDepth <= 0. {
+			return;
+		}
+
+		// Leave some margin to prevent clipping due to rounding
+		let margin = f32::max(0.01*(maxDepth - minDepth), 0.001*maxDepth);
+		self.zFar = maxDepth + margin;
+		self.zNear = f32::max(minDepth - margin, self.zFar*MIN_NEAR_FAR_RATIO);
+	}
+
 	pub fn ui (&mut self, ui: &mut egui::Ui) {
 		// Create ControlTable layouter and delegate the actual work
-		self.uiWithLayouter(ui, &gui::layout::ControlTableLayouter::new(ui));
+		self.uiWithLayouter(ui, &gui::layout::ControlTableLayouter::new(ui), false);
 	}
 
-	pub fn uiWithLayouter (&mut self, ui: &mut egui::Ui, layouter: &gui::layout::ControlTableLayouter)
-	{
+	/// Define the GUI for the intrinsics using the given layouter. If `depthRangeLocked` is `true`, the controls for
+	/// the clipping planes are disabled as they're being managed automatically (see
+	/// [`Player::setAutoClippingPlanes`](crate::Player::setAutoClippingPlanes)).
+	pub fn uiWithLayouter (
+		&mut self, ui: &mut egui::Ui, layouter: &gui::layout::ControlTableLayouter, depthRangeLocked: bool
+	){
 		// Header
 		ui.label(egui::RichText::new("Intrinsics").underline());
 
@@ -132,21 +165,21 @@ impl Intrinsics
 
 			// zNear
 			let tmp = self.zNear;
-			intrinsicsUi.add("zNear", |ui, _| ui.add(
+			intrinsicsUi.add("zNear", |ui, _| ui.add_enabled(!depthRangeLocked,
 				egui::Slider::new(&mut self.zNear, 0.0001..=self.zFar-0.0001)
 					.logarithmic(true)
 					.drag_value_speed(0.03125*tmp as f64)
 					.clamping(egui::SliderClamping::Always)
-			));
+			).on_disabled_hover_text(DEPTH_RANGE_LOCKED_HINT));
 
 			// zFar
 			let tmp = self.zFar;
-			intrinsicsUi.add("zFar", |ui, _| ui.add(
+			intrinsicsUi.add("zFar", |ui, _| ui.add_enabled(!depthRangeLocked,
 				egui::Slider::new(&mut self.zFar, self.zNear+0.0001..=1024.)
 					.logarithmic(true)
 					.drag_value_speed(0.03125*tmp as f64)
 					.clamping(egui::SliderClamping::Always)
-			));
+			).on_disabled_hover_text(DEPTH_RANGE_LOCKED_HINT));
 		});
 	}
 }
@@ -163,7 +196,7 @@ impl PartialEq for Intrinsics
 	}
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, serde::Serialize, serde::Deserialize)]
 pub struct Extrinsics {
 	pub eye: glm::Vec3,
 	pub dir: glm::Vec3,
@@ -234,7 +267,7 @@ impl PartialEq for Extrinsics
 	}
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, serde::Serialize, serde::Deserialize)]
 pub struct CameraParameters {
 	pub intrinsics: Intrinsics,
 	pub extrinsics: Extrinsics,
@@ -252,6 +285,34 @@ impl CameraParameters
 		self.extrinsics.eye = focus - self.extrinsics.dir*self.intrinsics.f;
 	}
 
+	/// Zoom towards the focus point by the given factor, with values greater than *1* zooming in.
+	pub fn zoomBy (&mut self, factor: f32) {
+		let focus = self.extrinsics.eye + self.extrinsics.dir*self.intrinsics.f;
+		self.intrinsics.f /= factor;
+		self.extrinsics.eye = focus - self.extrinsics.dir*self.intrinsics.f;
+	}
+
+	/// Move the eye along the current viewing direction such that the given bounding box fits completely into view,
+	/// focusing on its center and adapting the [clipping planes](Intrinsics::adaptDepthRange) to it.
+	pub fn fitToBounds (&mut self, bounds: &math::Aabb)
+	{
+		let center = bounds.center();
+		let radius = f32::max(0.5*bounds.diameter(), 0.0001);
+		match self.intrinsics.fovY
+		{
+			FoV::Perspective(fovY) => {
+				let halfFovX = f32::atan(f32::tan(0.5*fovY) * self.intrinsics.aspect);
+				self.intrinsics.f = radius / f32::sin(f32::min(0.5*fovY, halfFovX));
+			},
+			FoV::Orthographic(_) => {
+				self.intrinsics.fovY = FoV::Orthographic(2.*radius * f32::max(1., 1./self.intrinsics.aspect));
+				self.intrinsics.f = 2.*radius;
+			}
+		}
+		self.extrinsics.eye = center - self.intrinsics.f*self.extrinsics.dir;
+		self.intrinsics.adaptDepthRange(&self.extrinsics, bounds);
+	}
+
 	pub fn adjustForTargetFov (&mut self, newFov: f32, orthoThreshold: f32)
 	{
 		if let FoV::Perspective(fov) = self.intrinsics.fovY
@@ -299,7 +360,9 @@ impl CameraParameters
 		}
 	}
 
-	pub fn ui (camera: &mut dyn Camera, ui: &mut egui::Ui)
+	/// Define the GUI for the parameters of the given camera. If `depthRangeLocked` is `true`, the controls for the
+	/// clipping planes are disabled as they're being managed automatically.
+	pub fn ui (camera: &mut dyn Camera, ui: &mut egui::Ui, depthRangeLocked: bool)
 	{
 		// Track changes to camera parameters
 		let params_orig = camera.parameters();
@@ -349,7 +412,7 @@ impl CameraParameters
 		}).inner;
 
 		// UI for intrinsics
-		params.intrinsics.uiWithLayouter(ui, &controlTable);
+		params.intrinsics.uiWithLayouter(ui, &controlTable, depthRangeLocked);
 		changed |= params.intrinsics != params_orig.intrinsics;
 
 		// UI for extrinsics
@@ -20,6 +20,22 @@ pub use orbitinteractor::OrbitInteractor; // re-export
 mod wasdinteractor;
 pub use wasdinteractor::WASDInteractor; // re-export
 
+/// The internal submodule for the TrackballInteractor implementation
+mod trackballinteractor;
+pub use trackballinteractor::{TrackballInteractor, TrackballMode}; // re-export
+
+/// The internal submodule for region selection over rendered elements
+mod selection;
+pub use selection::{SelectionTool, SelectionMode, SelectionRegion, Selection, GPU_SELECTION_THRESHOLD}; // re-export
+
+/// The internal submodule for keyframe-based camera animation
+mod animation;
+pub use animation::{Keyframe, CameraPath, CameraAnimation}; // re-export
+
+/// The internal submodule for named view bookmarks
+mod bookmarks;
+pub use bookmarks::{ViewBookmark, ViewBookmarks}; // re-export
+
 
 
 //////
@@ -44,7 +60,7 @@ use crate::*;
 //
 
 /// Enum representing either a perspective or orthographic field-of-view in the vertical direction.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, serde::Serialize, serde::Deserialize)]
 pub enum FoV {
 	/// The FoV represents a perspective opening angle, in radians
 	Perspective(f32),
@@ -86,13 +102,16 @@ impl PartialEq for FoV {
 // Classes
 //
 
-/// A helper object for managing animated camera focus changes.
+/// A helper object for managing animated camera focus changes, optionally including changes in orientation and field
+/// of view.
 struct FocusChange {
 	camDir: glm::Vec3,
 	pub oldEye: glm::Vec3,
 	pub newEye: glm::Vec3,
 	pub oldF: f32,
 	pub newF: f32,
+	pub orientation: Option<(nalgebra::UnitQuaternion<f32>, nalgebra::UnitQuaternion<f32>)>,
+	pub fovY: Option<(FoV, FoV)>,
 	pub t: f32,
 	pub speed: f32
 }
@@ -112,7 +131,8 @@ impl FocusChange
 	/// A new `FocusChange` instance initialized for the given parameters.
 	pub fn new (cameraParameters: &CameraParameters, timespan: f32) -> Self { Self {
 		camDir: cameraParameters.extrinsics.dir, oldEye: cameraParameters.extrinsics.eye, newEye: glm::Vec3::default(),
-		oldF: cameraParameters.intrinsics.f, newF: f32::default(), t: 0., speed: 1./timespan
+		oldF: cameraParameters.intrinsics.f, newF: f32::default(), orientation: None, fovY: None, t: 0.,
+		speed: 1./timespan
 	}}
 
 	/// Set the desired focus point to animate onto. Must be called before the first call to [`Self::update`].
@@ -126,6 +146,25 @@ impl FocusChange
 		self.newF = (newFocus - self.newEye).norm();
 	}
 
+	/// Set the complete camera state to animate onto, including orientation and field of view. Can be called instead
+	/// of [`setNewFocus`](Self::setNewFocus) before the first call to [`Self::update`].
+	///
+	/// # Arguments
+	///
+	/// * `oldParameters` – The camera parameters representing the current state before the transition.
+	/// * `newParameters` – The desired camera parameters after the transition.
+	pub fn setNewParameters (&mut self, oldParameters: &CameraParameters, newParameters: &CameraParameters)
+	{
+		let orientation = |extrinsics: &Extrinsics|
+			nalgebra::UnitQuaternion::face_towards(&extrinsics.dir, &extrinsics.up);
+		self.newEye = newParameters.extrinsics.eye;
+		self.newF = newParameters.intrinsics.f;
+		self.orientation = Some((
+			orientation(&oldParameters.extrinsics), orientation(&newParameters.extrinsics)
+		));
+		self.fovY = Some((oldParameters.intrinsics.fovY, newParameters.intrinsics.fovY));
+	}
+
 	/// Update the focus transition for the given change in time, manipulating the provided camera parameters.
 	///
 	/// # Arguments
@@ -141,7 +180,24 @@ impl FocusChange
 		self.t = f32::min(self.t + self.speed*dt, 1f32);
 		cameraParameters.extrinsics.eye = util::math::smoothLerp(self.oldEye, self.newEye, self.t);
 		cameraParameters.intrinsics.f = util::math::smoothLerp(self.oldF, self.newF, self.t);
-		cameraParameters.extrinsics.eye == self.newEye
+		if let Some((oldOrientation, newOrientation)) = &self.orientation {
+			let t = util::math::smoothstep(self.t);
+			let orientation = oldOrientation.try_slerp(newOrientation, t, 1e-6).unwrap_or(
+				if t < 0.5 { *oldOrientation } else { *newOrientation }
+			);
+			cameraParameters.extrinsics.dir = orientation * glm::vec3(0., 0., 1.);
+			cameraParameters.extrinsics.up = orientation * glm::vec3(0., 1., 0.);
+		}
+		if let Some((oldFov, newFov)) = self.fovY {
+			cameraParameters.intrinsics.fovY = match (oldFov, newFov) {
+				(FoV::Perspective(old), FoV::Perspective(new))
+					=> FoV::Perspective(util::math::smoothLerp(old, new, self.t)),
+				(FoV::Orthographic(old), FoV::Orthographic(new))
+					=> FoV::Orthographic(util::math::smoothLerp(old, new, self.t)),
+				_ => if self.t < 0.5 { oldFov } else { newFov }
+			};
+		}
+		self.t >= 1.
 	}
 }
 
@@ -320,6 +376,29 @@ pub trait Camera: Send
 	/// Reference the framebuffer containing the rendering of the scene acquired by the camera.
 	fn framebuffer (&self) -> &hal::Framebuffer;
 
+	/// Add or remove [object ID attachments](hal::Framebuffer::ids) to or from all render targets of the camera,
+	/// including its main [framebuffer](Camera::framebuffer). Object IDs at a pixel of the main framebuffer enable
+	/// [picking](Player::pickAtSurfacePixel_async).
+	///
+	/// # Arguments
+	///
+	/// * `context` – The graphics context.
+	/// * `enabled` – Whether the render targets should have an object ID attachment.
+	fn setIdAttachment (&mut self, _context: &Context, _enabled: bool) {}
+
+	/// Restrict the image produced by the camera to the given window of its full image plane, effectively rendering
+	/// only the corresponding sub-frustum at the full resolution of the camera. Enables e.g. tiled rendering of images
+	/// that exceed the maximum framebuffer size.
+	///
+	/// # Arguments
+	///
+	/// * `window` – The lower left and upper right corner of the window, in normalized device coordinates of the full
+	///              image plane (i.e. *x* pointing right and *y* pointing up, both in the range *-1..1*). Passing
+	///              `None` restores the full image plane.
+	///
+	/// The default implementation ignores the window, for cameras that don't support restricting their image.
+	fn setProjectionWindow (&mut self, _window: Option<(glm::Vec2, glm::Vec2)>) {}
+
 	/// Report the individual name of the camera instance.
 	///
 	/// # Returns
@@ -409,3 +488,26 @@ pub fn transformClipspaceOGL2WGPU (oglProjection: &glm::Mat4) -> glm::Mat4
 	// ToDo: investigate why any attempt to boil this down to individual component updates failed so far
 	CLIPSPACE_TRANSFORM_OGL2WGPU  *  *oglProjection
 }
+
+/// Compute the transformation that maps the given window of *WebGPU* clip space onto the full clip space, such that
+/// applying it to a projection matrix yields the projection of the corresponding sub-frustum.
+///
+/// # Arguments
+///
+/// * `min` – The lower left corner of the window, in normalized device coordinates.
+/// * `max` – The upper right corner of the window, in normalized device coordinates.
+///
+/// # Returns
+///
+/// The matrix that needs to be multiplied onto a projection matrix from the left to restrict it to the window.
+pub fn projectionWindowTransform (min: &glm::Vec2, max: &glm::Vec2) -> glm::Mat4
+{
+	let scale = glm::vec2(2., 2.).component_div(&(max - min));
+	let offset = -(max + min).component_div(&(max - min));
+	glm::Mat4::new(
+		scale.x, 0.0,     0.0, offset.x,
+		0.0,     scale.y, 0.0, offset.y,
+		0.0,     0.0,     1.0, 0.0,
+		0.0,     0.0,     0.0, 1.0,
+	)
+}
@@ -28,6 +28,7 @@ pub struct MonoCamera {
 	defaultClearColor: wgpu::Color, // <- cached default clear color (we need it to be able to undo overrides)
 	globalPasses: Vec<GlobalPassInfo>,
 	parameters: CameraParameters,
+	window: Option<(glm::Vec2, glm::Vec2)>,
 	dirty: bool
 }
 impl MonoCamera
@@ -54,7 +55,7 @@ impl MonoCamera
 		// Create framebuffer
 		let framebuffer = hal::FramebufferBuilder::withDims(&resolution)
 			.withLabel(format!("{name}_framebuffer").as_str())
-			.attachColor(colorFormat, Some(wgpu::TextureUsages::TEXTURE_BINDING))
+			.attachColor(colorFormat, Some(wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_SRC))
 			.attachDepthStencil(depthStencilFormat, Some(wgpu::TextureUsages::COPY_SRC))
 			.build(context);
 
@@ -67,6 +68,7 @@ impl MonoCamera
 			globalPasses: Self::declareRenderPasses(renderSetup),
 			renderState,
 			parameters: CameraParameters::defaultWithAspect(resolution.x as f32 / resolution.y as f32),
+			window: None,
 			dirty: true
 		}
 	}
@@ -148,6 +150,9 @@ impl Camera for MonoCamera
 					))
 				}
 			};
+			if let Some((min, max)) = &self.window {
+				mats.projection = projectionWindowTransform(min, max) * mats.projection;
+			}
 			mats.view = glm::look_at(
 				&self.parameters.extrinsics.eye,
 				&(self.parameters.extrinsics.eye + self.parameters.extrinsics.dir*self.parameters.intrinsics.f),
@@ -181,6 +186,15 @@ impl Camera for MonoCamera
 		&self.renderState.framebuffer
 	}
 
+	fn setIdAttachment (&mut self, context: &Context, enabled: bool) {
+		self.renderState.setIdAttachment(context, enabled);
+	}
+
+	fn setProjectionWindow (&mut self, window: Option<(glm::Vec2, glm::Vec2)>) {
+		self.window = window;
+		self.dirty = true;
+	}
+
 	fn name (&self) -> &str {
 		&self.name
 	}
@@ -13,6 +13,7 @@ use egui;
 // Local imports
 use crate::*;
 use view::*;
+use player::Action;
 use util::math;
 
 
@@ -58,109 +59,112 @@ impl CameraInteractor for OrbitInteractor
 
 	fn input (&mut self, event: &InputEvent, player: &mut Player, handle: player::Handle) -> EventOutcome
 	{
-		// Match on relevant events
-		match event
+		// We only act on high-level actions, which makes us work the same with any input modality
+		let InputEvent::Action(info) = event else {
+			return EventOutcome::NotHandled;
+		};
+
+		// We only borrow the camera parameters inside a scope where we're sure we'll be changing something, as the
+		// camera usually recalculates internal state after a mutable borrow
+		match info.action
 		{
-			InputEvent::Dragged(info)
+			Action::Rotate(direction)
 			=> {
-				// Adapt dragged delta
-				let delta = glm::vec2(-info.direction.x, info.direction.y);
-				let mut handled= false;
-
-				// We only borrow the camera parameters inside a scope where we're sure we'll be changing something,
-				// as the camera usually recalculates internal state after a mutable borrow
-				if info.button(egui::PointerButton::Primary)
+				let delta = glm::vec2(-direction.x, direction.y);
+				let p = player.camera.parameters_mut();
+				if let Some(upAxis) = &self.fixUp
 				{
-					let p = player.camera.parameters_mut();
-					if info.modifiers.shift && self.fixUp.is_none() {
-						p.extrinsics.up = glm::rotate_vec3(
-							&p.extrinsics.up, math::deg2rad!(delta.y*-0.75*self.dragSensitivity),
-							&p.extrinsics.dir
-						);
-					}
-					else if let Some(upAxis) = &self.fixUp
-					{
-						let target = p.extrinsics.eye + p.intrinsics.f*p.extrinsics.dir;
-						let mut newDir = glm::rotate_vec3(
-							&p.extrinsics.dir, math::deg2rad!(delta.x*self.dragSensitivity), &upAxis
-						);
-						let right = glm::normalize(&glm::cross(&newDir, &upAxis));
-						newDir = glm::rotate_vec3(
-							&newDir, math::deg2rad!(delta.y*-self.dragSensitivity), &right
-						);
-						p.extrinsics.dir = newDir;
-						p.extrinsics.up = glm::cross(&right, &p.extrinsics.dir);
-						p.extrinsics.eye = target - p.intrinsics.f*p.extrinsics.dir;
-					}
-					else {
-						let target = p.extrinsics.eye + p.intrinsics.f*p.extrinsics.dir;
-						let mut right = glm::normalize(&glm::cross(&p.extrinsics.dir, &p.extrinsics.up));
-						right = glm::rotate_vec3(
-							&right, math::deg2rad!(delta.x*self.dragSensitivity), &p.extrinsics.up
-						);
-						p.extrinsics.up = glm::rotate_vec3(
-							&p.extrinsics.up, math::deg2rad!(delta.y*-self.dragSensitivity), &right
-						);
-						p.extrinsics.dir = glm::cross(&p.extrinsics.up, &right);
-						p.extrinsics.eye = target - p.intrinsics.f*p.extrinsics.dir;
-					}
-					handled = true;
+					let target = p.extrinsics.eye + p.intrinsics.f*p.extrinsics.dir;
+					let mut newDir = glm::rotate_vec3(
+						&p.extrinsics.dir, math::deg2rad!(delta.x*self.dragSensitivity), &upAxis
+					);
+					let right = glm::normalize(&glm::cross(&newDir, &upAxis));
+					newDir = glm::rotate_vec3(
+						&newDir, math::deg2rad!(delta.y*-self.dragSensitivity), &right
+					);
+					p.extrinsics.dir = newDir;
+					p.extrinsics.up = glm::cross(&right, &p.extrinsics.dir);
+					p.extrinsics.eye = target - p.intrinsics.f*p.extrinsics.dir;
 				}
-				if info.button(egui::PointerButton::Secondary)
-				{
-					let p = player.camera.parameters_mut();
-					let speed = p.intrinsics.f * delta*1./192.*self.dragSensitivity;
-					let right = &glm::cross(&p.extrinsics.dir, &p.extrinsics.up);
-					let diff = speed.x*right + speed.y*p.extrinsics.up;
-					p.extrinsics.eye += diff;
-					if self.focusChange.is_some() {
-						self.focusChange = None;
-						player.dropContinuousRedrawRequest();
-					}
-					handled = true;
+				else {
+					let target = p.extrinsics.eye + p.intrinsics.f*p.extrinsics.dir;
+					let mut right = glm::normalize(&glm::cross(&p.extrinsics.dir, &p.extrinsics.up));
+					right = glm::rotate_vec3(
+						&right, math::deg2rad!(delta.x*self.dragSensitivity), &p.extrinsics.up
+					);
+					p.extrinsics.up = glm::rotate_vec3(
+						&p.extrinsics.up, math::deg2rad!(delta.y*-self.dragSensitivity), &right
+					);
+					p.extrinsics.dir = glm::cross(&p.extrinsics.up, &right);
+					p.extrinsics.eye = target - p.intrinsics.f*p.extrinsics.dir;
 				}
-				if info.button(egui::PointerButton::Middle)
-				{
-					let p = player.camera.parameters_mut();
-					let movement = p.intrinsics.f*delta.y*1./96.*self.dragSensitivity * p.extrinsics.dir;
-					p.extrinsics.eye += movement;
-					if self.focusChange.is_some() {
-						self.focusChange = None;
-						player.dropContinuousRedrawRequest();
-					}
-					handled = true;
+				EventOutcome::HandledExclusively(/* redraw */true)
+			},
+
+			Action::Roll(_) | Action::DragRoll(_)
+			=> {
+				let angle = info.action.rollAngle(0.75*self.dragSensitivity);
+				let p = player.camera.parameters_mut();
+				if let Some(upAxis) = &self.fixUp {
+					// Rolling would break the fixed up direction, so spin the scene around it instead
+					let target = p.extrinsics.eye + p.intrinsics.f*p.extrinsics.dir;
+					p.extrinsics.dir = glm::rotate_vec3(&p.extrinsics.dir, -angle, upAxis);
+					p.extrinsics.up = glm::rotate_vec3(&p.extrinsics.up, -angle, upAxis);
+					p.extrinsics.eye = target - p.intrinsics.f*p.extrinsics.dir;
 				}
-				if handled {
-					EventOutcome::HandledExclusively(/* redraw */true)
-				} else {
-					EventOutcome::NotHandled
+				else {
+					p.extrinsics.up = glm::rotate_vec3(&p.extrinsics.up, -angle, &p.extrinsics.dir);
 				}
+				EventOutcome::HandledExclusively(/* redraw */true)
 			},
 
-			InputEvent::MouseWheel(info)
+			Action::Pan(direction)
 			=> {
-				if info.amount.y != 0. {
-					if info.modifiers.alt {
-						let params = player.camera.parameters_mut();
-						params.adjustFovBy(info.amount.y, math::deg2rad!(5.));
-					} else {
-						let params = player.camera.parameters_mut();
-						params.adjustZoom(info.amount.y);
-					}
-					EventOutcome::HandledExclusively(/* redraw */true)
+				let delta = glm::vec2(-direction.x, direction.y);
+				let p = player.camera.parameters_mut();
+				let speed = p.intrinsics.f * delta*1./192.*self.dragSensitivity;
+				let right = &glm::cross(&p.extrinsics.dir, &p.extrinsics.up);
+				let diff = speed.x*right + speed.y*p.extrinsics.up;
+				p.extrinsics.eye += diff;
+				if self.focusChange.is_some() {
+					self.focusChange = None;
+					player.dropContinuousRedrawRequest();
 				}
-				else {
-					EventOutcome::NotHandled
+				EventOutcome::HandledExclusively(/* redraw */true)
+			},
+
+			Action::Dolly(amount)
+			=> {
+				let p = player.camera.parameters_mut();
+				let movement = p.intrinsics.f*amount*1./96.*self.dragSensitivity * p.extrinsics.dir;
+				p.extrinsics.eye += movement;
+				if self.focusChange.is_some() {
+					self.focusChange = None;
+					player.dropContinuousRedrawRequest();
 				}
+				EventOutcome::HandledExclusively(/* redraw */true)
+			},
+
+			Action::Zoom(factor)
+			=> {
+				player.camera.parameters_mut().zoomBy(factor);
+				EventOutcome::HandledExclusively(/* redraw */true)
+			},
+
+			Action::FieldOfView(angle)
+			=> {
+				// `adjustFovBy` expects the amount in eighths of a degree
+				player.camera.parameters_mut().adjustFovBy(math::rad2deg!(angle)*8., math::deg2rad!(5.));
+				EventOutcome::HandledExclusively(/* redraw */true)
 			},
 
-			InputEvent::DoubleClick(info)
+			Action::Focus(position)
 			=> {
 				let mut focusChange = FocusChange::new(player.camera.parameters(), 0.5);
-				player.unprojectPointAtSurfacePixel_async(info.position, move |point| {
+				player.unprojectPointAtSurfacePixel_async(position, move |point| {
 					let Some(point) = point else {return};
 
-					tracing::debug!("Double-click to new focus: {:?}", point);
+					tracing::debug!("Focus action to new focus: {:?}", point);
 					focusChange.setNewFocus(point);
 					let mut player = player::lock();
 					player.cameraInteractors.get_mut::<Self>(handle).focusChange = Some(focusChange);
@@ -0,0 +1,366 @@
+//////
+//
+// Imports
+//
+
+// Standard library
+use std::sync::LazyLock;
+
+// Local imports
+use crate::*;
+
+
+
+//////
+//
+// Constants
+//
+
+/// Data with at least this many elements gets tested against a [`SelectionRegion`] using a compute kernel on the GPU
+/// when calling [`SelectionRegion::select_async`], smaller data is tested on the CPU.
+pub const GPU_SELECTION_THRESHOLD: u32 = 1 << 16;
+
+
+
+//////
+//
+// Globals
+//
+
+/// The compute kernel testing elements against a selection region on the GPU.
+static SELECTION_KERNEL: LazyLock<gpu::Kernel<'static>> = LazyLock::new(|| {
+	static SHADER_PACKAGE: LazyLock<shader::Package> = LazyLock::new(||
+		shader::Package::deserialize(
+			util::sourceGeneratedBytes!("/shader/view/selection.spk")
+		).expect("baked 'selection.spk' shader package should be available and valid")
+	);
+	use gpu::compute::Binding;
+	gpu::Kernel::new(&SHADER_PACKAGE, "selectElements", glm::vec3(64, 1, 1), &[
+		Binding::Uniform, Binding::Storage{readOnly: true}, Binding::Storage{readOnly: true},
+		Binding::Storage{readOnly: false}
+	])
+});
+
+
+
+//////
+//
+// Enums
+//
+
+/// The shapes of screen-space regions the [`Player`] lets users draw in [selection mode](Player::setSelectionTool).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectionTool
+{
+	/// An axis-aligned rectangle spanned by the start and end points of the drag.
+	Rectangle,
+
+	/// A free-form polygon following the pointer while dragging.
+	Lasso
+}
+
+/// How a newly selected region should be combined with an existing [`Selection`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectionMode
+{
+	/// The region replaces the existing selection.
+	Replace,
+
+	/// The region gets added to the existing selection.
+	Add,
+
+	/// The region gets removed from the existing selection.
+	Subtract
+}
+impl SelectionMode
+{
+	/// Determine the mode according to the usual conventions: holding *Shift* adds to the selection, holding *Ctrl*
+	/// (*Cmd* on Mac) removes from it.
+	pub fn fromModifiers (modifiers: &egui::Modifiers) -> Self {
+		if modifiers.shift { Self::Add }
+		else if modifiers.command { Self::Subtract }
+		else { Self::Replace }
+	}
+}
+
+
+
+//////
+//
+// Structs
+//
+
+/// The uniforms of the [selection kernel](SELECTION_KERNEL). Must match the layout of the `QueryParams` struct in
+/// `selection.slang`.
+#[repr(C,align(16))]
+#[derive(Clone, Copy, bytemuck::NoUninit)]
+struct QueryParams {
+	projView: glm::Mat4,
+	viewportDims: glm::Vec2,
+	numVertices: u32,
+	numElements: u32,
+	boundsMin: glm::Vec2,
+	boundsMax: glm::Vec2
+}
+
+/// A region drawn by the user in screen space, as delivered to applications via [`InputEvent::RegionSelected`]. The
+/// region remembers the camera it was drawn over, so elements can be tested against it at any later time.
+#[derive(Debug, Clone)]
+pub struct SelectionRegion
+{
+	polygon: Vec<glm::Vec2>,
+	viewportDims: glm::UVec2,
+	projView: glm::Mat4,
+
+	/// How the region should be combined with any existing selection.
+	pub mode: SelectionMode
+}
+impl SelectionRegion
+{
+	/// Create a rectangular region spanned by the given corners.
+	///
+	/// # Arguments
+	///
+	/// * `corner0`, `corner1` – Opposing corners of the rectangle, in viewport pixel coordinates.
+	/// * `viewportDims` – The dimensions of the viewport, in pixels.
+	/// * `projView` – The combined `projection`×`view` matrix of the camera the region was drawn over.
+	/// * `mode` – How the region should be combined with any existing selection.
+	pub fn rectangle (
+		corner0: glm::Vec2, corner1: glm::Vec2, viewportDims: glm::UVec2, projView: glm::Mat4, mode: SelectionMode
+	) -> Self {
+		let (min, max) = (glm::min2(&corner0, &corner1), glm::max2(&corner0, &corner1));
+		Self {
+			polygon: vec![min, glm::vec2(max.x, min.y), max, glm::vec2(min.x, max.y)], viewportDims, projView, mode
+		}
+	}
+
+	/// Create a free-form region enclosed by the given polygon, which gets closed implicitly. Self-intersecting
+	/// polygons are handled according to the even-odd rule. See [`Self::rectangle`] for the remaining arguments.
+	pub fn lasso (polygon: Vec<glm::Vec2>, viewportDims: glm::UVec2, projView: glm::Mat4, mode: SelectionMode)
+		-> Self {
+		Self { polygon, viewportDims, projView, mode }
+	}
+
+	/// Reference the vertices of the region boundary, in viewport pixel coordinates.
+	#[inline(always)]
+	pub fn polygon (&self) -> &[glm::Vec2] {
+		&self.polygon
+	}
+
+	/// The dimensions of the viewport the region was drawn in, in pixels.
+	#[inline(always)]
+	pub fn viewportDims (&self) -> glm::UVec2 {
+		self.viewportDims
+	}
+
+	/// The combined `projection`×`view` matrix of the camera the region was drawn over.
+	#[inline(always)]
+	pub fn projView (&self) -> &glm::Mat4 {
+		&self.projView
+	}
+
+	/// Compute the bounding box of the region, in viewport pixel coordinates.
+	pub fn bounds (&self) -> (glm::Vec2, glm::Vec2) {
+		self.polygon.iter().fold(
+			(glm::Vec2::from_element(f32::INFINITY), glm::Vec2::from_element(f32::NEG_INFINITY)),
+			|(min, max), p| (glm::min2(&min, p), glm::max2(&max, p))
+		)
+	}
+
+	/// Check whether the given pixel lies inside the region.
+	pub fn containsPixel (&self, pixel: &glm::Vec2) -> bool
+	{
+		let mut inside = false;
+		let mut j = self.polygon.len().wrapping_sub(1);
+		for (i, a) in self.polygon.iter().enumerate() {
+			let b = &self.polygon[j];
+			if (a.y > pixel.y) != (b.y > pixel.y) && pixel.x < (b.x-a.x) * (pixel.y-a.y) / (b.y-a.y) + a.x {
+				inside = !inside;
+			}
+			j = i;
+		}
+		inside
+	}
+
+	/// Check whether the given point projects into the region. Points outside the depth range of the view frustum
+	/// are never contained.
+	pub fn containsPoint (&self, point: &glm::Vec3) -> bool
+	{
+		let clip = self.projView * glm::vec4(point.x, point.y, point.z, 1.);
+		if clip.w <= 0. || clip.z < 0. || clip.z > clip.w {
+			return false;
+		}
+		let ndc = clip.xy() / clip.w;
+		let dims = glm::vec2(self.viewportDims.x as f32, self.viewportDims.y as f32);
+		self.containsPixel(&glm::vec2(ndc.x*0.5 + 0.5, 0.5 - ndc.y*0.5).component_mul(&dims))
+	}
+
+	/// Determine which elements of the given data project into the region, testing them on the CPU.
+	pub fn selectHost<D: renderer::HostData+?Sized> (&self, data: &D) -> Selection
+	{
+		let mut selection = Selection::new(data.num());
+		if self.polygon.len() < 3 {
+			return selection;
+		}
+		for (index, ref pos) in data.positions().enumerate() {
+			if self.containsPoint(pos) {
+				selection.set(index as u32, true);
+			}
+		}
+		selection
+	}
+
+	/// Determine which elements of the given data project into the region, testing them in a compute kernel on the
+	/// GPU. The positions get uploaded to the GPU for this, so there must not be more elements than fit into a storage
+	/// buffer binding of the device (using 16 bytes per element).
+	///
+	/// # Arguments
+	///
+	/// * `context` – The graphics context.
+	/// * `data` – The data to test.
+	/// * `callback` – Receives the selection once the GPU has finished testing. As with all asynchronous readbacks,
+	///                this happens the next time the device gets polled.
+	pub fn selectGpu_async<D: renderer::HostData+?Sized, Closure: FnOnce(Selection) + wgpu::WasmNotSend + 'static> (
+		&self, context: &Context, data: &D, callback: Closure
+	){
+		// Early-out for trivial cases
+		let num = data.num();
+		if num == 0 || self.polygon.len() < 3 {
+			return callback(Selection::new(num));
+		}
+
+		// Upload everything the kernel needs
+		let createBuffer = |label: &str, size: usize, usage: wgpu::BufferUsages|
+			context.device().create_buffer(&wgpu::BufferDescriptor {
+				label: Some(label), size: size as u64, usage: usage | wgpu::BufferUsages::COPY_DST,
+				mapped_at_creation: false
+			});
+		let (boundsMin, boundsMax) = self.bounds();
+		let params = createBuffer(
+			"CGV__view_selectionParams", size_of::<QueryParams>(), wgpu::BufferUsages::UNIFORM
+		);
+		context.queue().write_buffer(&params, 0, bytemuck::bytes_of(&QueryParams {
+			projView: self.projView,
+			viewportDims: glm::vec2(self.viewportDims.x as f32, self.viewportDims.y as f32),
+			numVertices: self.polygon.len() as u32, numElements: num, boundsMin, boundsMax
+		}));
+		let positions = data.positions().map(|p| glm::vec4(p.x, p.y, p.z, 1.)).collect::<Vec<_>>();
+		let positionsBuffer = createBuffer(
+			"CGV__view_selectionPositions", size_of::<glm::Vec4>() * positions.len(), wgpu::BufferUsages::STORAGE
+		);
+		context.queue().write_buffer(&positionsBuffer, 0, bytemuck::cast_slice(positions.as_slice()));
+		let polygon = createBuffer(
+			"CGV__view_selectionPolygon", size_of::<glm::Vec2>() * self.polygon.len(), wgpu::BufferUsages::STORAGE
+		);
+		context.queue().write_buffer(&polygon, 0, bytemuck::cast_slice(self.polygon.as_slice()));
+		let numWords = num.div_ceil(32);
+		let selected = createBuffer(
+			"CGV__view_selectionResult", size_of::<u32>() * numWords as usize,
+			wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC
+		);
+
+		// Test and read back
+		use gpu::compute::Resource;
+		SELECTION_KERNEL.dispatchAdhoc(context, &[
+			Resource::Buffer(&params), Resource::Buffer(&positionsBuffer), Resource::Buffer(&polygon),
+			Resource::Buffer(&selected)
+		], glm::vec3(numWords, 1, 1));
+		hal::readbackBufferAsync(context, &selected, 0..selected.size(), move |words: &[u32]| {
+			callback(Selection { words: words.to_vec(), num })
+		});
+	}
+
+	/// Determine which elements of the given data project into the region, choosing between
+	/// [testing on the CPU](Self::selectHost) and [on the GPU](Self::selectGpu_async) depending on the size of the
+	/// data (see [`GPU_SELECTION_THRESHOLD`]). When testing on the CPU, the callback gets invoked immediately.
+	pub fn select_async<D: renderer::HostData+?Sized, Closure: FnOnce(Selection) + wgpu::WasmNotSend + 'static> (
+		&self, context: &Context, data: &D, callback: Closure
+	){
+		if data.num() < GPU_SELECTION_THRESHOLD {
+			callback(self.selectHost(data))
+		}
+		else {
+			self.selectGpu_async(context, data, callback)
+		}
+	}
+}
+
+/// A set of selected elements out of a fixed number of elements, stored as a bit set. The words of the bit set can be
+/// uploaded to the GPU as-is for renderers to [highlight](renderer::Spheres::setSelection) the selected elements.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Selection {
+	words: Vec<u32>,
+	num: u32
+}
+impl Selection
+{
+	/// Create an empty selection over the given number of elements.
+	pub fn new (num: u32) -> Self { Self {
+		words: vec![0; num.div_ceil(32) as usize], num
+	}}
+
+	/// The number of elements the selection is over.
+	#[inline(always)]
+	pub fn num (&self) -> u32 {
+		self.num
+	}
+
+	/// Count the selected elements.
+	pub fn count (&self) -> u32 {
+		self.words.iter().map(|word| word.count_ones()).sum()
+	}
+
+	/// Check whether no element is selected.
+	pub fn isEmpty (&self) -> bool {
+		self.words.iter().all(|&word| word == 0)
+	}
+
+	/// Check whether the element with the given index is selected. Indices beyond the [number of elements](Self::num)
+	/// are never selected.
+	#[inline]
+	pub fn contains (&self, index: u32) -> bool {
+		index < self.num && self.words[(index/32) as usize] & (1 << (index%32)) != 0
+	}
+
+	/// Select or deselect the element with the given index. Panics if the index is out of range.
+	#[inline]
+	pub fn set (&mut self, index: u32, selected: bool)
+	{
+		assert!(index < self.num, "element index {index} out of range for selection over {} elements", self.num);
+		let word = &mut self.words[(index/32) as usize];
+		if selected { *word |= 1 << (index%32) }
+		else        { *word &= !(1 << (index%32)) }
+	}
+
+	/// Deselect all elements.
+	pub fn clear (&mut self) {
+		self.words.fill(0);
+	}
+
+	/// Iterate over the indices of all selected elements in ascending order.
+	pub fn iter (&self) -> impl Iterator<Item=u32> + '_ {
+		self.words.iter().enumerate().flat_map(|(w, &word)|
+			(0..32).filter(move |bit| word & (1 << bit) != 0).map(move |bit| w as u32*32 + bit)
+		)
+	}
+
+	/// Reference the words of the bit set, with element *i* corresponding to bit *i mod 32* of word *i / 32*.
+	#[inline(always)]
+	pub fn words (&self) -> &[u32] {
+		&self.words
+	}
+
+	/// Combine the given selection into this one according to the given mode. Both selections must be over the same
+	/// number of elements.
+	pub fn combine (&mut self, other: &Selection, mode: SelectionMode)
+	{
+		assert_eq!(self.num, other.num, "cannot combine selections over different numbers of elements");
+		for (word, &otherWord) in self.words.iter_mut().zip(&other.words) {
+			*word = match mode {
+				SelectionMode::Replace => otherWord,
+				SelectionMode::Add => *word | otherWord,
+				SelectionMode::Subtract => *word & !otherWord
+			};
+		}
+	}
+}
@@ -0,0 +1,419 @@
+//////
+//
+// Imports
+//
+
+// Standard library
+/* nothing here yet */
+
+// Egui library
+use egui;
+
+// Local imports
+use crate::*;
+use view::*;
+use player::{Action, Command};
+use util::math;
+
+
+
+//////
+//
+// Enums
+//
+
+/// The ways in which the [`TrackballInteractor`] maps pointer drags onto rotations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrackballMode
+{
+	/// Rotate around the axis in the image plane perpendicular to the drag direction, by an angle proportional to the
+	/// drag distance. Behaves the same no matter where on the viewport the drag happens.
+	Trackball,
+
+	/// Rotate as if dragging a virtual sphere around the focus point, such that the point under the pointer follows
+	/// it. Dragging along the border of the viewport rolls the view.
+	Arcball
+}
+
+
+
+//////
+//
+// Structs
+//
+
+/// Helper struct to keep track of the ongoing inertial rotation.
+#[derive(Clone, Copy)]
+struct Spin {
+	/// The world-space axis the scene rotates around.
+	axis: glm::Vec3,
+
+	/// The angular velocity of the scene, in radians per second.
+	velocity: f32,
+
+	/// Whether the rotation originates from the current frame, in which case it must not be continued by inertia yet.
+	fresh: bool,
+
+	/// Whether the drag that caused the rotation was released, letting the scene coast on.
+	released: bool
+}
+
+
+
+//////
+//
+// Classes
+//
+
+////
+// TrackballInteractor
+
+/// A camera interactor for free rotation around a focal point without any preferred up direction, suitable for data
+/// that lacks a natural orientation.
+pub struct TrackballInteractor {
+	mode: TrackballMode,
+	dragSensitivity: f32,
+	inertia: bool,
+	damping: f32,
+	dragPosition: Option<glm::Vec2>,
+	prevDragPosition: Option<glm::Vec2>,
+	spin: Option<Spin>,
+	lastSnap: Option<(Command, bool)>,
+	pendingSnap: Option<Command>,
+	focusChange: Option<FocusChange>,
+	redrawRequested: bool
+}
+
+impl TrackballInteractor
+{
+	/// The angular velocity, in radians per second, below which inertial rotation stops.
+	const MIN_SPIN_VELOCITY: f32 = 0.05;
+
+	pub fn new () -> Self { Self {
+		mode: TrackballMode::Trackball,
+		dragSensitivity: 1./3.,
+		inertia: false,
+		damping: 2.,
+		dragPosition: None,
+		prevDragPosition: None,
+		spin: None,
+		lastSnap: None,
+		pendingSnap: None,
+		focusChange: None,
+		redrawRequested: false
+	}}
+
+	/// Rotate the scene around the focus point of the given camera parameters, i.e. rotate the camera in the opposite
+	/// direction.
+	pub(crate) fn rotateScene (params: &mut CameraParameters, axis: &glm::Vec3, angle: f32)
+	{
+		let target = params.extrinsics.eye + params.intrinsics.f*params.extrinsics.dir;
+		let dir = glm::rotate_vec3(&params.extrinsics.dir, -angle, axis).normalize();
+		let up = glm::rotate_vec3(&params.extrinsics.up, -angle, axis);
+		params.extrinsics.dir = dir;
+		params.extrinsics.up = glm::cross(&glm::cross(&dir, &up), &dir).normalize(); // re-orthonormalize
+		params.extrinsics.eye = target - params.intrinsics.f*dir;
+	}
+
+	/// Project the given pixel coordinates within a viewport of the given dimensions onto the virtual arcball,
+	/// using the hyperbolic sheet of *Bell* outside the ball to avoid discontinuities.
+	pub(crate) fn projectOntoBall (position: &glm::Vec2, viewportDims: &glm::Vec2) -> glm::Vec3
+	{
+		let radius = 0.5*viewportDims.x.min(viewportDims.y);
+		let p = glm::vec2(position.x - 0.5*viewportDims.x, 0.5*viewportDims.y - position.y) / radius;
+		let r2 = p.norm_squared();
+		let z = if r2 <= 0.5 { (1. - r2).sqrt() } else { 0.5 / r2.sqrt() };
+		glm::vec3(p.x, p.y, z).normalize()
+	}
+
+	/// Determine the world-space axis and angle of the scene rotation implied by the given drag.
+	fn dragRotation (&self, direction: &glm::Vec2, params: &CameraParameters, viewportDims: &glm::Vec2)
+		-> Option<(glm::Vec3, f32)>
+	{
+		// Find axis and angle in eye space (x right, y up, z towards the viewer)
+		let (axis, angle) = match (self.mode, self.dragPosition)
+		{
+			(TrackballMode::Arcball, Some(position)) => {
+				// The motion of the first drag frame is only known in logical screen points, which we approximate
+				let from = self.prevDragPosition.unwrap_or(position - direction);
+				let from = Self::projectOntoBall(&from, viewportDims);
+				let to = Self::projectOntoBall(&position, viewportDims);
+				(glm::cross(&from, &to), from.dot(&to).clamp(-1., 1.).acos())
+			},
+			_ => (
+				glm::vec3(direction.y, direction.x, 0.),
+				math::deg2rad!(direction.norm()*self.dragSensitivity)
+			)
+		};
+		if axis.norm_squared() < 1e-12 || angle == 0. {
+			return None;
+		}
+
+		// Transform to world space
+		let e = &params.extrinsics;
+		let right = glm::normalize(&glm::cross(&e.dir, &e.up));
+		Some(((axis.x*right + axis.y*e.up - axis.z*e.dir).normalize(), angle))
+	}
+
+	/// Start animating the camera towards the canonical view associated with the given command, keeping the focus
+	/// point. Repeatedly snapping to the same view flips over to the opposite side.
+	///
+	/// # Returns
+	///
+	/// The camera parameters the animation ends at.
+	pub(crate) fn snap (&mut self, command: Command, params: &CameraParameters) -> CameraParameters
+	{
+		let opposite = self.lastSnap.is_some_and(|(last, opposite)| last == command && !opposite);
+		let sign = if opposite { -1. } else { 1. };
+		let (dir, up) = match command {
+			Command::ViewFront => (glm::vec3(0., 0., -sign), glm::vec3(0., 1., 0.)),
+			Command::ViewSide => (glm::vec3(-sign, 0., 0.), glm::vec3(0., 1., 0.)),
+			_ /* ViewTop */ => (glm::vec3(0., -sign, 0.), glm::vec3(0., 0., -sign))
+		};
+		let mut target = *params;
+		let focus = params.extrinsics.eye + params.intrinsics.f*params.extrinsics.dir;
+		target.extrinsics.dir = dir;
+		target.extrinsics.up = up;
+		target.extrinsics.eye = focus - params.intrinsics.f*dir;
+		let mut focusChange = FocusChange::new(params, 0.5);
+		focusChange.setNewParameters(params, &target);
+		self.focusChange = Some(focusChange);
+		self.spin = None;
+		self.lastSnap = Some((command, opposite));
+		target
+	}
+
+	/// Abort any ongoing automatic camera movement in favor of direct user control.
+	#[inline(always)]
+	fn stopMoving (&mut self) {
+		self.focusChange = None;
+		self.spin = None;
+	}
+}
+
+impl CameraInteractor for TrackballInteractor
+{
+	fn title (&self) -> &str {
+		"Trackball"
+	}
+
+	fn update (&mut self, player: &mut Player, _: player::Handle)
+	{
+		// Remember where the pointer was dragged to for arcball rotations in the next frame
+		self.prevDragPosition = self.dragPosition.take();
+
+		// Snap to views requested from the GUI
+		if let Some(command) = self.pendingSnap.take() {
+			self.snap(command, player.camera.parameters());
+		}
+
+		// Advance automatic movement
+		let dt = player.lastFrameTime();
+		if let Some(focusChange) = &mut self.focusChange
+			&& focusChange.update(dt, player.camera.parameters_mut())
+		{
+			self.focusChange = None;
+		}
+		if let Some(spin) = &mut self.spin
+		{
+			let fresh = std::mem::replace(&mut spin.fresh, false);
+			if !spin.released {
+				// Only start coasting once the drag is released, and forget the spin whenever the pointer is held
+				// still so that letting go afterwards doesn't set the scene in motion
+				if player.egui.input(|input| input.pointer.any_down()) {
+					if !fresh {
+						self.spin = None;
+					}
+				}
+				else {
+					spin.released = true;
+				}
+			}
+			else if !self.inertia || spin.velocity < Self::MIN_SPIN_VELOCITY {
+				self.spin = None;
+			}
+			else {
+				Self::rotateScene(player.camera.parameters_mut(), &spin.axis, spin.velocity*dt);
+				spin.velocity *= (-self.damping*dt).exp();
+			}
+		}
+
+		// Keep the scene redrawing continuously for as long as we're moving it on our own
+		let moving = self.focusChange.is_some() || self.spin.is_some_and(|spin| spin.released);
+		if moving != self.redrawRequested {
+			if moving {
+				player.pushContinuousRedrawRequest();
+			}
+			else {
+				player.dropContinuousRedrawRequest();
+			}
+			self.redrawRequested = moving;
+		}
+	}
+
+	fn input (&mut self, event: &InputEvent, player: &mut Player, handle: player::Handle) -> EventOutcome
+	{
+		match event
+		{
+			// Keep track of the pointer for arcball rotations, but leave the drag to the derived actions
+			InputEvent::Dragged(info) => {
+				self.dragPosition = Some(info.position);
+				EventOutcome::NotHandled
+			},
+
+			// View snapping keys
+			InputEvent::Key(info) if info.pressed && !info.repeat => {
+				let bindings = player.bindings();
+				let command = [Command::ViewFront, Command::ViewSide, Command::ViewTop].into_iter().find(|&command|
+					bindings.matchesKey(command, info.key, info.physicalKey, Some(&info.modifiers))
+				);
+				if let Some(command) = command {
+					self.snap(command, player.camera.parameters());
+					EventOutcome::HandledExclusively(/* redraw */true)
+				}
+				else {
+					EventOutcome::NotHandled
+				}
+			},
+
+			// We only borrow the camera parameters inside a scope where we're sure we'll be changing something, as
+			// the camera usually recalculates internal state after a mutable borrow
+			InputEvent::Action(info) => match info.action
+			{
+				Action::Rotate(direction)
+				=> {
+					let dims = player.camera.framebuffer().dims();
+					let dims = glm::vec2(dims.x as f32, dims.y as f32);
+					let Some((axis, angle)) = self.dragRotation(&direction, player.camera.parameters(), &dims) else {
+						return EventOutcome::HandledExclusively(/* redraw */false);
+					};
+					self.stopMoving();
+					Self::rotateScene(player.camera.parameters_mut(), &axis, angle);
+					if self.inertia {
+						let dt = player.lastFrameTime().max(1./240.);
+						self.spin = Some(Spin { axis, velocity: angle/dt, fresh: true, released: false });
+					}
+					self.lastSnap = None;
+					EventOutcome::HandledExclusively(/* redraw */true)
+				},
+
+				Action::Roll(_) | Action::DragRoll(_)
+				=> {
+					self.stopMoving();
+					let angle = info.action.rollAngle(0.75*self.dragSensitivity);
+					let p = player.camera.parameters_mut();
+					p.extrinsics.up = glm::rotate_vec3(&p.extrinsics.up, -angle, &p.extrinsics.dir);
+					self.lastSnap = None;
+					EventOutcome::HandledExclusively(/* redraw */true)
+				},
+
+				Action::Pan(direction)
+				=> {
+					self.stopMoving();
+					let delta = glm::vec2(-direction.x, direction.y);
+					let p = player.camera.parameters_mut();
+					let speed = p.intrinsics.f * delta*1./192.*self.dragSensitivity;
+					let right = glm::cross(&p.extrinsics.dir, &p.extrinsics.up);
+					p.extrinsics.eye += speed.x*right + speed.y*p.extrinsics.up;
+					EventOutcome::HandledExclusively(/* redraw */true)
+				},
+
+				Action::Dolly(amount)
+				=> {
+					self.stopMoving();
+					let p = player.camera.parameters_mut();
+					p.extrinsics.eye += p.intrinsics.f*amount*1./96.*self.dragSensitivity * p.extrinsics.dir;
+					EventOutcome::HandledExclusively(/* redraw */true)
+				},
+
+				Action::Zoom(factor)
+				=> {
+					player.camera.parameters_mut().zoomBy(factor);
+					EventOutcome::HandledExclusively(/* redraw */true)
+				},
+
+				Action::FieldOfView(angle)
+				=> {
+					// `adjustFovBy` expects the amount in eighths of a degree
+					player.camera.parameters_mut().adjustFovBy(math::rad2deg!(angle)*8., math::deg2rad!(5.));
+					EventOutcome::HandledExclusively(/* redraw */true)
+				},
+
+				Action::Focus(position)
+				=> {
+					let mut focusChange = FocusChange::new(player.camera.parameters(), 0.5);
+					player.unprojectPointAtSurfacePixel_async(position, move |point| {
+						let Some(point) = point else {return};
+
+						tracing::debug!("Focus action to new focus: {:?}", point);
+						focusChange.setNewFocus(point);
+						let mut lock = player::lock();
+						let player = &mut*lock;
+						let this = player.cameraInteractors.get_mut::<Self>(handle);
+						this.focusChange = Some(focusChange);
+						this.spin = None;
+						if !std::mem::replace(&mut this.redrawRequested, true) {
+							player.state.pushContinuousRedrawRequest();
+						}
+					});
+					EventOutcome::HandledExclusively(/* redraw */true)
+				},
+			},
+
+			_ => EventOutcome::NotHandled
+		}
+	}
+
+	fn ui (&mut self, _: &mut dyn Camera, ui: &mut egui::Ui)
+	{
+		// Put the UI inside a standard ControlTable
+		gui::layout::ControlTableLayouter::new(ui).layout(ui, "CGV__tbint", |trackballUi|
+		{
+			// mode
+			trackballUi.add("mode", |ui, idealSize|
+				egui::ComboBox::from_id_salt("CGV__tbint_mode")
+					.selected_text(format!("{:?}", self.mode))
+					.width(idealSize)
+					.show_ui(ui, |ui| {
+						ui.selectable_value(&mut self.mode, TrackballMode::Trackball, "Trackball");
+						ui.selectable_value(&mut self.mode, TrackballMode::Arcball, "Arcball");
+					})
+			);
+
+			// inertia
+			trackballUi.add("inertia", |ui, _| ui.horizontal(|ui| {
+				ui.checkbox(&mut self.inertia, "");
+				ui.add_enabled(self.inertia, egui::Slider::new(&mut self.damping, 0.25..=8.)
+					.logarithmic(true)
+					.text("damping")
+				);
+			}));
+
+			// Actions: snap to canonical views
+			trackballUi.add("view", |ui, _| ui.horizontal(|ui| {
+				for (command, label) in [
+					(Command::ViewFront, "front"), (Command::ViewSide, "side"), (Command::ViewTop, "top")
+				]{
+					if ui.button(label).on_hover_text("Click again for the opposite side").clicked() {
+						self.pendingSnap = Some(command);
+					}
+				}
+			}));
+
+			// dragSensitivity
+			trackballUi.add("drag sensitivity", |ui, _| ui.add(
+				egui::Slider::new(&mut self.dragSensitivity, 0.03125..=2.)
+					.clamping(egui::SliderClamping::Always)
+			));
+		});
+	}
+}
+
+impl Drop for TrackballInteractor {
+	fn drop (&mut self) {
+		// Make sure we let go of our continuous redraw request
+		if self.redrawRequested {
+			player::lock().dropContinuousRedrawRequest();
+		}
+	}
+}
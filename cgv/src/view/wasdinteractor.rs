@@ -13,6 +13,7 @@ use egui;
 // Local imports
 use crate::*;
 use view::*;
+use player::{Action, Command};
 use util::math;
 
 
@@ -51,9 +52,12 @@ impl ContinuousRedrawing
 ////
 // OrbitCamera
 
-/// A camera interactor for orbital movement around a focal point.
+/// A camera interactor for first-person movement using the keyboard. By default, the [pan](Action::Pan) chord (the
+/// secondary drag) looks around while [rotate](Action::Rotate) is left alone. Enabling
+/// [`setLookOnRotate`](WASDInteractor::setLookOnRotate) looks around on rotate instead and pans on the pan chord.
 pub struct WASDInteractor {
 	dragSensitivity: f32,
+	lookOnRotate: bool,
 	movementSpeedFactor: f32,
 	slowFactor: f32,
 	referenceUp: glm::Vec3,
@@ -73,6 +77,7 @@ impl WASDInteractor
 
 	pub fn new () -> Self { Self {
 		dragSensitivity: 1./3.,
+		lookOnRotate: false,
 		movementSpeedFactor: 1.,
 		slowFactor: 0.25,
 		referenceUp: glm::vec3(0., 1., 0.),
@@ -81,6 +86,27 @@ impl WASDInteractor
 		continuousRedrawing: ContinuousRedrawing::Idle,
 	}}
 
+	/// Set whether to look around on [rotate](Action::Rotate) and pan on [pan](Action::Pan), instead of looking around
+	/// on pan.
+	pub fn setLookOnRotate (&mut self, enabled: bool) {
+		self.lookOnRotate = enabled;
+	}
+
+	/// Turn the view direction following a pointer motion in the given direction, keeping the reference up direction.
+	fn lookAround (&self, p: &mut CameraParameters, direction: &glm::Vec2)
+	{
+		let delta = glm::vec2(-direction.x, direction.y);
+		let mut newDir = glm::rotate_vec3(
+			&p.extrinsics.dir, math::deg2rad!(delta.x*self.dragSensitivity), &self.referenceUp
+		);
+		let right = glm::normalize(&glm::cross(&newDir, &self.referenceUp));
+		newDir = glm::rotate_vec3(
+			&newDir, math::deg2rad!(delta.y*-self.dragSensitivity), &right
+		);
+		p.extrinsics.dir = newDir;
+		p.extrinsics.up = glm::cross(&right, &p.extrinsics.dir);
+	}
+
 	#[inline(always)]
 	fn anyMovementKeyPressed (&self) -> bool {
 		self.moving.iter().any(|state| *state)
@@ -168,118 +194,110 @@ impl CameraInteractor for WASDInteractor
 			InputEvent::Key(info)
 			=> {
 				// Handle slow modifier
-				let noMoveOutcome = if self.slow != info.modifiers.shift {
+				let bindings = player.bindings();
+				let slow = bindings.modifiersHeld(Command::MoveSlowly, &info.modifiers);
+				let noMoveOutcome = if self.slow != slow {
 					EventOutcome::HandledDontClose(false)
 				} else {
 					EventOutcome::NotHandled
 				};
-				self.slow = info.modifiers.shift;
-
-				// React to movement keys
-				if !info.repeat { match info.key
-				{
-					egui::Key::W
-					=> EventOutcome::HandledExclusively(updateKeyFlag(self, Self::FORE, info.pressed, player)),
-
-					egui::Key::A
-					=> EventOutcome::HandledExclusively(updateKeyFlag(self, Self::LEFT, info.pressed, player)),
-
-					egui::Key::S
-					=> EventOutcome::HandledExclusively(updateKeyFlag(self, Self::BACK, info.pressed, player)),
-
-					egui::Key::D
-					=> EventOutcome::HandledExclusively(updateKeyFlag(self, Self::RIGHT, info.pressed, player)),
-
-					egui::Key::Q
-					=> EventOutcome::HandledExclusively(updateKeyFlag(self, Self::DOWN, info.pressed, player)),
-
-					egui::Key::E
-					=> EventOutcome::HandledExclusively(updateKeyFlag(self, Self::UP, info.pressed, player)),
-
-					_ => noMoveOutcome
-				}}
+				self.slow = slow;
+
+				// React to movement keys – modifiers only need to match when pressing, so that keys get released
+				// regardless of which modifiers changed in the meantime
+				if !info.repeat {
+					let modifiers = info.pressed.then_some(&info.modifiers);
+					let directionId = [
+						(Command::MoveForward, Self::FORE), (Command::MoveLeft, Self::LEFT),
+						(Command::MoveBack, Self::BACK), (Command::MoveRight, Self::RIGHT),
+						(Command::MoveDown, Self::DOWN), (Command::MoveUp, Self::UP)
+					].into_iter().find_map(|(command, directionId)|
+						bindings.matchesKey(command, info.key, info.physicalKey, modifiers).then_some(directionId)
+					);
+					match directionId {
+						Some(directionId)
+						=> EventOutcome::HandledExclusively(updateKeyFlag(self, directionId, info.pressed, player)),
+						None => noMoveOutcome
+					}
+				}
 				else {
 					noMoveOutcome
 				}
 			},
 
-			InputEvent::Dragged(info)
-			=> {
-				// Adapt dragged delta
-				let delta = glm::vec2(-info.direction.x, info.direction.y);
-
+			InputEvent::Action(info) => match info.action
+			{
 				// We only borrow the camera parameters inside a scope where we're sure we'll be changing something,
 				// as the camera usually recalculates internal state after a mutable borrow
-				if info.button(egui::PointerButton::Primary) && info.modifiers.shift
-				{
+				Action::Roll(_) | Action::DragRoll(_)
+				=> {
+					let angle = info.action.rollAngle(0.75*self.dragSensitivity);
 					let p = player.camera.parameters_mut();
-					self.referenceUp = glm::rotate_vec3(
-						&p.extrinsics.up, math::deg2rad!(delta.y*-0.75*self.dragSensitivity),
-						&p.extrinsics.dir
-					);
+					self.referenceUp = glm::rotate_vec3(&p.extrinsics.up, -angle, &p.extrinsics.dir);
 					p.extrinsics.up = self.referenceUp;
 					EventOutcome::HandledExclusively(/* redraw */true)
-				}
-				else if info.button(egui::PointerButton::Secondary)
-				{
+				},
+
+				Action::Rotate(direction) if self.lookOnRotate
+				=> {
+					self.lookAround(player.camera.parameters_mut(), &direction);
+					EventOutcome::HandledExclusively(/* redraw */true)
+				},
+
+				Action::Pan(direction) if !self.lookOnRotate
+				=> {
+					self.lookAround(player.camera.parameters_mut(), &direction);
+					EventOutcome::HandledExclusively(/* redraw */true)
+				},
+
+				Action::Pan(direction)
+				=> {
+					let delta = glm::vec2(-direction.x, direction.y);
 					let p = player.camera.parameters_mut();
-					let mut newDir = glm::rotate_vec3(
-						&p.extrinsics.dir, math::deg2rad!(delta.x*self.dragSensitivity), &self.referenceUp
-					);
-					let right = glm::normalize(&glm::cross(&newDir, &self.referenceUp));
-					newDir = glm::rotate_vec3(
-						&newDir, math::deg2rad!(delta.y*-self.dragSensitivity), &right
-					);
-					p.extrinsics.dir = newDir;
-					p.extrinsics.up = glm::cross(&right, &p.extrinsics.dir);
+					let speed = p.intrinsics.f * delta*1./192.*self.dragSensitivity;
+					let right = glm::cross(&p.extrinsics.dir, &p.extrinsics.up);
+					p.extrinsics.eye += speed.x*right + speed.y*p.extrinsics.up;
 					EventOutcome::HandledExclusively(/* redraw */true)
-				}
-				else {
-					EventOutcome::NotHandled
-				}
-			},
+				},
 
-			InputEvent::MouseWheel(info)
-			=> {
-				if info.amount.y != 0. {
-					if info.modifiers.alt {
-						// We only borrow the camera parameters inside a scope where we're sure we'll be changing
-						// something, as the camera usually recalculates internal state after a mutable borrow
-						let params = player.camera.parameters_mut();
-						params.adjustFovBy(info.amount.y, math::deg2rad!(5.));
-					} else {
-						// We only borrow the camera parameters inside a scope where we're sure we'll be changing
-						// something, as the camera usually recalculates internal state after a mutable borrow
-						let params = player.camera.parameters_mut();
-						params.adjustZoom(info.amount.y);
-					}
+				Action::Zoom(factor)
+				=> {
+					player.camera.parameters_mut().zoomBy(factor);
 					EventOutcome::HandledExclusively(/* redraw */true)
-				}
-				else {
-					EventOutcome::NotHandled
-				}
-			},
+				},
 
-			InputEvent::DoubleClick(info)
-			=> {
-				if let ContinuousRedrawing::WASD = self.continuousRedrawing {break 'event EventOutcome::NotHandled}
-				let mut focusChange = FocusChange::new(player.camera.parameters(), 0.5);
-				player.unprojectPointAtSurfacePixel_async(info.position, move |point| {
-					let Some(point) = point else {return};
-
-					tracing::debug!("Double-click to new focus: {:?}", point);
-					let mut lock = player::lock();
-					let player = &mut*lock;
-					let this = player.cameraInteractors.get_mut::<Self>(handle);
-
-					if this.continuousRedrawing.notChangingFocus() {
-						// Re-use the ongoing continuous redraw request if a focus change was in progress before
-						player.state.pushContinuousRedrawRequest();
+				Action::FieldOfView(angle)
+				=> {
+					// `adjustFovBy` expects the amount in eighths of a degree
+					player.camera.parameters_mut().adjustFovBy(math::rad2deg!(angle)*8., math::deg2rad!(5.));
+					EventOutcome::HandledExclusively(/* redraw */true)
+				},
+
+				Action::Focus(position)
+				=> {
+					if let ContinuousRedrawing::WASD = self.continuousRedrawing {
+						break 'event EventOutcome::NotHandled
 					}
-					focusChange.setNewFocus(point);
-					this.continuousRedrawing = ContinuousRedrawing::FocusChange(focusChange);
-				});
-				EventOutcome::HandledExclusively(/* redraw */true)
+					let mut focusChange = FocusChange::new(player.camera.parameters(), 0.5);
+					player.unprojectPointAtSurfacePixel_async(position, move |point| {
+						let Some(point) = point else {return};
+
+						tracing::debug!("Focus action to new focus: {:?}", point);
+						let mut lock = player::lock();
+						let player = &mut*lock;
+						let this = player.cameraInteractors.get_mut::<Self>(handle);
+
+						if this.continuousRedrawing.notChangingFocus() {
+							// Re-use the ongoing continuous redraw request if a focus change was in progress before
+							player.state.pushContinuousRedrawRequest();
+						}
+						focusChange.setNewFocus(point);
+						this.continuousRedrawing = ContinuousRedrawing::FocusChange(focusChange);
+					});
+					EventOutcome::HandledExclusively(/* redraw */true)
+				},
+
+				_ => EventOutcome::NotHandled
 			},
 
 			_ => EventOutcome::NotHandled
@@ -301,6 +319,9 @@ impl CameraInteractor for WASDInteractor
 				params.extrinsics.up = right.cross(&params.extrinsics.dir).normalize();
 			}
 
+			// lookOnRotate
+			wasdUi.add("look on rotate", |ui, _| ui.checkbox(&mut self.lookOnRotate, ""));
+
 			// movementSpeedFactor
 			wasdUi.add("speed mult.", |ui, _| ui.add(
 				egui::Slider::new(&mut self.movementSpeedFactor, 0.03125..=4.)
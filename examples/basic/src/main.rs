@@ -24,7 +24,7 @@ use cgv::{wgpu, glm, egui, tracing};
 use wgpu::util::DeviceExt;
 
 // CGV-rs Framework
-use cgv::{self, util};
+use cgv::{self, util, hal::VertexLayout};
 
 
 
@@ -92,29 +92,17 @@ const INDICES: &[u32; 10] = &[/*front*/0, 1, 2, 3,  /*degen*/3, 5,  /*back*/5, 4
 // QuadVertex
 
 #[repr(C)]
-#[derive(Copy, Clone, Debug, bytemuck::NoUninit)]
+#[derive(Copy, Clone, Debug, bytemuck::NoUninit, cgv::hal::VertexLayout)]
 struct QuadVertex {
 	pos: glm::Vec4,
 	color: glm::Vec4,
 	texcoord: glm::Vec2,
-	pad: [u32; 2]
+	#[cgv_vertexAttr(skip)] pad: [u32; 2]
 }
-impl QuadVertex
-{
-	const GPU_ATTRIBS: [wgpu::VertexAttribute; 3] =
-		wgpu::vertex_attr_array![0=>Float32x4, 1=>Float32x4, 2=>Float32x2];
-
+impl QuadVertex {
 	const fn new (pos: glm::Vec4, color: glm::Vec4, texcoord: glm::Vec2) -> Self {
 		Self{pos, color, texcoord, pad: [0; 2]}
 	}
-
-	fn layoutDesc () -> wgpu::VertexBufferLayout<'static> {
-		wgpu::VertexBufferLayout {
-			array_stride: size_of::<Self>() as wgpu::BufferAddress,
-			step_mode: wgpu::VertexStepMode::Vertex,
-			attributes: &Self::GPU_ATTRIBS,
-		}
-	}
 }
 
 
@@ -217,54 +205,18 @@ fn createBasicExampleApp (context: &cgv::Context, _: &cgv::RenderSetup, environm
 	);
 
 	// Texture uniform
-	static TEX_BINDGROUP_LAYOUT_ENTRIES: [wgpu::BindGroupLayoutEntry; 2] = [
-		wgpu::BindGroupLayoutEntry {
-			binding: 0,
-			visibility: wgpu::ShaderStages::FRAGMENT,
-			ty: wgpu::BindingType::Texture {
-				multisampled: false,
-				view_dimension: wgpu::TextureViewDimension::D2,
-				sample_type: wgpu::TextureSampleType::Float { filterable: true },
-			},
-			count: None,
-		},
-		wgpu::BindGroupLayoutEntry {
-			binding: 1,
-			visibility: wgpu::ShaderStages::FRAGMENT,
-			ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
-			count: None,
-		},
-	];
-	let texBindGroupLayout = context.device().create_bind_group_layout(
-		&wgpu::BindGroupLayoutDescriptor {
-			entries: TEX_BINDGROUP_LAYOUT_ENTRIES.as_slice(),
-			label: Some("ExBasic__TexBindGroupLayout"),
-		}
-	);
-	let texBindGroup = context.device().create_bind_group(
-		&wgpu::BindGroupDescriptor {
-			layout: &texBindGroupLayout,
-			entries: &[
-				wgpu::BindGroupEntry {
-					binding: 0,
-					resource: wgpu::BindingResource::TextureView(&tex.view()),
-				},
-				wgpu::BindGroupEntry {
-					binding: 1,
-					resource: wgpu::BindingResource::Sampler(&context.refSampler(
-						&wgpu::SamplerDescriptor {
-							mag_filter: wgpu::FilterMode::Linear,
-							min_filter: wgpu::FilterMode::Linear,
-							mipmap_filter: wgpu::MipmapFilterMode::Linear,
-							anisotropy_clamp: 16,
-							..Default::default()
-						}
-					)),
-				}
-			],
-			label: Some("ExBasic__TexBindGroup"),
-		}
-	);
+	let texBindGroup = cgv::hal::TextureBindGroup::create(
+		context, wgpu::ShaderStages::FRAGMENT, &[cgv::hal::SampledTexture::withSampler(
+			&tex, wgpu::SamplerDescriptor {
+				mag_filter: wgpu::FilterMode::Linear,
+				min_filter: wgpu::FilterMode::Linear,
+				mipmap_filter: wgpu::MipmapFilterMode::Linear,
+				anisotropy_clamp: 16,
+				..Default::default()
+			}
+		)],
+		Some("ExBasic__Tex")
+	)?;
 
 
 	////
@@ -286,7 +238,7 @@ fn createBasicExampleApp (context: &cgv::Context, _: &cgv::RenderSetup, environm
 
 	// Construct the instance and put it in a box
 	Ok(Box::new(ExampleApplication {
-		shader, appearanceUniforms, texBindGroupLayout, texBindGroup, vertexBuffer, indexBuffer, guiState,
+		shader, appearanceUniforms, texBindGroup, vertexBuffer, indexBuffer, guiState,
 		pipelines: Vec::new(), // <- delayed, *CGV-rs* has a dedicated cycle for this as typically we don't have all
 	}))                        //    required information at this point, like viewport dimensions
 }
@@ -315,8 +267,7 @@ struct ExampleApplication
 	// Rendering related
 	shader: wgpu::ShaderModule,
 	appearanceUniforms: AppearanceUniformGroup,
-	texBindGroupLayout: wgpu::BindGroupLayout,
-	texBindGroup: wgpu::BindGroup,
+	texBindGroup: cgv::hal::TextureBindGroup,
 	pipelines: Vec<wgpu::RenderPipeline>,
 	vertexBuffer: wgpu::Buffer,
 	indexBuffer: wgpu::Buffer,
@@ -340,7 +291,7 @@ impl ExampleApplication
 				label: Some("ExBasic__RenderPipelineLayout"),
 				bind_group_layouts: &[
 					Some(&renderSetup.bindGroupLayouts().viewing), Some(&self.appearanceUniforms.bindGroupLayout),
-					Some(&self.texBindGroupLayout)
+					Some(&self.texBindGroup.bindGroupLayout)
 				],
 				immediate_size: 0
 			});
@@ -356,9 +307,9 @@ impl ExampleApplication
 			fragment: Some(wgpu::FragmentState {
 				module: &self.shader,
 				entry_point: Some("fragmentMain"), // Slang (for now) requires explicitly stating entry points
-				targets: &[Some(cgv::renderstate::changeColorTargetState_blending(
+				targets: &renderState.colorTargetStates(cgv::renderstate::changeColorTargetState_blending(
 					renderState.colorTargetState(), cgv::renderstate::BlendingOperation::AlphaPreMultiplied
-				))],
+				), /* writeIds: */false),
 				compilation_options: wgpu::PipelineCompilationOptions::default(),
 			}),
 			primitive: wgpu::PrimitiveState {
@@ -453,7 +404,7 @@ impl cgv::Application for ExampleApplication
 		renderPass.set_pipeline(&self.pipelines[0]);
 		renderPass.set_bind_group(0, &renderState.viewingUniforms.bindGroup, &[]);
 		renderPass.set_bind_group(1, &self.appearanceUniforms.bindGroup, &[]);
-		renderPass.set_bind_group(2, &self.texBindGroup, &[]);
+		renderPass.set_bind_group(2, &self.texBindGroup.bindGroup, &[]);
 		renderPass.set_vertex_buffer(0, self.vertexBuffer.slice(..));
 		renderPass.set_index_buffer(self.indexBuffer.slice(..), wgpu::IndexFormat::Uint32);
 		renderPass.draw_indexed(
@@ -554,6 +505,11 @@ impl cgv::Application for ExampleApplication
 			})
 		);
 	}
+
+	fn sceneBounds (&self) -> Option<cgv::util::math::Aabb> {
+		// Our quad never changes, so we can just compute its bounds on the fly
+		cgv::util::math::Aabb::fromPoints(QUAD_VERTS.iter().map(|vertex| vertex.pos.xyz()))
+	}
 }
 
 
@@ -115,13 +115,18 @@ fn createRenderersDemo (context: &cgv::Context, renderSetup: &cgv::RenderSetup,
 /// constructed.
 #[derive(Default)]
 struct TestData {
-	samples: Vec<DataPoint>
+	samples: Vec<DataPoint>,
+	bounds: Option<cgv::util::math::Aabb>,
+	maxRadius: f32
 }
 impl TestData
 {
 	/// (Re-)generate the test data and upload to a new [`InterleavedBuffer`](renderer::data::InterleavedBuffer).
 	fn regenerateData (&mut self, context: &cgv::Context, num: usize) -> Arc<renderer::data::InterleavedBuffer> {
 		regenerateData(&mut self.samples, num);
+		// Cache the bounds of the sphere centers, as the player asks for them every frame
+		self.bounds = renderer::data::host::Data::bounds(&self.samples);
+		self.maxRadius = self.samples.iter().fold(0., |max, sample| sample.radius.max(max));
 		renderer::data::InterleavedBuffer::fromHost(
 			context, &self.samples, /* options: */Default::default(), Some("RenderersDemo_spheresData")
 		)
@@ -328,6 +333,14 @@ impl cgv::Application for RenderersDemo
 			})
 		);
 	}
+
+	fn sceneBounds (&self) -> Option<cgv::util::math::Aabb>
+	{
+		// Grow the bounds of the sphere centers by the largest radius that will actually get rendered
+		let radius = if self.guiState.radiiFromData { self.testData.maxRadius } else { self.guiState.defaultRadius };
+		let margin = glm::Vec3::repeat(radius*self.guiState.radiusScale);
+		self.testData.bounds.map(|bounds| cgv::util::math::Aabb::new(bounds.min-margin, bounds.max+margin))
+	}
 }
 
 
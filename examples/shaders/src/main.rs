@@ -25,7 +25,7 @@ mod slang_completer;
 //
 
 // Standard library
-use std::default::Default;
+use std::{default::Default, collections::BTreeSet, ops::Range, path::PathBuf};
 
 // CGV re-imports
 use cgv::{wgpu, glm, egui, tracing};
@@ -133,7 +133,8 @@ fn createOnlineShadersDemo (_: &cgv::Context, _: &cgv::RenderSetup, environment:
 
 	// Construct the instance and put it in a box
 	Ok(Box::new(OnlineShadersDemo {
-		statusText: "<STATUS UNKNOWN>".into(), mainModule, slangCtx, userShaderCode, pipelines: Vec::new(),
+		statusText: "<STATUS UNKNOWN>".into(), mainModule, slangCtx, userShaderCode, userShaderPath: PathBuf::new(),
+		errorLines: BTreeSet::new(), pipelines: Vec::new(),
 		shader: cgv::util::LaterInit::uninit(), highlighterSettings, syntaxCompleter, guiState: Default::default()
 	}))
 }
@@ -150,6 +151,8 @@ struct OnlineShadersDemo
 	slangCtx: cgv::shader::slang::Context<'static>,
 	mainModule: cgv::shader::slang::Module<'static>,
 	userShaderCode: String,
+	userShaderPath: PathBuf,
+	errorLines: BTreeSet<u32>,
 
 	// Rendering related
 	shader: cgv::util::LaterInit<wgpu::ShaderModule>,
@@ -189,7 +192,7 @@ impl OnlineShadersDemo
 			fragment: Some(wgpu::FragmentState {
 				module: &self.shader,
 				entry_point: Some("fragmentMain"), // Slang (for now) requires explicitly stating entry points
-				targets: &[Some(renderState.colorTargetState().clone())],
+				targets: &renderState.colorTargetStates(renderState.colorTargetState().clone(), /* writeIds: */false),
 				compilation_options: wgpu::PipelineCompilationOptions::default(),
 			}),
 			primitive: wgpu::PrimitiveState {
@@ -210,7 +213,9 @@ impl OnlineShadersDemo
 	-> Result<&'static str, cgv::shader::compile::CompileOrBuildError> {
 		// Load our concrete `IGlyph`-implementing type that our "sdf_demo.slang" module expects into the context
 		tracing::info!("(Re-)building shader: specializing for custom glyph");
-		let glyphModule = self.slangCtx.compileFromSource(&self.userShaderCode)?;
+		self.errorLines.clear();
+		self.userShaderPath = format!("user_glyph_{}.slang", cgv::shader::uniqueAnonymousName()).into();
+		let glyphModule = self.slangCtx.compileFromNamedSource(&self.userShaderPath, &self.userShaderCode)?;
 
 		// Link into usable program
 		tracing::info!("(Re-)building shader: linking final program");
@@ -237,6 +242,32 @@ impl OnlineShadersDemo
 		// Done!
 		Ok("Code OK.")
 	}
+
+	/// Helper function: turn a failed shader rebuild into a status message, remembering which lines of the user code
+	/// the compiler complained about so the editor can underline them.
+	fn reportBuildError (&mut self, err: &cgv::shader::compile::CompileOrBuildError) -> String
+	{
+		tracing::error!("{err}");
+		let Some(diagnostics) = err.diagnostics() else {
+			return format!("{err}");
+		};
+		self.errorLines = diagnostics.inFile(&self.userShaderPath).filter(|d| d.isError()).filter_map(
+			|d| d.line
+		).collect();
+		diagnostics.iter().map(|d|
+			if d.isInFile(&self.userShaderPath) {
+				// Refer to lines in the user code without the meaningless internal module name
+				match (d.line, d.code) {
+					(Some(line), Some(code)) => format!("line {line}: {} {code}: {}", d.severity, d.message),
+					(Some(line), None) => format!("line {line}: {}: {}", d.severity, d.message),
+					_ => format!("{}: {}", d.severity, d.message)
+				}
+			}
+			else {
+				d.to_string()
+			}
+		).collect::<Vec<_>>().join("\n")
+	}
 }
 impl cgv::Application for OnlineShadersDemo
 {
@@ -385,6 +416,10 @@ impl cgv::Application for OnlineShadersDemo
 									"hlsl", // <- wait until there is a sublime-text theme for Slang
 									&self.highlighterSettings
 								);
+								underlineLines(
+									&mut layoutJob, text.as_str(), &self.errorLines,
+									egui::Stroke::new(1., egui::Color32::RED)
+								);
 								layoutJob.wrap.max_width = wrapWidth;
 								ui.fonts_mut(|f| f.layout_job(layoutJob))
 							};
@@ -419,13 +454,10 @@ impl cgv::Application for OnlineShadersDemo
 										statusText.into()
 									},
 
-									Err(  CompileOrBuildError::CompilationError(err)
-									    | CompileOrBuildError::CreateCompositeError(err)
-									    | CompileOrBuildError::LinkError(err))
-									=> {
-										tracing::error!("{err}");
-										format!("{err}")
-									},
+									Err(err @ (  CompileOrBuildError::CompilationError(_)
+									           | CompileOrBuildError::CreateCompositeError(_)
+									           | CompileOrBuildError::LinkError(_)))
+									=> self.reportBuildError(&err),
 
 									Err(  CompileOrBuildError::DuplicateModulePaths(_)
 									    | CompileOrBuildError::InvalidModulePath(_))
@@ -464,6 +496,45 @@ unsafe impl Send for OnlineShadersDemo {}
 // Functions
 //
 
+/// Underline the given (1-based) lines in a laid-out text, splitting sections where they straddle line boundaries.
+fn underlineLines (layoutJob: &mut egui::text::LayoutJob, text: &str, lines: &BTreeSet<u32>, stroke: egui::Stroke)
+{
+	// Determine byte ranges of the lines to underline
+	if lines.is_empty() {
+		return;
+	}
+	let mut offset = 0;
+	let ranges: Vec<Range<usize>> = text.split_inclusive('\n').enumerate().filter_map(|(idx, line)| {
+		let range = offset..offset+line.trim_end_matches(['\r', '\n']).len();
+		offset += line.len();
+		lines.contains(&(idx as u32 + 1)).then_some(range)
+	}).collect();
+
+	// Split and mark the affected sections
+	let sections = std::mem::take(&mut layoutJob.sections);
+	for section in sections
+	{
+		let mut cuts: Vec<usize> = ranges.iter().flat_map(|r| [r.start, r.end]).filter(
+			|&cut| cut > section.byte_range.start && cut < section.byte_range.end
+		).collect();
+		cuts.push(section.byte_range.end);
+		let mut start = section.byte_range.start;
+		for end in cuts
+		{
+			let mut part = section.clone();
+			if start > section.byte_range.start {
+				part.leading_space = 0.;
+			}
+			if ranges.iter().any(|r| r.start <= start && end <= r.end) {
+				part.format.underline = stroke;
+			}
+			part.byte_range = start..end;
+			layoutJob.sections.push(part);
+			start = end;
+		}
+	}
+}
+
 /// The application entry point.
 pub fn main () -> cgv::Result<()> {
 	// Immediately hand off control flow, passing in a factory for our online shader compilation demo app